@@ -0,0 +1,187 @@
+use std::fmt::Display;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// A transformation of the values before they are placed on an axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AxisTransform {
+    /// Leave the value unchanged.
+    Linear,
+    /// Take the n-th root of the value with `n = degree`.
+    PolynomialRoot { degree: f64 },
+    /// Take the reciprocal of the value, e.g. to convert a duration into a rate.
+    /// The reciprocal of zero is not finite, so such values cannot be plotted.
+    Reciprocal,
+    /// Take the logarithm of the value to the given base, e.g. for values spanning several orders of magnitude.
+    /// The logarithm of zero and of negative values is not finite, so such values cannot be plotted.
+    Log { base: f64 },
+}
+
+/// The kind of an [`AxisTransform`], as selected on the command line or in a recipe.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, ValueEnum, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum AxisTransformKind {
+    Linear,
+    #[default]
+    PolynomialRoot,
+    Reciprocal,
+    Log,
+}
+
+impl AxisTransform {
+    pub fn new(kind: AxisTransformKind, value_polynomial_degree: f64, value_log_base: f64) -> Self {
+        match kind {
+            AxisTransformKind::Linear => Self::Linear,
+            AxisTransformKind::PolynomialRoot => Self::PolynomialRoot {
+                degree: value_polynomial_degree,
+            },
+            AxisTransformKind::Reciprocal => Self::Reciprocal,
+            AxisTransformKind::Log => Self::Log {
+                base: value_log_base,
+            },
+        }
+    }
+
+    /// True if the transform does not change the values.
+    pub fn is_linear(&self) -> bool {
+        matches!(self, Self::Linear | Self::PolynomialRoot { degree: 1.0 })
+    }
+
+    /// True if the transform is a logarithm, and hence undefined at zero.
+    pub fn is_log(&self) -> bool {
+        matches!(self, Self::Log { .. })
+    }
+
+    pub fn apply(&self, value: f64) -> f64 {
+        match self {
+            Self::Linear => value,
+            Self::PolynomialRoot { degree } => value.powf(1.0 / degree),
+            Self::Reciprocal => {
+                if value == 0.0 {
+                    f64::NAN
+                } else {
+                    1.0 / value
+                }
+            }
+            Self::Log { base } => {
+                if value > 0.0 {
+                    value.log(*base)
+                } else {
+                    f64::NAN
+                }
+            }
+        }
+    }
+
+    pub fn apply_inverse(&self, value: f64) -> f64 {
+        match self {
+            Self::Linear => value,
+            Self::PolynomialRoot { degree } => value.powf(*degree),
+            Self::Reciprocal => {
+                if value == 0.0 {
+                    f64::NAN
+                } else {
+                    1.0 / value
+                }
+            }
+            Self::Log { base } => base.powf(value),
+        }
+    }
+}
+
+impl Display for AxisTransform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PolynomialRoot { degree } => write!(f, "{degree}-th root"),
+            Self::Reciprocal => write!(f, "1/x"),
+            Self::Linear => write!(f, "linear"),
+            Self::Log { base } => write!(f, "log{base}"),
+        }
+    }
+}
+
+/// How zero values are placed on a logarithmic axis, where they are undefined.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum, Default)]
+pub enum LogZeroPolicy {
+    /// Replace zero with the log epsilon, or without one with a tenth of the smallest positive value,
+    /// i.e. one decade below all other values.
+    Epsilon,
+    /// Leave out zero values with a warning.
+    #[default]
+    Skip,
+    /// Replace zero with the smallest positive value.
+    ClampMin,
+}
+
+/// True if the value counts as zero on a logarithmic axis,
+/// i.e. if it lies within `value_epsilon` of zero or below the log epsilon, if any.
+pub fn is_log_zero(value: f64, value_epsilon: f64, log_epsilon: Option<f64>) -> bool {
+    value.abs() <= value_epsilon || log_epsilon.is_some_and(|log_epsilon| value < log_epsilon)
+}
+
+impl LogZeroPolicy {
+    /// The given policy, or if none is given, the epsilon policy if there is a log epsilon and the default otherwise,
+    /// such that a log epsilon alone clamps small values to it instead of skipping them.
+    pub fn resolve(policy: Option<Self>, log_epsilon: Option<f64>) -> Self {
+        policy.unwrap_or(if log_epsilon.is_some() {
+            Self::Epsilon
+        } else {
+            Self::default()
+        })
+    }
+
+    /// The value that replaces zero, given the smallest positive value of the plot and the log epsilon, if any.
+    /// A replacement of NaN means that zero is skipped.
+    pub fn replacement(&self, min_positive_value: f64, log_epsilon: Option<f64>) -> f64 {
+        match self {
+            Self::Epsilon => log_epsilon.unwrap_or(min_positive_value / 10.0),
+            Self::Skip => f64::NAN,
+            Self::ClampMin => min_positive_value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_undoes_every_transform() {
+        for transform in [
+            AxisTransform::Linear,
+            AxisTransform::PolynomialRoot { degree: 1.0 },
+            AxisTransform::PolynomialRoot { degree: 6.0 },
+            AxisTransform::Reciprocal,
+            AxisTransform::Log { base: 10.0 },
+            AxisTransform::Log { base: 2.0 },
+        ] {
+            for value in [1e-6, 0.25, 1.0, 3.0, 1234.5, 1e12] {
+                let round_trip = transform.apply_inverse(transform.apply(value));
+                assert!(
+                    ((round_trip - value) / value).abs() < 1e-9,
+                    "{transform} maps {value} to {round_trip}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn undefined_values_become_nan() {
+        assert!(AxisTransform::Reciprocal.apply(0.0).is_nan());
+        assert!(AxisTransform::Log { base: 10.0 }.apply(0.0).is_nan());
+        assert!(AxisTransform::Log { base: 10.0 }.apply(-1.0).is_nan());
+    }
+
+    #[test]
+    fn log_epsilon_implies_the_epsilon_policy() {
+        let policy = LogZeroPolicy::resolve(None, Some(1e-3));
+        assert_eq!(policy, LogZeroPolicy::Epsilon);
+        assert_eq!(policy.replacement(0.5, Some(1e-3)), 1e-3);
+        assert_eq!(LogZeroPolicy::resolve(None, None), LogZeroPolicy::Skip);
+        assert_eq!(
+            LogZeroPolicy::resolve(Some(LogZeroPolicy::ClampMin), Some(1e-3)),
+            LogZeroPolicy::ClampMin
+        );
+    }
+}
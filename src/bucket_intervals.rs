@@ -0,0 +1,99 @@
+use std::str::FromStr;
+
+/// Explicit histogram bins, given as half-open intervals `0..10,10..100,100..1000`.
+/// The intervals must not overlap, but may leave gaps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BucketIntervals {
+    /// Sorted by their lower bound.
+    intervals: Vec<(f64, f64)>,
+}
+
+impl BucketIntervals {
+    /// The intervals sorted by their lower bound.
+    pub fn intervals(&self) -> &[(f64, f64)] {
+        &self.intervals
+    }
+
+    /// The index of the interval containing the given value.
+    pub fn index_of(&self, value: f64) -> Option<usize> {
+        let index = self
+            .intervals
+            .partition_point(|&(lower, _)| lower <= value)
+            .checked_sub(1)?;
+        (value < self.intervals[index].1).then_some(index)
+    }
+}
+
+impl FromStr for BucketIntervals {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let parse = |bound: &str| {
+            bound
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|bound| bound.is_finite())
+                .ok_or_else(|| format!("Interval bound {bound:?} is not a finite number"))
+        };
+        let mut intervals = source
+            .split(',')
+            .map(|interval| {
+                let (lower, upper) = interval
+                    .split_once("..")
+                    .ok_or_else(|| format!("Interval {interval:?} is not of the form `lo..hi`"))?;
+                let (lower, upper) = (parse(lower)?, parse(upper)?);
+                if lower >= upper {
+                    return Err(format!("Interval {interval:?} is empty"));
+                }
+                Ok((lower, upper))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        intervals.sort_by(|a, b| a.0.total_cmp(&b.0));
+        if let Some(window) = intervals
+            .windows(2)
+            .find(|window| window[0].1 > window[1].0)
+        {
+            return Err(format!(
+                "Intervals {}..{} and {}..{} overlap, so values would be counted twice",
+                window[0].0, window[0].1, window[1].0, window[1].1
+            ));
+        }
+        Ok(Self { intervals })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_are_assigned_to_half_open_intervals() {
+        let intervals: BucketIntervals = "10..100, 0..10, 200..300".parse().unwrap();
+        assert_eq!(
+            intervals.intervals(),
+            [(0.0, 10.0), (10.0, 100.0), (200.0, 300.0)]
+        );
+        assert_eq!(intervals.index_of(0.0), Some(0));
+        assert_eq!(intervals.index_of(10.0), Some(1));
+        assert_eq!(intervals.index_of(99.9), Some(1));
+        // Values in gaps or outside of all intervals lie in no interval.
+        assert_eq!(intervals.index_of(150.0), None);
+        assert_eq!(intervals.index_of(300.0), None);
+        assert_eq!(intervals.index_of(-1.0), None);
+    }
+
+    #[test]
+    fn overlapping_intervals_are_rejected() {
+        let error = "0..10,5..20".parse::<BucketIntervals>().unwrap_err();
+        assert!(error.contains("overlap"), "{error}");
+    }
+
+    #[test]
+    fn malformed_intervals_are_rejected() {
+        for source in ["0..0", "10..5", "0-10", "0..inf", "a..b"] {
+            assert!(source.parse::<BucketIntervals>().is_err(), "{source:?}");
+        }
+    }
+}
@@ -0,0 +1,41 @@
+/// A binary unit for byte values, such that the values can be displayed without many digits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ByteUnit {
+    /// The amount of bytes in one unit.
+    pub divisor: f64,
+    pub suffix: &'static str,
+}
+
+const SUFFIXES: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+impl ByteUnit {
+    /// The largest unit from bytes up to TiB in which `max_value` bytes are at least one.
+    pub fn for_max_value(max_value: f64) -> Self {
+        let mut exponent = 0;
+        while exponent + 1 < SUFFIXES.len() && max_value >= 1024f64.powi(exponent as i32 + 1) {
+            exponent += 1;
+        }
+        Self {
+            divisor: 1024f64.powi(exponent as i32),
+            suffix: SUFFIXES[exponent],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn largest_unit_with_at_least_one_unit_is_chosen() {
+        let suffix = |max_value| ByteUnit::for_max_value(max_value).suffix;
+        assert_eq!(suffix(0.0), "B");
+        assert_eq!(suffix(1023.0), "B");
+        assert_eq!(suffix(1024.0), "KiB");
+        assert_eq!(suffix(5.0 * 1024.0 * 1024.0), "MiB");
+        assert_eq!(suffix(1024f64.powi(4)), "TiB");
+        // There is no unit beyond TiB.
+        assert_eq!(suffix(1024f64.powi(6)), "TiB");
+        assert_eq!(ByteUnit::for_max_value(3e9).divisor, 1024f64.powi(3));
+    }
+}
@@ -0,0 +1,139 @@
+use clap::ValueEnum;
+use plotters::style::RGBColor;
+
+use crate::theme::ThemeColor;
+
+/// A continuous color scale, e.g. for groups that are ordered such as lengths or seeds.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum Colormap {
+    Viridis,
+    Magma,
+    Coolwarm,
+    #[value(name = "viridis_r")]
+    ViridisR,
+    #[value(name = "magma_r")]
+    MagmaR,
+    #[value(name = "coolwarm_r")]
+    CoolwarmR,
+}
+
+/// Evenly spaced samples of the colormaps, between which colors are interpolated linearly.
+const VIRIDIS: [(u8, u8, u8); 9] = [
+    (68, 1, 84),
+    (71, 45, 123),
+    (59, 82, 139),
+    (44, 114, 142),
+    (33, 145, 140),
+    (40, 174, 128),
+    (94, 201, 98),
+    (173, 220, 48),
+    (253, 231, 37),
+];
+const MAGMA: [(u8, u8, u8); 9] = [
+    (0, 0, 4),
+    (28, 16, 68),
+    (79, 18, 123),
+    (129, 37, 129),
+    (181, 54, 122),
+    (229, 80, 100),
+    (251, 135, 97),
+    (254, 194, 135),
+    (252, 253, 191),
+];
+const COOLWARM: [(u8, u8, u8); 9] = [
+    (59, 76, 192),
+    (98, 130, 234),
+    (141, 176, 254),
+    (184, 208, 249),
+    (221, 221, 221),
+    (245, 196, 173),
+    (244, 154, 123),
+    (222, 96, 77),
+    (180, 4, 38),
+];
+
+impl Colormap {
+    /// The color at the given position of the colormap.
+    /// Positions outside of `[0, 1]` are clamped, and NaN is treated as zero.
+    pub fn color(&self, position: f64) -> RGBColor {
+        let (table, reversed) = match self {
+            Self::Viridis => (&VIRIDIS, false),
+            Self::Magma => (&MAGMA, false),
+            Self::Coolwarm => (&COOLWARM, false),
+            Self::ViridisR => (&VIRIDIS, true),
+            Self::MagmaR => (&MAGMA, true),
+            Self::CoolwarmR => (&COOLWARM, true),
+        };
+        let position = if position.is_nan() {
+            0.0
+        } else {
+            position.clamp(0.0, 1.0)
+        };
+        let position = if reversed { 1.0 - position } else { position };
+
+        let scaled = position * (table.len() - 1) as f64;
+        let lower = (scaled.floor() as usize).min(table.len() - 2);
+        let fraction = scaled - lower as f64;
+        let channel = |lower: u8, upper: u8| {
+            (lower as f64 + (upper as f64 - lower as f64) * fraction).round() as u8
+        };
+        let ((r1, g1, b1), (r2, g2, b2)) = (table[lower], table[lower + 1]);
+        RGBColor(channel(r1, r2), channel(g1, g2), channel(b1, b2))
+    }
+
+    /// A palette of the given amount of colors spread evenly over the colormap, starting and ending at its endpoints.
+    pub fn palette(&self, amount: usize) -> Vec<ThemeColor> {
+        (0..amount)
+            .map(|index| {
+                let position = if amount > 1 {
+                    index as f64 / (amount - 1) as f64
+                } else {
+                    0.0
+                };
+                self.color(position).into()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoints_and_midpoint_are_table_entries() {
+        assert_eq!(Colormap::Viridis.color(0.0), RGBColor(68, 1, 84));
+        assert_eq!(Colormap::Viridis.color(1.0), RGBColor(253, 231, 37));
+        assert_eq!(Colormap::Viridis.color(0.5), RGBColor(33, 145, 140));
+        assert_eq!(Colormap::Coolwarm.color(0.5), RGBColor(221, 221, 221));
+    }
+
+    #[test]
+    fn colors_between_samples_are_interpolated() {
+        // Halfway between the first two samples of magma.
+        assert_eq!(Colormap::Magma.color(0.0625), RGBColor(14, 8, 36));
+    }
+
+    #[test]
+    fn reversed_colormaps_swap_the_endpoints() {
+        assert_eq!(Colormap::ViridisR.color(0.0), Colormap::Viridis.color(1.0));
+        assert_eq!(Colormap::ViridisR.color(1.0), Colormap::Viridis.color(0.0));
+    }
+
+    #[test]
+    fn positions_outside_the_unit_interval_are_clamped() {
+        assert_eq!(Colormap::Viridis.color(-1.0), Colormap::Viridis.color(0.0));
+        assert_eq!(Colormap::Viridis.color(2.0), Colormap::Viridis.color(1.0));
+        assert_eq!(
+            Colormap::Viridis.color(f64::NAN),
+            Colormap::Viridis.color(0.0)
+        );
+    }
+
+    #[test]
+    fn palette_spans_the_whole_colormap() {
+        assert_eq!(Colormap::Viridis.palette(0).len(), 0);
+        assert_eq!(Colormap::Viridis.palette(1).len(), 1);
+        assert_eq!(Colormap::Viridis.palette(5).len(), 5);
+    }
+}
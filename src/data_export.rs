@@ -0,0 +1,304 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use log::info;
+
+use crate::statistics_file::{MergedStatisticsFile, StatisticsFile};
+
+/// Write the plotted data of a figure into a csv file next to it, with the same name but extension `.csv`.
+///
+/// If `drop_constant_columns` is set, columns that have the same value in all rows are omitted.
+pub fn write_data_csv<Row: IntoIterator<Item = String>>(
+    figure_file: &Path,
+    header: &[&str],
+    rows: impl IntoIterator<Item = Row>,
+    drop_constant_columns: bool,
+) {
+    let path = figure_file.with_extension("csv");
+    info!("Writing plotted data to {path:?}");
+    write_csv(&path, header, rows, drop_constant_columns);
+}
+
+/// Write the drawn primitives of a figure into a csv file next to it, with the same name but extension `.layout.csv`.
+pub fn write_layout_csv<Row: IntoIterator<Item = String>>(
+    figure_file: &Path,
+    header: &[&str],
+    rows: impl IntoIterator<Item = Row>,
+) {
+    let path = figure_file.with_extension("layout.csv");
+    info!("Writing layout to {path:?}");
+    write_csv(&path, header, rows, false);
+}
+
+/// Write the given statistics files into a csv file with one row per statistics file.
+///
+/// The columns are the parameters, the further strategies of all files in alphabetical order, and the main statistics.
+/// Strategies and memory usage that a file does not record are left empty.
+/// If `drop_constant_columns` is set, columns that have the same value in all rows are omitted.
+pub fn write_statistics_csv(
+    path: impl AsRef<Path>,
+    statistics_files: &[StatisticsFile],
+    drop_constant_columns: bool,
+) {
+    let path = path.as_ref();
+    info!(
+        "Writing {} statistics files to {path:?}",
+        statistics_files.len()
+    );
+
+    let strategies: BTreeSet<_> = statistics_files
+        .iter()
+        .flat_map(|file| file.parameters.strategies.keys())
+        .map(String::as_str)
+        .collect();
+    let header: Vec<_> = [
+        "test_sequence_name",
+        "length",
+        "seed",
+        "alignment_config",
+        "ts_node_ord_strategy",
+    ]
+    .into_iter()
+    .chain(strategies.iter().copied())
+    .chain([
+        "cost",
+        "opened_nodes",
+        "template_switch_amount",
+        "duration_seconds",
+        "memory_bytes",
+    ])
+    .collect();
+    let rows = statistics_files.iter().map(|file| {
+        let parameters = &file.parameters;
+        let statistics = &file.statistics.statistics;
+        [
+            parameters.test_sequence_name.clone(),
+            parameters.length.to_string(),
+            parameters.seed.to_string(),
+            parameters.alignment_config.clone(),
+            parameters.ts_node_ord_strategy.clone(),
+        ]
+        .into_iter()
+        .chain(strategies.iter().map(|&strategy| {
+            parameters
+                .strategies
+                .get(strategy)
+                .cloned()
+                .unwrap_or_default()
+        }))
+        .chain([
+            statistics.cost.to_string(),
+            statistics.opened_nodes.to_string(),
+            file.template_switch_amount().to_string(),
+            statistics.duration_seconds.to_string(),
+            file.memory_bytes
+                .map(|memory_bytes| memory_bytes.to_string())
+                .unwrap_or_default(),
+        ])
+        .collect::<Vec<_>>()
+    });
+    write_csv(path, &header, rows, drop_constant_columns);
+}
+
+/// Write the merged statistics of each group and key into the given file,
+/// as TOML if its extension is `toml` and as JSON otherwise.
+pub fn write_merged_statistics<GroupName: ToString>(
+    path: impl AsRef<Path>,
+    groups: &BTreeMap<GroupName, Vec<MergedStatisticsFile>>,
+) {
+    let path = path.as_ref();
+    info!(
+        "Writing merged statistics of {} groups to {path:?}",
+        groups.len()
+    );
+
+    let groups: BTreeMap<_, _> = groups
+        .iter()
+        .map(|(group_name, group)| (group_name.to_string(), group))
+        .collect();
+    let serialised = if path
+        .extension()
+        .is_some_and(|extension| extension == "toml")
+    {
+        toml::to_string(&groups).unwrap_or_else(|error| {
+            panic!("Could not serialise merged statistics as TOML: {error}")
+        })
+    } else {
+        serde_json::to_string_pretty(&groups).unwrap_or_else(|error| {
+            panic!("Could not serialise merged statistics as JSON: {error}")
+        })
+    };
+    std::fs::write(path, serialised)
+        .unwrap_or_else(|error| panic!("Could not write merged statistics to {path:?}: {error}"));
+}
+
+fn write_csv<Row: IntoIterator<Item = String>>(
+    path: &Path,
+    header: &[&str],
+    rows: impl IntoIterator<Item = Row>,
+    drop_constant_columns: bool,
+) {
+    let mut rows: Vec<Vec<_>> = rows
+        .into_iter()
+        .map(|row| row.into_iter().collect())
+        .collect();
+    let mut header = header.to_vec();
+    if drop_constant_columns {
+        let constant_columns = constant_columns(&rows);
+        if !constant_columns.is_empty() {
+            info!(
+                "Dropping constant columns {:?}",
+                constant_columns
+                    .iter()
+                    .map(|&column| header[column])
+                    .collect::<Vec<_>>()
+            );
+        }
+        header = drop_columns(header, &constant_columns);
+        rows = rows
+            .into_iter()
+            .map(|row| drop_columns(row, &constant_columns))
+            .collect();
+    }
+
+    let mut writer = BufWriter::new(
+        File::create(path)
+            .unwrap_or_else(|error| panic!("Could not create data file {path:?}: {error}")),
+    );
+    let fields = std::iter::once(header.iter().map(|field| escape(field)).collect::<Vec<_>>())
+        .chain(
+            rows.into_iter()
+                .map(|row| row.iter().map(|field| escape(field)).collect()),
+        );
+    for fields in fields {
+        writeln!(writer, "{}", fields.join(","))
+            .unwrap_or_else(|error| panic!("Could not write data file {path:?}: {error}"));
+    }
+    writer
+        .flush()
+        .unwrap_or_else(|error| panic!("Could not write data file {path:?}: {error}"));
+}
+
+/// Returns the indices of the columns that have the same value in all rows.
+///
+/// With less than two rows, no column is considered constant, since then there is nothing to compare.
+fn constant_columns<Field: PartialEq>(rows: &[Vec<Field>]) -> Vec<usize> {
+    let [first, others @ ..] = rows else {
+        return Vec::new();
+    };
+    if others.is_empty() {
+        return Vec::new();
+    }
+
+    let mut constant_columns: Vec<_> = (0..first.len()).collect();
+    for row in others {
+        constant_columns.retain(|&column| row[column] == first[column]);
+    }
+    constant_columns
+}
+
+fn drop_columns<Field>(row: Vec<Field>, columns: &[usize]) -> Vec<Field> {
+    row.into_iter()
+        .enumerate()
+        .filter(|(column, _)| !columns.contains(column))
+        .map(|(_, field)| field)
+        .collect()
+}
+
+/// Quote a csv field if it contains a separator, quote or line break.
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_csv_has_one_row_per_group_key_and_value() {
+        let directory = std::env::temp_dir().join(format!(
+            "template-switch-statistics-data-csv-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+        let figure_file = directory.join("figure.svg");
+
+        let rows = [
+            ("plain", 1.0, 10.0),
+            ("plain", 1.0, 12.0),
+            ("with, comma", 2.0, 20.0),
+            ("with \"quotes\"", 2.0, 21.0),
+        ];
+        write_data_csv(
+            &figure_file,
+            &["group", "key", "value"],
+            rows.iter()
+                .map(|(group, key, value)| [group.to_string(), key.to_string(), value.to_string()]),
+            false,
+        );
+
+        let csv = std::fs::read_to_string(directory.join("figure.csv")).unwrap();
+        assert_eq!(
+            csv,
+            "group,key,value\n\
+             plain,1,10\n\
+             plain,1,12\n\
+             \"with, comma\",2,20\n\
+             \"with \"\"quotes\"\"\",2,21\n"
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn statistics_csv_has_a_column_per_strategy() {
+        let directory = std::env::temp_dir().join(format!(
+            "template-switch-statistics-statistics-csv-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("statistics.csv");
+
+        let file =
+            StatisticsFile::from_toml_str(include_str!("../test_files/statistics-0.toml")).unwrap();
+        let mut first = file.clone();
+        first
+            .parameters
+            .strategies
+            .insert("tsj_strategy".to_string(), "a, b".to_string());
+        let mut second = file;
+        second.parameters.seed = 1;
+        second.parameters.test_sequence_name = "say \"hi\"".to_string();
+        second.memory_bytes = Some(1024);
+        second
+            .parameters
+            .strategies
+            .insert("chaining_strategy".to_string(), "none".to_string());
+        write_statistics_csv(&path, &[first, second], false);
+
+        let csv = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            csv,
+            "test_sequence_name,length,seed,alignment_config,ts_node_ord_strategy,chaining_strategy,tsj_strategy,\
+             cost,opened_nodes,template_switch_amount,duration_seconds,memory_bytes\n\
+             single_ts_0,100,0,default,anti-diagonal,,\"a, b\",0,2137,1,0.000546915,\n\
+             \"say \"\"hi\"\"\",100,1,default,anti-diagonal,none,,0,2137,1,0.000546915,1024\n"
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn constant_columns_are_dropped() {
+        let rows = vec![vec!["a", "1", "x"], vec!["a", "2", "y"]];
+        assert_eq!(constant_columns(&rows), vec![0]);
+        assert!(constant_columns(&rows[..1]).is_empty());
+        assert_eq!(drop_columns(rows[0].clone(), &[0]), vec!["1", "x"]);
+    }
+}
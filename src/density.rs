@@ -0,0 +1,113 @@
+use std::f64::consts::PI;
+
+/// Estimate the density of the given values at the given points with a gaussian kernel.
+///
+/// The bandwidth is chosen by Silverman's rule of thumb, but is at least `min_bandwidth`,
+/// such that values that are all equal still produce a visible peak.
+pub fn kernel_density_estimate(
+    values: &[f64],
+    points: impl IntoIterator<Item = f64>,
+    min_bandwidth: f64,
+) -> Vec<(f64, f64)> {
+    if values.is_empty() {
+        return Vec::new();
+    }
+
+    gaussian_kernel_density(
+        values,
+        points,
+        silverman_bandwidth(values).max(min_bandwidth),
+    )
+}
+
+/// Estimate the density of the given values at the given points with a gaussian kernel of the given bandwidth.
+pub fn gaussian_kernel_density(
+    values: &[f64],
+    points: impl IntoIterator<Item = f64>,
+    bandwidth: f64,
+) -> Vec<(f64, f64)> {
+    let normalisation = 1.0 / (values.len() as f64 * bandwidth * (2.0 * PI).sqrt());
+    points
+        .into_iter()
+        .map(|point| {
+            let density = values
+                .iter()
+                .map(|value| {
+                    let distance = (point - value) / bandwidth;
+                    (-0.5 * distance * distance).exp()
+                })
+                .sum::<f64>()
+                * normalisation;
+            (point, density)
+        })
+        .collect()
+}
+
+/// The bandwidth of a gaussian kernel by Silverman's rule of thumb, `1.06 * std * n^(-1/5)`.
+/// Values that are all equal have a bandwidth of zero.
+pub fn silverman_bandwidth(values: &[f64]) -> f64 {
+    let amount = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / amount;
+    let variance = values
+        .iter()
+        .map(|value| (value - mean) * (value - mean))
+        .sum::<f64>()
+        / amount;
+    1.06 * variance.sqrt() * amount.powf(-0.2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The integral of the density over evenly spaced points by the trapezoidal rule.
+    fn integral(density: &[(f64, f64)]) -> f64 {
+        density
+            .windows(2)
+            .map(|window| (window[1].0 - window[0].0) * (window[0].1 + window[1].1) * 0.5)
+            .sum()
+    }
+
+    fn points(from: f64, to: f64) -> impl Iterator<Item = f64> {
+        (0..=1000).map(move |index| from + (to - from) * index as f64 / 1000.0)
+    }
+
+    #[test]
+    fn silverman_bandwidth_of_known_sample() {
+        // The sample has mean 5 and a standard deviation of 2.
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let expected = 1.06 * 2.0 * 8.0f64.powf(-0.2);
+        assert!((silverman_bandwidth(&values) - expected).abs() < 1e-12);
+        assert!((silverman_bandwidth(&values) - 1.398679).abs() < 1e-6);
+    }
+
+    #[test]
+    fn density_integrates_to_one() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let density = kernel_density_estimate(&values, points(-10.0, 20.0), 0.0);
+        assert!((integral(&density) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn single_and_constant_samples_fall_back_to_the_minimum_bandwidth() {
+        assert_eq!(silverman_bandwidth(&[3.0]), 0.0);
+        assert_eq!(silverman_bandwidth(&[3.0, 3.0, 3.0]), 0.0);
+
+        for values in [&[3.0][..], &[3.0, 3.0, 3.0]] {
+            let density = kernel_density_estimate(values, points(-7.0, 13.0), 1.0);
+            assert!(density.iter().all(|(_, density)| density.is_finite()));
+            assert!((integral(&density) - 1.0).abs() < 1e-6);
+            let (peak, _) = density
+                .iter()
+                .copied()
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap();
+            assert!((peak - 3.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn density_of_nothing_is_empty() {
+        assert!(kernel_density_estimate(&[], points(0.0, 1.0), 1.0).is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::BufWriter,
+    path::PathBuf,
+    sync::{Mutex, MutexGuard},
+};
+
+use log::{error, info, log, Level};
+use serde::Serialize;
+
+static DIAGNOSTICS: Mutex<Vec<Diagnostic>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticCategory {
+    Cache,
+    CaptionTemplate,
+    Censored,
+    DroppedFile,
+    InapplicableMetric,
+    IndistinctColors,
+    Merge,
+    MissingBaseline,
+    MissingMetadata,
+    NarrowBoxes,
+    NonFiniteValue,
+    NonMonotonic,
+    OpaqueBackground,
+    TruncatedFile,
+    UnbinnedValue,
+    UnequalGroups,
+    UnexpectedSeeds,
+    UnreliablePoint,
+    UnitMismatch,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub category: DiagnosticCategory,
+    pub message: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Writes all collected diagnostics as a JSON array when dropped.
+///
+/// Since it is written on drop, the diagnostics are also written if the program panics.
+pub struct DiagnosticsJsonWriter {
+    path: PathBuf,
+}
+
+/// Log a diagnostic and record it in the global collector.
+pub fn report<Key: ToString, Value: ToString>(
+    level: Level,
+    category: DiagnosticCategory,
+    message: impl ToString,
+    fields: impl IntoIterator<Item = (Key, Value)>,
+) {
+    let diagnostic = Diagnostic {
+        category,
+        message: message.to_string(),
+        fields: fields
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+    };
+
+    if diagnostic.fields.is_empty() {
+        log!(level, "{}", diagnostic.message);
+    } else {
+        log!(
+            level,
+            "{} ({})",
+            diagnostic.message,
+            diagnostic
+                .fields
+                .iter()
+                .map(|(key, value)| format!("{key}: {value}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    lock_diagnostics().push(diagnostic);
+}
+
+fn lock_diagnostics() -> MutexGuard<'static, Vec<Diagnostic>> {
+    // A panic while holding the lock does not invalidate the collected diagnostics.
+    DIAGNOSTICS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl DiagnosticsJsonWriter {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl Drop for DiagnosticsJsonWriter {
+    fn drop(&mut self) {
+        let diagnostics = lock_diagnostics();
+        info!(
+            "Writing {} diagnostics to {:?}",
+            diagnostics.len(),
+            self.path
+        );
+
+        match File::create(&self.path) {
+            Ok(file) => {
+                if let Err(error) =
+                    serde_json::to_writer_pretty(BufWriter::new(file), &*diagnostics)
+                {
+                    error!("Could not write diagnostics to {:?}: {error}", self.path);
+                }
+            }
+            Err(error) => error!("Could not create diagnostics file {:?}: {error}", self.path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reported_warning_appears_as_json_entry() {
+        let path = std::env::temp_dir().join(format!(
+            "template-switch-statistics-diagnostics-{}.json",
+            std::process::id()
+        ));
+        {
+            let _writer = DiagnosticsJsonWriter::new(path.clone());
+            report(
+                Level::Warn,
+                DiagnosticCategory::DroppedFile,
+                "Dropped a file for testing",
+                [("path", "statistics-7.toml")],
+            );
+        }
+
+        let diagnostics: Vec<serde_json::Value> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        // Other tests report into the same collector, so only this entry is looked for.
+        assert!(diagnostics.contains(&serde_json::json!({
+            "category": "dropped_file",
+            "message": "Dropped a file for testing",
+            "fields": { "path": "statistics-7.toml" },
+        })));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,112 @@
+use std::{collections::BTreeMap, ops::Range, str::FromStr};
+
+/// The seeds every set of repeated runs is expected to contain, given as a half-open range `0..30`
+/// or as a count `30`, which is short for `0..30`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedSeeds {
+    seeds: Range<u64>,
+}
+
+impl ExpectedSeeds {
+    /// The expected seeds in ascending order.
+    pub fn seeds(&self) -> Range<u64> {
+        self.seeds.clone()
+    }
+
+    /// How the seeds of a set of repeated runs, given with the amount of runs of each seed, deviate from the expected seeds.
+    pub fn deviations(&self, seed_amounts: &BTreeMap<u64, usize>) -> SeedDeviations {
+        SeedDeviations {
+            missing: self
+                .seeds()
+                .filter(|seed| !seed_amounts.contains_key(seed))
+                .collect(),
+            duplicate: seed_amounts
+                .iter()
+                .filter(|(_, &amount)| amount > 1)
+                .map(|(seed, _)| *seed)
+                .collect(),
+            unexpected: seed_amounts
+                .keys()
+                .filter(|seed| !self.seeds.contains(seed))
+                .copied()
+                .collect(),
+        }
+    }
+}
+
+/// The seeds of a set of repeated runs that deviate from the expected seeds, in ascending order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeedDeviations {
+    pub missing: Vec<u64>,
+    pub duplicate: Vec<u64>,
+    pub unexpected: Vec<u64>,
+}
+
+impl SeedDeviations {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.duplicate.is_empty() && self.unexpected.is_empty()
+    }
+}
+
+impl FromStr for ExpectedSeeds {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let parse = |seed: &str| {
+            seed.trim()
+                .parse::<u64>()
+                .map_err(|error| format!("Seed {seed:?} is not a non-negative integer: {error}"))
+        };
+        let seeds = match source.split_once("..") {
+            Some((start, end)) => parse(start)?..parse(end)?,
+            None => 0..parse(source)?,
+        };
+
+        if seeds.is_empty() {
+            return Err(format!("The expected seeds {source:?} are empty"));
+        }
+        Ok(Self { seeds })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_seed_is_reported() {
+        let expected_seeds: ExpectedSeeds = "0..10".parse().unwrap();
+        let seed_amounts = (0..10)
+            .filter(|&seed| seed != 7)
+            .map(|seed| (seed, 1))
+            .collect();
+        assert_eq!(
+            expected_seeds.deviations(&seed_amounts),
+            SeedDeviations {
+                missing: vec![7],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn duplicate_and_unexpected_seeds_are_reported() {
+        let expected_seeds: ExpectedSeeds = "3".parse().unwrap();
+        assert_eq!(expected_seeds.seeds(), 0..3);
+        let seed_amounts = BTreeMap::from([(0, 1), (1, 2), (2, 1), (5, 1)]);
+        let deviations = expected_seeds.deviations(&seed_amounts);
+        assert_eq!(deviations.duplicate, [1]);
+        assert_eq!(deviations.unexpected, [5]);
+        assert!(deviations.missing.is_empty());
+
+        let complete = BTreeMap::from([(0, 1), (1, 1), (2, 1)]);
+        assert!(expected_seeds.deviations(&complete).is_empty());
+    }
+
+    #[test]
+    fn empty_or_malformed_seeds_are_rejected() {
+        for source in ["0", "5..5", "5..2", "-1..3", "a"] {
+            assert!(source.parse::<ExpectedSeeds>().is_err(), "{source:?}");
+        }
+    }
+}
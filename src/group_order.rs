@@ -0,0 +1,69 @@
+use std::fmt::Display;
+
+/// A group name that is ordered by its position in an explicit group order,
+/// and after all listed groups by the name itself.
+///
+/// Groups are drawn, colored and listed in the legend in the order of their names,
+/// so this fixes the order of the groups across plots.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct OrderedGroupName {
+    position: usize,
+    name: String,
+}
+
+impl OrderedGroupName {
+    pub fn new(name: String, group_order: &[String]) -> Self {
+        let position = group_order
+            .iter()
+            .position(|listed_name| listed_name == &name)
+            .unwrap_or(usize::MAX);
+        Self { position, name }
+    }
+}
+
+impl Display for OrderedGroupName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Groups that are created while plotting, such as the group `other` combining the remaining groups,
+/// are placed after all listed groups.
+impl From<&str> for OrderedGroupName {
+    fn from(name: &str) -> Self {
+        Self {
+            position: usize::MAX,
+            name: name.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listed_groups_come_first_in_the_given_order() {
+        let group_order = ["fpa".to_string(), "tsalign".to_string()];
+        let mut names: Vec<_> = ["b", "tsalign", "a", "fpa"]
+            .into_iter()
+            .map(|name| OrderedGroupName::new(name.to_string(), &group_order))
+            .collect();
+        names.push("other".into());
+        names.sort();
+        let names: Vec<_> = names.iter().map(ToString::to_string).collect();
+        // Groups that are not listed keep their alphabetical order after the listed ones.
+        assert_eq!(names, ["fpa", "tsalign", "a", "b", "other"]);
+    }
+
+    #[test]
+    fn listed_groups_without_data_are_ignored() {
+        let group_order = ["missing".to_string(), "b".to_string()];
+        let mut names = [
+            OrderedGroupName::new("b".to_string(), &group_order),
+            OrderedGroupName::new("a".to_string(), &group_order),
+        ];
+        names.sort();
+        assert_eq!(names.map(|name| name.to_string()), ["b", "a"]);
+    }
+}
@@ -0,0 +1,51 @@
+/// Linearly interpolate the given points onto the given keys.
+///
+/// The points must be sorted by key.
+/// Keys outside of the range of the points are omitted, since extrapolating would suggest measurements that do not exist.
+pub fn interpolate_onto_keys(
+    points: &[(f64, f64)],
+    keys: impl IntoIterator<Item = f64>,
+) -> Vec<(f64, f64)> {
+    let (Some(&(first_key, _)), Some(&(last_key, _))) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+
+    keys.into_iter()
+        .filter(|key| (first_key..=last_key).contains(key))
+        .map(|key| {
+            let upper_index = points.partition_point(|&(point_key, _)| point_key < key);
+            let (upper_key, upper_value) = points[upper_index];
+            if upper_key == key || upper_index == 0 {
+                return (key, upper_value);
+            }
+
+            let (lower_key, lower_value) = points[upper_index - 1];
+            let fraction = (key - lower_key) / (upper_key - lower_key);
+            (key, lower_value + fraction * (upper_value - lower_value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_keys_are_interpolated_linearly() {
+        let points = [(1.0, 10.0), (3.0, 30.0)];
+        assert_eq!(
+            interpolate_onto_keys(&points, [1.0, 2.0, 3.0]),
+            [(1.0, 10.0), (2.0, 20.0), (3.0, 30.0)]
+        );
+    }
+
+    #[test]
+    fn keys_outside_of_the_points_are_omitted() {
+        let points = [(1.0, 10.0), (3.0, 30.0)];
+        assert_eq!(
+            interpolate_onto_keys(&points, [0.0, 2.5, 4.0]),
+            [(2.5, 25.0)]
+        );
+        assert!(interpolate_onto_keys(&[], [1.0]).is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// How the boundaries of key buckets are spaced between the smallest and the largest key.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, ValueEnum, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyBucketScale {
+    /// Buckets of equal width.
+    #[default]
+    Linear,
+    /// Buckets of equal width in the logarithm of the key, e.g. for keys spanning several orders of magnitude.
+    /// All keys must be positive.
+    Log,
+}
+
+impl KeyBucketScale {
+    /// The index of the bucket containing the given key.
+    pub fn bucket_index(
+        &self,
+        key: f64,
+        min_key: f64,
+        max_key: f64,
+        bucket_amount: usize,
+    ) -> usize {
+        let bucket_amount_f64 = bucket_amount as f64;
+        let bucket_index = match self {
+            Self::Linear => (key - min_key) * bucket_amount_f64 / (max_key - min_key),
+            Self::Log => (key / min_key).ln() * bucket_amount_f64 / (max_key / min_key).ln(),
+        };
+        // If all keys are equal, the index is NaN, which is cast to the first bucket.
+        (bucket_index.floor() as usize).min(bucket_amount - 1)
+    }
+
+    /// The key representing the bucket with the given index, i.e. its arithmetic center under linear scaling
+    /// and its geometric center under logarithmic scaling.
+    pub fn bucket_key(
+        &self,
+        bucket_index: usize,
+        min_key: f64,
+        max_key: f64,
+        bucket_amount: usize,
+    ) -> f64 {
+        let position = (bucket_index as f64 + 0.5) / bucket_amount as f64;
+        match self {
+            Self::Linear => (position * (max_key - min_key)) + min_key,
+            Self::Log => min_key * (max_key / min_key).powf(position),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_buckets_have_equal_width() {
+        let scale = KeyBucketScale::default();
+        assert_eq!(scale, KeyBucketScale::Linear);
+        let indices: Vec<_> = [0.0, 24.0, 25.0, 99.0, 100.0]
+            .into_iter()
+            .map(|key| scale.bucket_index(key, 0.0, 100.0, 4))
+            .collect();
+        // The largest key belongs to the last bucket.
+        assert_eq!(indices, [0, 0, 1, 3, 3]);
+        assert_eq!(scale.bucket_key(1, 0.0, 100.0, 4), 37.5);
+    }
+
+    #[test]
+    fn log_buckets_have_equal_width_in_the_logarithm() {
+        let scale = KeyBucketScale::Log;
+        let indices: Vec<_> = [1.0, 9.0, 10.0, 999.0, 1000.0]
+            .into_iter()
+            .map(|key| scale.bucket_index(key, 1.0, 1000.0, 3))
+            .collect();
+        assert_eq!(indices, [0, 0, 1, 2, 2]);
+        // The key of a bucket is its geometric center.
+        let key = scale.bucket_key(1, 1.0, 1000.0, 3);
+        assert!((key - 10f64.powf(1.5)).abs() < 1e-9, "{key}");
+    }
+
+    #[test]
+    fn equal_keys_share_the_first_bucket() {
+        for scale in [KeyBucketScale::Linear, KeyBucketScale::Log] {
+            assert_eq!(scale.bucket_index(5.0, 5.0, 5.0, 3), 0);
+        }
+    }
+}
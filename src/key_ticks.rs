@@ -0,0 +1,87 @@
+use std::str::FromStr;
+
+/// Explicit tick positions on the key axis, given as `100,1000,10000` or with labels as `100,1000,10000:1e2,1e3,1e4`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyTicks {
+    ticks: Vec<(f64, String)>,
+}
+
+impl KeyTicks {
+    /// The tick positions together with their labels.
+    pub fn ticks(&self) -> &[(f64, String)] {
+        &self.ticks
+    }
+}
+
+impl FromStr for KeyTicks {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let (positions, labels) = match source.split_once(':') {
+            Some((positions, labels)) => (positions, Some(labels)),
+            None => (source, None),
+        };
+
+        let positions = positions
+            .split(',')
+            .map(|position| {
+                position
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|position| position.is_finite())
+                    .ok_or_else(|| format!("Key tick position {position:?} is not a finite number"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let labels: Vec<_> = if let Some(labels) = labels {
+            labels
+                .split(',')
+                .map(|label| label.trim().to_string())
+                .collect()
+        } else {
+            positions
+                .iter()
+                .map(|position| position.to_string())
+                .collect()
+        };
+
+        if positions.len() != labels.len() {
+            return Err(format!(
+                "Got {} key tick positions, but {} labels",
+                positions.len(),
+                labels.len()
+            ));
+        }
+
+        Ok(Self {
+            ticks: positions.into_iter().zip(labels).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_are_labelled_by_themselves_or_by_the_given_labels() {
+        let key_ticks: KeyTicks = "100, 1000".parse().unwrap();
+        assert_eq!(
+            key_ticks.ticks(),
+            [(100.0, "100".to_string()), (1000.0, "1000".to_string())]
+        );
+
+        let key_ticks: KeyTicks = "100,1000:1e2, 1e3".parse().unwrap();
+        assert_eq!(
+            key_ticks.ticks(),
+            [(100.0, "1e2".to_string()), (1000.0, "1e3".to_string())]
+        );
+    }
+
+    #[test]
+    fn invalid_ticks_are_rejected() {
+        for source in ["100,x", "100,inf", "100,1000:1e2"] {
+            assert!(source.parse::<KeyTicks>().is_err(), "{source:?}");
+        }
+    }
+}
@@ -1,19 +1,97 @@
 use std::{
-    collections::BTreeMap,
-    fs::File,
-    io::Read,
-    path::{Path, PathBuf},
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    path::{Component, Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
-use clap::Parser;
-use lib_tsalign::a_star_aligner::alignment_result::AlignmentStatistics;
-use log::info;
-use noisy_float::prelude::Float;
+use anyhow::{bail, Context};
+use axis_transform::{is_log_zero, AxisTransform, AxisTransformKind, LogZeroPolicy};
+use bucket_intervals::BucketIntervals;
+use byte_unit::ByteUnit;
+use clap::{Parser, ValueEnum};
+use colormap::Colormap;
+use diagnostics::{DiagnosticCategory, DiagnosticsJsonWriter};
+use expected_seeds::{ExpectedSeeds, SeedDeviations};
+use group_order::OrderedGroupName;
+use key_bucket_scale::KeyBucketScale;
+use key_ticks::KeyTicks;
+use log::{debug, info, Level};
+use metric::{Metric, MetricPair};
 use noisy_float::types::R64;
-use plotters::prelude::*;
-use statistics_file::{AlignmentParameters, MergedStatisticsFile, StatisticsFile};
+use parameter_filter::ParameterFilter;
+use plotters::{
+    coord::{
+        types::{RangedCoordf32, RangedCoordf64},
+        Shift,
+    },
+    prelude::*,
+    style::text_anchor::{HPos, Pos, VPos},
+};
+use recipe::Recipe;
+use reference_curve::ReferenceCurve;
+use significance::Significance;
+use statistics_cache::StatisticsCache;
+use statistics_file::{
+    AlignmentParameterName, AlignmentParameters, MergedStatisticsFile, StatisticsFile,
+};
+use theme::{GridLines, GridStyle, LegendPosition, Theme, WhiskerStyle};
 
+mod axis_transform;
+mod bucket_intervals;
+mod byte_unit;
+mod colormap;
+mod data_export;
+mod density;
+mod diagnostics;
+mod expected_seeds;
+mod group_order;
+mod interpolation;
+mod key_bucket_scale;
+mod key_ticks;
+mod metric;
+mod parameter_filter;
+#[cfg(feature = "parquet")]
+mod parquet_export;
+mod recipe;
+mod reference_curve;
+mod runtime;
+mod sampling;
+mod significance;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod statistics_cache;
+mod statistics_diff;
 mod statistics_file;
+mod stats;
+mod theme;
+
+/// Create a drawing area for `output_file` in the given [`OutputFormat`], evaluate `$draw` with it bound to `$root`
+/// and write the drawing area to the file.
+/// The expression is expanded once per backend, such that the drawing code can be generic over the backend.
+macro_rules! with_drawing_area {
+    ($output_file:expr, $size:expr, $output_format:expr, $theme:expr, |$root:ident| $draw:expr) => {
+        match $output_format {
+            OutputFormat::Svg => {
+                create_drawing_area($output_file, $size, $theme).and_then(|$root| {
+                    $draw;
+                    $root.present()?;
+                    Ok(())
+                })
+            }
+            #[cfg(feature = "png")]
+            OutputFormat::Png => {
+                create_bitmap_drawing_area($output_file, $size, $theme).and_then(|$root| {
+                    $draw;
+                    $root.present()?;
+                    Ok(())
+                })
+            }
+            #[cfg(not(feature = "png"))]
+            OutputFormat::Png => unreachable!("Writing png files requires the png feature."),
+        }
+    };
+}
 
 #[derive(Parser)]
 struct Cli {
@@ -21,242 +99,6011 @@ struct Cli {
     #[arg(long, short = 'o')]
     output_directory: PathBuf,
 
+    /// The file format of the plots and of the legend.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Svg)]
+    output_format: OutputFormat,
+
     /// Bucket the experiments by their key (`x`-value).
     #[arg(long)]
     key_bucket_amount: Option<usize>,
 
+    /// How the boundaries of the key buckets are spaced.
+    /// Under logarithmic scaling, each bucket is represented by its geometric center and all keys must be positive.
+    #[arg(long, value_enum, default_value_t = KeyBucketScale::Linear)]
+    key_bucket_scale: KeyBucketScale,
+
+    /// Abort if the groups do not contain the same amount of files, instead of only warning about it.
+    #[arg(long)]
+    require_balanced_groups: bool,
+
     /// Make the `y`-axis an n-th-root paxislot with `n = value_polynomial_degree`.
     #[arg(long, default_value = "1.0")]
     value_polynomial_degree: f64,
 
+    /// The transformation applied to the values before placing them on the `y`-axis.
+    /// A reciprocal axis turns e.g. a runtime into a throughput.
+    #[arg(long, value_enum, default_value_t = AxisTransformKind::PolynomialRoot)]
+    value_axis: AxisTransformKind,
+
+    /// If the value axis is not transformed, but the values span more than two orders of magnitude,
+    /// switch to a logarithmic value axis instead of only suggesting it.
+    #[arg(long)]
+    auto_transform: bool,
+
+    /// The base of a logarithmic value axis.
+    #[arg(long, default_value = "10.0")]
+    value_log_base: f64,
+
+    /// How zero values are placed on a logarithmic value axis, where they are undefined.
+    /// Defaults to `epsilon` if a log epsilon is given, and to `skip` otherwise.
+    #[arg(long, value_enum)]
+    log_zero_policy: Option<LogZeroPolicy>,
+
+    /// On a logarithmic value axis, treat values below this as zero.
+    /// Under the epsilon policy, which is the default with a log epsilon, zero values are replaced by this.
+    #[arg(long)]
+    log_epsilon: Option<f64>,
+
+    /// The value that is plotted.
+    /// Values are keyed by the alignment cost, except for the cost itself, which is keyed by the sequence length.
+    #[arg(long, value_enum, default_value_t = Metric::OpenedNodes)]
+    metric: Metric,
+
+    /// Divide the metric of each file by its sequence length before merging,
+    /// such that sequences of different lengths can be compared.
+    /// Files of length zero are skipped.
+    #[arg(long, conflicts_with = "recipe")]
+    per_base: bool,
+
+    /// Instead of plotting the metric by alignment cost, plot it by the time since the earliest run,
+    /// to reveal drift of the measurement conditions over a sweep.
+    /// Files without a valid RFC3339 timestamp are dropped.
+    #[arg(long, conflicts_with_all = ["histogram", "recipe"])]
+    by_timestamp: bool,
+
+    /// Instead of plotting the metric by alignment cost,
+    /// plot a histogram of the metric rounded to integers.
+    #[arg(long)]
+    histogram: bool,
+
+    /// In histograms, sum up this metric per bar instead of counting the files.
+    #[arg(long, value_enum, requires = "histogram")]
+    histogram_weight: Option<Metric>,
+
+    /// In histograms, bucket the values into bins of this width, starting at the minimum value,
+    /// instead of rounding them to integers.
+    #[arg(long, requires = "histogram")]
+    bin_width: Option<f64>,
+
+    /// In histograms, round the values to the nearest multiple of this integer instead of to the nearest integer.
+    #[arg(long, requires = "histogram", conflicts_with = "bin_width")]
+    histogram_round_to: Option<u64>,
+
+    /// In histograms, bucket the values into these half-open intervals, e.g. `0..10,10..100,100..1000`,
+    /// instead of rounding them to integers.
+    /// The bars are as wide as their intervals, and values outside of all intervals are dropped.
+    #[arg(long, requires = "histogram", conflicts_with_all = ["bin_width", "histogram_round_to"])]
+    bucket_intervals: Option<BucketIntervals>,
+
+    /// Append the parameters that are equal in all plotted files to the caption.
+    #[arg(long)]
+    auto_caption: bool,
+
+    /// Build the caption of each plot from this template instead of using the name of the plot.
+    /// The placeholders `{name}`, `{metric}` and `{group_count}` are replaced by the name of the plot,
+    /// the plotted metric and the amount of groups, and placeholders named after a parameter,
+    /// such as `{test_sequence_name}`, by its value if it is equal in all plotted files.
+    #[arg(long)]
+    caption_template: Option<String>,
+
+    /// Use this text as the caption of each plot instead of its name.
+    /// Faceted plots carry this as caption of the whole figure, and the facets keep their names.
+    #[arg(long, conflicts_with = "caption_template")]
+    caption: Option<String>,
+
+    /// The width of each plot in pixels.
+    #[arg(long, default_value_t = 400)]
+    width: u32,
+
+    /// The height of each plot in pixels.
+    #[arg(long, default_value_t = 400)]
+    height: u32,
+
+    /// The corner of the plot in which the legend is drawn, overriding the theme.
+    #[arg(long, value_enum)]
+    legend_position: Option<LegendPosition>,
+
+    /// How the distribution of values at each key is drawn.
+    #[arg(long, value_enum, default_value_t = PlotStyle::Boxplot)]
+    plot_style: PlotStyle,
+
+    /// In line plots, interpolate each group onto the union of the keys of all groups,
+    /// such that all lines share the same key positions.
+    /// Keys outside of the measured range of a group are omitted.
+    #[arg(long)]
+    interpolate_keys: bool,
+
+    /// In ridgeline plots, the vertical distance between the baselines of neighbouring groups,
+    /// relative to the height of the curves.
+    /// Values below one make the curves overlap.
+    #[arg(long, default_value = "0.6")]
+    ridgeline_offset: f64,
+
+    /// The bandwidth of the gaussian kernel of violins, in units of the value axis.
+    /// By default, it is chosen by Silverman's rule of thumb for each violin.
+    #[arg(long)]
+    violin_bandwidth: Option<f64>,
+
+    /// In fan plots, the percentiles that bound the shaded ribbons around the median.
+    /// The lowest and the highest percentile bound the outermost ribbon, the second lowest and
+    /// second highest the next one, and so on.
+    #[arg(long, value_delimiter = ',', default_value = "10,25,75,90")]
+    fan_percentiles: Vec<f64>,
+
+    /// In boxplots, mark this percentile between 0 and 100 on each box, e.g. 90 for skewed runtimes.
+    /// The percentile is also added to the summary json and to the statistics written with `--dump-merged`.
+    /// Can be given multiple times.
+    #[arg(long)]
+    percentile: Vec<f64>,
+
+    /// Narrow the boxes of boxplots around the median to the approximate 95% confidence interval of the median.
+    /// If the notches of two boxes do not overlap, their medians likely differ.
+    #[arg(long)]
+    notched: bool,
+
+    /// Divide the space around each key among the boxes proportionally to the amount of files in each box,
+    /// and scale the widths of the boxes accordingly.
+    #[arg(long)]
+    width_by_sample_count: bool,
+
+    /// Instead of plotting the metric, draw one stacked bar per group
+    /// that shows the proportion of its runs with each amount of template switches.
+    #[arg(long, conflicts_with_all = ["histogram", "recipe", "raw_line", "baseline_dir", "facet"])]
+    ts_proportion: bool,
+
+    /// Instead of merging the files of each key, draw one point per file and connect the points of each group.
+    /// The groups do not need to be of equal size.
+    #[arg(long, conflicts_with_all = ["histogram", "recipe", "baseline_dir"])]
+    raw_line: bool,
+
+    /// Shorten the group names in legends and labels to at most this many characters, ending in an ellipsis.
+    /// Exported data always contains the full group names.
+    #[arg(long)]
+    max_label_length: Option<usize>,
+
+    /// Mark the values of boxplots that lie outside the fences at 1.5 times the interquartile range
+    /// and label them with the seed of their file.
+    #[arg(long)]
+    label_outliers: bool,
+
+    /// Additionally draw one faint line per seed through the values of each group.
+    #[arg(long)]
+    show_trajectories: bool,
+
+    /// Draw a function of the key, such as `n^2` or `n*log(n)`, scaled to fit the values by least squares.
+    #[arg(long)]
+    reference_curve: Option<ReferenceCurve>,
+
+    /// Draw a dashed horizontal line at the median of the values of all plotted files, ignoring the groups.
+    #[arg(long)]
+    overall_median_line: bool,
+
+    /// Draw a dashed line per group, fitted by least squares through the medians of the group at each key.
+    /// The line is fitted to the transformed values, so it is straight on the value axis,
+    /// and its slope and intercept are logged.
+    #[arg(long)]
+    trendline: bool,
+
+    /// Draw the group of this name on top of all other groups, without changing its color or legend position.
+    /// Can be given multiple times.
+    #[arg(long)]
+    on_top: Vec<String>,
+
+    /// Draw, color and list the groups in this order, given as `name1,name2,...`.
+    /// Groups that are not listed follow in the order of their names, and listed groups without files are ignored.
+    #[arg(long, value_delimiter = ',', conflicts_with = "recipe")]
+    group_order: Vec<String>,
+
+    /// How groups without any finite value are shown in the legend.
+    /// Such groups keep their color either way, such that colors are consistent between facets.
+    #[arg(long, value_enum, default_value_t = EmptyGroupPolicy::Omit)]
+    empty_group_policy: EmptyGroupPolicy,
+
+    /// Label exactly these positions of the key axis, given as `100,1000,10000`,
+    /// optionally followed by their labels as `100,1000,10000:small,medium,large`.
+    #[arg(long)]
+    key_ticks: Option<KeyTicks>,
+
+    /// Shrink the margins and label areas of the plots, e.g. for thumbnails.
+    /// Without this, they are scaled with the plot size.
+    #[arg(long)]
+    compact: bool,
+
+    /// Omit the transformation of the value axis from its label.
+    #[arg(long)]
+    hide_transform_in_label: bool,
+
+    /// For each figure, write the plotted values into a csv file with the same name.
+    /// Byte values are written in the unit of the plot, which the column headers name.
+    #[arg(long)]
+    emit_data: bool,
+
+    /// For each figure, write the drawn boxes and points in chart coordinates, including the shift of their keys,
+    /// into a csv file with the same name and extension `.layout.csv`, e.g. to tell aggregation bugs from rendering bugs.
+    #[arg(long)]
+    debug_layout: bool,
+
+    /// Omit columns that have the same value in all rows from the exported csv and parquet files.
+    #[arg(long)]
+    drop_constant_columns: bool,
+
+    /// Annotate each plot with the sum of the runtimes of all plotted alignments.
+    #[arg(long)]
+    annotate_total_runtime: bool,
+
+    /// Annotate each plot, and each facet of faceted plots, with the number of plotted files.
+    #[arg(long)]
+    annotate_facet_n: bool,
+
+    /// Annotate each box with the number of files merged into its key bucket.
+    #[arg(long)]
+    annotate_bucket_n: bool,
+
+    /// Annotate each pair of groups at each key with the significance of their difference,
+    /// tested with the Mann-Whitney U test: `**` for `p < 0.01`, `*` for `p < 0.05` and `ns` otherwise.
+    /// Groups with fewer than three values at a key are annotated as `ns`.
+    #[arg(long, conflicts_with_all = ["histogram", "raw_line", "facet"])]
+    significance: bool,
+
+    /// Clamp all runtimes at or above this amount of seconds to it, and mark the files as censored.
+    /// The timeout can also be given as `mm:ss`, `hh:mm:ss` or `d-hh:mm:ss`, with fractional seconds.
+    /// In boxplots, censored files are marked with an open triangle.
+    #[arg(long, value_parser = runtime::parse_seconds)]
+    timeout: Option<f64>,
+
+    /// Ignore all alignments that were not proven to be optimal.
+    #[arg(long)]
+    optimal_only: bool,
+
+    /// Keep only the files whose parameter, strategy or sidecar metadata field has the given value,
+    /// e.g. `length=1000` or `ts_node_ord_strategy=anti-diagonal`.
+    /// Can be given multiple times, and then a file has to match all filters.
+    #[arg(long)]
+    filter: Vec<ParameterFilter>,
+
+    /// Drop the warmup run of each set of runs that differ only in their seed,
+    /// i.e. the run with the lowest seed, since it may have suffered from cold caches.
+    /// Sets consisting of a single run are kept.
+    #[arg(long)]
+    drop_first_per_group: bool,
+
+    /// Warn about files whose memory or runtime is a thousand times larger or smaller
+    /// than the median of their set of runs that differ only in their seed,
+    /// which hints at files recorded in different units (e.g. KiB instead of bytes).
+    #[arg(long)]
+    detect_unit_mismatch: bool,
+
+    /// Before plotting, check that each set of runs that differ only in their seed
+    /// contains exactly the given seeds, and report the missing and duplicate seeds of each set.
+    /// Given as a half-open range `0..30`, or as a count `30`, which is short for `0..30`.
+    #[arg(long)]
+    expect_seeds: Option<ExpectedSeeds>,
+
+    /// Draw a hollow marker for each alignment that was not proven to be optimal.
+    #[arg(long)]
+    mark_suboptimal: bool,
+
+    /// Draw at most this many individual points per box, i.e. scatter points, suboptimal markers
+    /// and trajectories passing through the box.
+    /// The points are subsampled deterministically, and the boxes are still computed from all points.
+    #[arg(long)]
+    max_points_per_box: Option<usize>,
+
+    /// Warn if the boxes of a boxplot are estimated to be narrower than this amount of pixels,
+    /// e.g. because there are too many groups or keys close to each other.
+    #[arg(long)]
+    min_box_pixels: Option<f64>,
+
+    /// Mark each point merged from fewer than this many files as unreliable,
+    /// drawing a hollow circle around its median and listing it in a warning.
+    #[arg(long)]
+    min_samples_for_reliable: Option<usize>,
+
+    /// Mark each point whose relative standard error of the mean exceeds this value as unreliable,
+    /// drawing a hollow circle around its median and listing it in a warning.
+    #[arg(long)]
+    max_relative_standard_error: Option<f64>,
+
+    /// Additionally write all diagnostics (warnings about merges, unequal groups, etc.)
+    /// as a JSON array into this file.
+    #[arg(long)]
+    diagnostics_json: Option<PathBuf>,
+
+    /// A toml file describing a list of plots to generate instead of the default plot.
+    #[arg(long)]
+    recipe: Option<PathBuf>,
+
+    /// Use the same value range for all plots generated from a recipe.
+    #[arg(long, requires = "recipe")]
+    shared_value_axis: bool,
+
+    /// Keep only this many groups with the most files or the highest median,
+    /// and combine the remaining groups into a group named `other`.
+    #[arg(long)]
+    top_groups: Option<usize>,
+
+    /// Split each group into the files with even and odd seeds, to check if the results depend on the seed.
+    /// Run sets with unequally many even and odd seeds, e.g. an odd amount of seeds, are reported.
+    #[arg(long)]
+    split_by_seed_parity: bool,
+
+    /// For each statistics file, read the top-level fields of its sidecar with extension `.meta.json`,
+    /// e.g. the git commit or the host, such that the files can be grouped by them.
+    #[arg(long)]
+    load_sidecar_meta: bool,
+
+    /// Instead of failing on statistics files that cannot be parsed, e.g. because their run was killed while writing them,
+    /// recover them from their complete lines if their parameters and statistics are complete.
+    /// Files that cannot be recovered, e.g. because statistics were cut off, are skipped.
+    #[arg(long)]
+    tolerate_truncated: bool,
+
+    /// Instead of failing on statistics files that cannot be read or parsed, report and skip them.
+    #[arg(long)]
+    skip_errors: bool,
+
+    /// Cache the parsed statistics files in this directory, and load unchanged statistics files from it in later runs.
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+
+    /// Neither read nor write the cache given by `--cache-dir`.
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Split each group by the value of this field of the metadata sidecars.
+    /// Can be given multiple times.
+    #[arg(long, requires = "load_sidecar_meta")]
+    group_by_meta: Vec<String>,
+
+    /// Instead of plotting, print the size of each group and the amount of files merged into each key.
+    #[arg(long)]
+    group_size_report: bool,
+
+    /// Instead of plotting, print the Pearson and Spearman correlation between two metrics,
+    /// given as `x=<metric>,y=<metric>`, over all files and per group.
+    #[arg(long)]
+    correlate: Option<MetricPair>,
+
+    /// Instead of the default plot, draw all files as points of two metrics, given as `x=<metric>,y=<metric>`,
+    /// together with the Pareto front of each group, i.e. the files for which no other file of the group
+    /// is at least as good in both metrics, where lower is better.
+    #[arg(long, conflicts_with_all = ["histogram", "recipe", "raw_line", "baseline_dir"])]
+    pareto: Option<MetricPair>,
+
+    /// Split the plot into a grid of charts with shared axes, one for each value of this parameter.
+    #[arg(long, value_enum, conflicts_with_all = ["histogram", "recipe", "raw_line", "baseline_dir"])]
+    facet: Option<AlignmentParameterName>,
+
+    /// Print the area under the curve through the medians of each group, integrated over the key in untransformed space.
+    /// This summarises e.g. the total search effort over all costs in a single number.
+    #[arg(long, conflicts_with = "histogram")]
+    report_auc: bool,
+
+    /// Check that the median of each group is monotonic in the key, and report each pair of adjacent keys that violates this.
+    /// This catches data collection bugs as well as genuinely surprising behaviour.
+    #[arg(long, conflicts_with = "histogram")]
+    check_monotonic: Option<Monotonicity>,
+
+    /// Instead of plotting, print the median, mean, minimum, maximum and amount of the plotted values
+    /// of each group and key as JSON object to stdout.
+    /// Byte values are given in the unit of the plot, which each key names as `unit`.
+    /// The log is written to stderr.
+    #[arg(long, conflicts_with = "histogram")]
+    summary_json: bool,
+
+    /// Additionally write the merged statistics of each group and key into this file,
+    /// as TOML if its extension is `toml` and as JSON otherwise.
+    #[arg(long, conflicts_with_all = ["recipe", "histogram"])]
+    dump_merged: Option<PathBuf>,
+
+    /// Additionally write all statistics files that remain after filtering into this csv file,
+    /// with one row per file and one column per parameter and strategy, as well as the main statistics.
+    #[arg(long)]
+    csv: Option<PathBuf>,
+
+    /// Additionally write all statistics files that remain after filtering into this parquet file,
+    /// with one column per parameter and statistic.
+    #[cfg(feature = "parquet")]
+    #[arg(long)]
+    parquet: Option<PathBuf>,
+
+    /// Instead of plotting, print a field-by-field comparison of the statistics of the two given files.
+    #[arg(long, num_args = 2, value_names = ["A", "B"])]
+    diff_files: Option<Vec<PathBuf>>,
+
+    /// Additionally write the legend of the groups into this file, without axes or data.
+    /// Its extension is replaced by the one of the output format.
+    /// With a recipe, all plots must have the same groups.
+    #[arg(long)]
+    legend_only: Option<PathBuf>,
+
+    /// The arrangement of the groups in the legend written by `--legend-only`.
+    #[arg(long, value_enum, default_value_t = LegendLayout::Vertical)]
+    legend_layout: LegendLayout,
+
+    /// A toml file describing the styling of the plots.
+    #[arg(long)]
+    theme: Option<PathBuf>,
+
+    /// The axes at whose labels grid lines are drawn.
+    /// Overrides the theme.
+    #[arg(long)]
+    grid: Option<GridLines>,
+
+    /// The style of the grid lines.
+    /// Overrides the theme.
+    #[arg(long)]
+    grid_style: Option<GridStyle>,
+
+    /// Do not fill the background of the plots, e.g. to overlay them on coloured slides.
+    /// Overrides the background of the theme.
+    /// The legend keeps its translucent white background, such that it stays legible.
+    /// Png files have no transparency, so they are filled white instead.
+    #[arg(long)]
+    transparent_background: bool,
+
+    /// The whisker styles assigned to the groups, in order, paralleling the palette.
+    /// Overrides the theme.
+    #[arg(long, value_enum, value_delimiter = ',')]
+    whisker_styles: Option<Vec<WhiskerStyle>>,
+
+    /// Color the groups with colors spread evenly over the given colormap instead of the palette of the theme, e.g. for ordered groups.
+    /// The colormap is sampled once per group of each plot.
+    #[arg(long, value_enum)]
+    colormap: Option<Colormap>,
+
+    /// Additionally read statistics from the rows of an SQLite database.
+    /// The columns are mapped to the fields with the same name as in statistics toml files.
+    #[cfg(feature = "sqlite")]
+    #[arg(long)]
+    sqlite: Option<PathBuf>,
+
+    /// The query used to read statistics from the SQLite database.
+    #[cfg(feature = "sqlite")]
+    #[arg(long, requires = "sqlite", default_value = "SELECT * FROM statistics")]
+    query: String,
+
+    /// Map fields to differently named columns of the SQLite database, given as `field=column`.
+    #[cfg(feature = "sqlite")]
+    #[arg(long, requires = "sqlite", value_delimiter = ',', value_parser = sqlite::parse_column_mapping)]
+    column_map: Vec<(String, String)>,
+
+    /// A directory of statistics toml files of a previous run, drawn as solid boxes
+    /// next to the hatched boxes of the given statistics files.
+    #[arg(long)]
+    baseline_dir: Option<PathBuf>,
+
+    /// Plot the values relative to this group, dividing each value by the median value of the group at the same key.
+    /// Keys at which the group has no nonzero value are skipped.
+    /// The group itself is not plotted, instead a dashed line marks the ratio one, and on a logarithmic value axis, a speedup and slowdown by the same factor are symmetric.
+    #[arg(long, conflicts_with_all = ["recipe", "baseline_dir"])]
+    baseline: Option<String>,
+
+    /// Also collect the toml files in subdirectories of the given directories, including the baseline directory.
+    #[arg(long)]
+    recursive: bool,
+
     /// The statistics toml files to use for the plots.
+    /// Directories are replaced by the toml files in them,
+    /// and file names with the wildcards `*` and `?` by the matching toml files, which avoids overlong command lines.
+    /// A `-` reads further such paths from stdin, one per line, ignoring blank lines and lines starting with `#`.
     #[arg()]
     statistics_files: Vec<PathBuf>,
 }
 
-fn main() {
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
     simplelog::TermLogger::init(
         log::LevelFilter::Info,
         Default::default(),
-        simplelog::TerminalMode::Mixed,
+        if cli.summary_json {
+            // Keep stdout clean for the summary.
+            simplelog::TerminalMode::Stderr
+        } else {
+            simplelog::TerminalMode::Mixed
+        },
         simplelog::ColorChoice::Auto,
-    )
-    .unwrap();
+    )?;
 
-    let cli = Cli::parse();
+    let _diagnostics_json_writer = cli.diagnostics_json.clone().map(DiagnosticsJsonWriter::new);
 
-    if cli.statistics_files.is_empty() {
-        panic!("No statistics files given.");
+    if let Some(min_box_pixels) = cli.min_box_pixels {
+        if !(min_box_pixels > 0.0 && min_box_pixels.is_finite()) {
+            bail!("If set, the minimum box width must be positive.");
+        }
+    }
+    if cli.output_format == OutputFormat::Png && !cfg!(feature = "png") {
+        bail!("Writing png files requires the png feature.");
+    }
+    if cli.max_points_per_box == Some(0) {
+        bail!("If set, the maximum amount of points per box must be at least one.");
+    }
+    if cli.width == 0 || cli.height == 0 {
+        bail!("The width and height of the plots must be at least one pixel.");
+    }
+    if let Some(bin_width) = cli.bin_width {
+        if !(bin_width > 0.0 && bin_width.is_finite()) {
+            bail!("If set, the bin width must be positive.");
+        }
+    }
+    if cli.histogram_round_to == Some(0) {
+        bail!("If set, the histogram rounding granularity must be at least one.");
+    }
+    if let Some(timeout) = cli.timeout {
+        if !(timeout > 0.0 && timeout.is_finite()) {
+            bail!("If set, the timeout must be positive.");
+        }
+    }
+    if let Some(violin_bandwidth) = cli.violin_bandwidth {
+        if !(violin_bandwidth > 0.0 && violin_bandwidth.is_finite()) {
+            bail!("If set, the violin bandwidth must be positive.");
+        }
+    }
+    if !(cli.ridgeline_offset > 0.0 && cli.ridgeline_offset.is_finite()) {
+        bail!("The ridgeline offset must be positive.");
+    }
+    if cli.fan_percentiles.len() % 2 != 0
+        || cli
+            .fan_percentiles
+            .iter()
+            .any(|percentile| !(0.0..=100.0).contains(percentile))
+        || cli
+            .fan_percentiles
+            .windows(2)
+            .any(|window| window[0] >= window[1])
+    {
+        bail!("The fan percentiles must be an even amount of strictly increasing percentiles between 0 and 100.");
+    }
+    if cli
+        .percentile
+        .iter()
+        .any(|percentile| !(0.0..=100.0).contains(percentile))
+    {
+        bail!("The percentiles must be between 0 and 100.");
+    }
+    if cli.baseline_dir.is_some() && (cli.histogram || cli.plot_style != PlotStyle::Boxplot) {
+        bail!("A baseline can only be drawn in boxplots.");
+    }
+    if cli.significance
+        && !matches!(
+            cli.plot_style,
+            PlotStyle::Boxplot | PlotStyle::Scatter | PlotStyle::ErrorBar | PlotStyle::Violin
+        )
+    {
+        bail!(
+            "Significance can only be annotated in boxplots, scatter plots, error bar plots and violin plots."
+        );
+    }
+    if let Some(max_relative_standard_error) = cli.max_relative_standard_error {
+        if max_relative_standard_error <= 0.0 || max_relative_standard_error.is_nan() {
+            bail!("If set, the maximum relative standard error must be positive.");
+        }
+    }
+    if cli.facet.is_some() && cli.plot_style == PlotStyle::Ridgeline {
+        bail!("Ridgeline plots cannot be split into facets.");
+    }
+    if cli.max_label_length == Some(0) {
+        bail!("If set, the maximum label length must be at least one.");
+    }
+    if cli.top_groups == Some(0) {
+        bail!("If set, the amount of top groups must be at least one.");
     }
     if cli.key_bucket_amount == Some(0) {
-        panic!("If set, key buckets must be at least one.");
+        bail!("If set, key buckets must be at least one.");
+    }
+    if !(cli.value_log_base > 1.0 && cli.value_log_base.is_finite()) {
+        bail!("The value log base must be larger than one.");
+    }
+    if let Some(log_epsilon) = cli.log_epsilon {
+        if !(log_epsilon > 0.0 && log_epsilon.is_finite()) {
+            bail!("If set, the log epsilon must be positive.");
+        }
     }
     if cli.value_polynomial_degree < 1.0 || R64::try_new(cli.value_polynomial_degree).is_none() {
-        panic!("If set, the value polynomial degree must be at least one.");
+        bail!("If set, the value polynomial degree must be at least one.");
     }
 
-    let mut buffer = String::new();
-    let statistics_files: Vec<_> = cli
-        .statistics_files
-        .into_iter()
-        .map(|path| {
-            let mut file = File::open(path).unwrap();
-            buffer.clear();
-            file.read_to_string(&mut buffer).unwrap();
-            toml::from_str::<StatisticsFile>(&buffer)
-                .unwrap()
-                .deserialisation_post_processing()
-        })
+    let mut theme = cli.theme.as_ref().map(Theme::from_file).unwrap_or_default();
+    if let Some(grid) = cli.grid {
+        theme.grid = grid;
+    }
+    if let Some(grid_style) = cli.grid_style {
+        theme.grid_style = grid_style;
+    }
+    if let Some(legend_position) = cli.legend_position {
+        theme.legend_position = legend_position;
+    }
+    if cli.transparent_background {
+        theme.background = None;
+        if cli.output_format == OutputFormat::Png {
+            diagnostics::report(
+                Level::Warn,
+                DiagnosticCategory::OpaqueBackground,
+                "Png files have no transparency, so their background is filled white",
+                [("output_format", "png")],
+            );
+        }
+    }
+    if let Some(whisker_styles) = cli.whisker_styles.clone() {
+        theme.whisker_styles = whisker_styles;
+    }
+    theme.colormap = cli.colormap;
+    let mut render_options = RenderOptions::new(&cli, theme);
+    let merged_percentiles: Vec<_> = cli
+        .percentile
+        .iter()
+        .map(|percentile| R64::new(percentile / 100.0))
         .collect();
 
-    grouped_linear_bar_plot(
-        &cli.output_directory,
-        "opened_nodes_by_cost",
-        "Alignment Cost",
-        "Opened Nodes",
-        (400, 400),
-        cli.key_bucket_amount,
-        cli.value_polynomial_degree,
-        &statistics_files,
-        |parameters| parameters.cost as f64,
-        |file| {
-            format!(
-                "{} len {}",
-                file.parameters.test_sequence_name, file.parameters.length
+    if let Some(diff_files) = &cli.diff_files {
+        let diff_files = load_statistics_files(diff_files, false, false, false, None);
+        print!(
+            "{}",
+            statistics_diff::diff_statistics(
+                &diff_files[0].statistics.statistics,
+                &diff_files[1].statistics.statistics,
             )
-        },
-        |file| {
-            let mut parameters = file.parameters.clone();
-            parameters.seed = 0;
-            parameters.cost = 0;
-            parameters
-        },
-        |statistics| statistics.opened_nodes.raw(),
+        );
+        return Ok(());
+    }
+
+    let cache = cli
+        .cache_dir
+        .as_ref()
+        .filter(|_| !cli.no_cache)
+        .map(StatisticsCache::new);
+    let mut statistics_files = load_statistics_files(
+        &expand_statistics_file_arguments(&cli.statistics_files, cli.recursive),
+        cli.load_sidecar_meta,
+        cli.tolerate_truncated,
+        cli.skip_errors,
+        cache.as_ref(),
     );
-}
+    #[cfg(feature = "sqlite")]
+    if let Some(database) = &cli.sqlite {
+        statistics_files.extend(sqlite::read_statistics_files(
+            database,
+            &cli.query,
+            &cli.column_map,
+        ));
+    }
+    if statistics_files.is_empty() {
+        bail!("No statistics files given.");
+    }
 
-#[allow(clippy::too_many_arguments)]
-fn grouped_linear_bar_plot<GroupName: Ord + ToString>(
-    output_directory: impl AsRef<Path>,
-    name: impl ToString,
-    key_name: impl ToString,
-    value_name: impl ToString,
-    size: (u32, u32),
-    key_bucket_amount: Option<usize>,
-    value_polynomial_degree: f64,
-    statistics_files: &[StatisticsFile],
-    key_fn: impl Fn(&AlignmentParameters) -> f64,
-    group_name_fn: impl Fn(&StatisticsFile) -> GroupName,
-    merge_key_fn: impl Fn(&StatisticsFile) -> AlignmentParameters,
-    value_fn: impl Fn(&AlignmentStatistics) -> f64,
-) {
-    let groups = group_files(statistics_files, group_name_fn);
-    let (groups, min_key, max_key) =
-        merge_and_sort_files_in_groups(groups, key_bucket_amount, &key_fn, merge_key_fn);
+    filter_statistics_files(&mut statistics_files, &cli);
+    if statistics_files.is_empty() {
+        bail!("No statistics files left after filtering.");
+    }
+    if let Some(expected_seeds) = &cli.expect_seeds {
+        check_expected_seeds(&statistics_files, expected_seeds, &cli.group_by_meta);
+    }
+    if cli.split_by_seed_parity {
+        check_seed_parity_balance(&statistics_files, &cli.group_by_meta);
+    }
+    if let Some(csv) = &cli.csv {
+        data_export::write_statistics_csv(csv, &statistics_files, cli.drop_constant_columns);
+    }
+    #[cfg(feature = "parquet")]
+    if let Some(parquet) = &cli.parquet {
+        parquet_export::write_statistics_parquet(
+            parquet,
+            &statistics_files,
+            cli.drop_constant_columns,
+        );
+    }
 
-    let (min_value, max_value) = groups
-        .values()
-        .flat_map(|group| group.iter())
-        .map(|file| value_fn(&file.max_statistics))
-        .fold((f64::MAX, 0.0), |(min, max), value| {
-            let min = if min > value { value } else { min };
-            let max = if max < value { value } else { max };
-            (min, max)
-        });
-    let value_epsilon = min_value
-        .abs()
-        .max(max_value.abs())
-        .max(max_value - min_value)
-        * 1e-12;
-    let min_chart_value = min_value.powf(1.0 / value_polynomial_degree);
-    let max_chart_value = max_value.powf(1.0 / value_polynomial_degree);
+    let baseline_files = cli
+        .baseline_dir
+        .as_ref()
+        .map(|baseline_directory| {
+            info!("Loading baseline from {baseline_directory:?}");
+            let mut baseline_files = load_statistics_files(
+                &statistics_files_in_directory(baseline_directory, cli.recursive),
+                cli.load_sidecar_meta,
+                cli.tolerate_truncated,
+                cli.skip_errors,
+                cache.as_ref(),
+            );
+            filter_statistics_files(&mut baseline_files, &cli);
+            if baseline_files.is_empty() {
+                bail!("No baseline statistics files left in {baseline_directory:?}.");
+            }
+            anyhow::Ok(baseline_files)
+        })
+        .transpose()?;
 
-    let mut output_file_name = name.to_string();
-    output_file_name.push_str(".svg");
-    let mut output_file = output_directory.as_ref().to_owned();
-    output_file.push(output_file_name);
-    info!("Creating drawing area");
-    let root = SVGBackend::new(&output_file, size).into_drawing_area();
-    root.fill(&TRANSPARENT).unwrap();
+    if let Some(recipe) = &cli.recipe {
+        let recipe = Recipe::from_file(recipe);
+        for metric in recipe
+            .plots
+            .iter()
+            .map(|plot| plot.metric)
+            .collect::<BTreeSet<_>>()
+        {
+            metric.check_applicability(&statistics_files);
+        }
+        if recipe.plots.iter().any(|plot| plot.split_by_seed_parity) {
+            check_seed_parity_balance(&statistics_files, &[]);
+        }
+        if cli.group_size_report {
+            for plot in &recipe.plots {
+                println!("{}:", plot.name);
+                print!("{}", plot.group_size_report(&statistics_files));
+            }
+            return Ok(());
+        }
+
+        let mut plot_data: Vec<_> = recipe
+            .plots
+            .iter()
+            .map(|plot| plot.prepare(&statistics_files, cli.require_balanced_groups))
+            .collect();
+        if cli.shared_value_axis {
+            share_value_range(&mut plot_data);
+        }
+        if cli.report_auc {
+            for (plot, data) in recipe.plots.iter().zip(&plot_data) {
+                print!(
+                    "{}",
+                    auc_report(&plot.name, data, |file| plot.metric.value(file))
+                );
+            }
+        }
+        if let Some(monotonicity) = cli.check_monotonic {
+            for (plot, data) in recipe.plots.iter().zip(&plot_data) {
+                check_monotonicity(&plot.name, data, monotonicity, |file| {
+                    plot.metric.value(file)
+                });
+            }
+        }
+        if cli.summary_json {
+            let summary: serde_json::Map<_, _> = recipe
+                .plots
+                .iter()
+                .zip(&plot_data)
+                .map(|(plot, data)| {
+                    (
+                        plot.name.clone(),
+                        summary_json(
+                            data,
+                            |file| plot.metric.value(file),
+                            &render_options.percentiles,
+                            None,
+                        ),
+                    )
+                })
+                .collect();
+            println!("{}", serde_json::Value::Object(summary));
+            return Ok(());
+        }
+        if let Some(legend_only) = &cli.legend_only {
+            let group_names: Vec<Vec<_>> = plot_data
+                .iter()
+                .map(|data| data.groups.keys().cloned().collect())
+                .collect();
+            if group_names.windows(2).any(|window| window[0] != window[1]) {
+                bail!(
+                    "The plots of the recipe have different groups, so they cannot share a legend."
+                );
+            }
+            if let Some(group_names) = group_names.first() {
+                legend_image(
+                    legend_only,
+                    group_names,
+                    cli.legend_layout,
+                    cli.output_format,
+                    &render_options.theme,
+                    render_options.max_label_length,
+                )?;
+            }
+        }
+
+        let baseline_data: Vec<_> = recipe
+            .plots
+            .iter()
+            .map(|plot| {
+                baseline_files
+                    .as_ref()
+                    .map(|baseline_files| plot.prepare(baseline_files, cli.require_balanced_groups))
+            })
+            .collect();
+
+        for ((plot, data), baseline_data) in recipe.plots.iter().zip(&plot_data).zip(&baseline_data)
+        {
+            plot.render(
+                &cli.output_directory,
+                &render_options,
+                data,
+                baseline_data.as_ref(),
+            )?;
+        }
+        return Ok(());
+    }
+
+    cli.metric.check_applicability(&statistics_files);
+    if let Some(histogram_weight) = cli.histogram_weight {
+        histogram_weight.check_applicability(&statistics_files);
+    }
+
+    let first_timestamp = cli.by_timestamp.then(|| {
+        statistics_files
+            .iter()
+            .filter_map(|file| file.parameters.parsed_timestamp())
+            .min()
+            // Files without valid timestamp were dropped, and at least one file is left.
+            .unwrap()
+    });
+    let key_fn = |parameters: &AlignmentParameters| {
+        if let Some(first_timestamp) = first_timestamp {
+            let timestamp = parameters.parsed_timestamp().unwrap();
+            (timestamp - first_timestamp).num_milliseconds() as f64 / 1000.0
+        } else if cli.metric == Metric::Cost {
+            // Keying the cost by itself would show nothing, so show its distribution over the lengths instead.
+            parameters.length as f64
+        } else {
+            parameters.cost as f64
+        }
+    };
+    let varying_strategies = AlignmentParameters::varying_strategies(
+        statistics_files.iter().map(|file| &file.parameters),
+    );
+    if !varying_strategies.is_empty() {
+        info!("Distinguishing groups by the strategies {varying_strategies:?}");
+    }
+    let group_name_fn = |file: &StatisticsFile| {
+        let mut group_name = format!(
+            "{} len {}",
+            file.parameters.test_sequence_name, file.parameters.length
+        );
+        for strategy in &varying_strategies {
+            let value = file
+                .parameters
+                .strategies
+                .get(strategy)
+                .map_or("?", String::as_str);
+            group_name.push_str(&format!(" {strategy}={value}"));
+        }
+        let mut group_name = if cli.split_by_seed_parity {
+            split_by_seed_parity(group_name, file)
+        } else {
+            group_name
+        };
+        for field in &cli.group_by_meta {
+            let value = file.parameters.extra.get(field).map_or("?", String::as_str);
+            group_name.push_str(&format!(" {field}={value}"));
+        }
+        OrderedGroupName::new(group_name, &cli.group_order)
+    };
+    for listed_name in &cli.group_order {
+        if !statistics_files
+            .iter()
+            .any(|file| group_name_fn(file).to_string() == *listed_name)
+        {
+            debug!("Ignoring {listed_name:?} of the group order, since there is no such group");
+        }
+    }
+    let merge_key_fn = |file: &StatisticsFile| {
+        let mut parameters = run_set(file, &cli.group_by_meta);
+        if cli.by_timestamp {
+            parameters.timestamp.clone_from(&file.parameters.timestamp);
+        }
+        parameters
+    };
+    let unscaled_value_fn = |file: &StatisticsFile| {
+        let value = cli.metric.value(file);
+        if !cli.per_base {
+            value
+        } else if file.parameters.length == 0 {
+            // Non-finite values are skipped when plotting.
+            f64::NAN
+        } else {
+            value / file.parameters.length as f64
+        }
+    };
+    // Byte values are scaled to the largest unit in which the maximum value is at least one.
+    let byte_unit = cli.metric.is_bytes().then(|| {
+        let byte_unit = ByteUnit::for_max_value(
+            statistics_files
+                .iter()
+                .map(unscaled_value_fn)
+                .filter(|value| value.is_finite())
+                .fold(0.0, f64::max),
+        );
+        info!("Plotting {} in {}", cli.metric.name(), byte_unit.suffix);
+        byte_unit
+    });
+    let value_divisor = byte_unit.map_or(1.0, |byte_unit| byte_unit.divisor);
+    let scaled_value_fn = |file: &StatisticsFile| unscaled_value_fn(file) / value_divisor;
+    let baseline_medians = cli.baseline.as_deref().map(|baseline| {
+        baseline_medians(
+            &statistics_files,
+            baseline,
+            key_fn,
+            group_name_fn,
+            scaled_value_fn,
+        )
+    });
+    if let Some(baseline) = &cli.baseline {
+        // Relative to itself, the baseline is the dashed line at one.
+        statistics_files.retain(|file| group_name_fn(file).to_string() != *baseline);
+        if statistics_files.is_empty() {
+            bail!("There are no groups besides the baseline group {baseline:?}.");
+        }
+    }
+    let value_fn = |file: &StatisticsFile| match &baseline_medians {
+        // Non-finite values are skipped when plotting.
+        Some(baseline_medians) => baseline_medians
+            .get(&R64::new(key_fn(&file.parameters)))
+            .map_or(f64::NAN, |median| scaled_value_fn(file) / median),
+        None => scaled_value_fn(file),
+    };
+    let metric_name = match (&cli.baseline, byte_unit) {
+        // Ratios have no unit.
+        (Some(baseline), _) => format!("{} relative to {baseline}", cli.metric.name()),
+        (None, Some(byte_unit)) => format!("{} [{}]", cli.metric.name(), byte_unit.suffix),
+        (None, None) => cli.metric.to_string(),
+    };
+    let metric_identifier = if cli.baseline.is_some() {
+        format!("{}_relative", cli.metric.identifier())
+    } else {
+        cli.metric.identifier()
+    };
+    let (metric_identifier, metric_name) = if cli.per_base {
+        (
+            format!("{metric_identifier}_per_base"),
+            format!("{metric_name} /base"),
+        )
+    } else {
+        (metric_identifier, metric_name)
+    };
+    if cli.baseline.is_none() {
+        render_options.value_unit = byte_unit.map(|byte_unit| {
+            if cli.per_base {
+                format!("{}/base", byte_unit.suffix)
+            } else {
+                byte_unit.suffix.to_string()
+            }
+        });
+    }
+
+    if cli.group_size_report {
+        print!(
+            "{}",
+            group_size_report(
+                &statistics_files,
+                cli.key_bucket_amount,
+                cli.key_bucket_scale,
+                key_fn,
+                group_name_fn,
+                merge_key_fn,
+            )
+        );
+        return Ok(());
+    }
+
+    if let Some(metric_pair) = cli.correlate {
+        print!(
+            "{}",
+            correlation_report(&statistics_files, metric_pair, group_name_fn)
+        );
+        return Ok(());
+    }
+
+    if let Some(metric_pair) = cli.pareto {
+        metric_pair.x.check_applicability(&statistics_files);
+        metric_pair.y.check_applicability(&statistics_files);
+        grouped_pareto_plot(
+            &cli.output_directory,
+            format!(
+                "pareto_{}_{}",
+                metric_pair.x.identifier(),
+                metric_pair.y.identifier()
+            ),
+            metric_pair,
+            (cli.width, cli.height),
+            &render_options,
+            &statistics_files,
+            group_name_fn,
+        )?;
+        return Ok(());
+    }
+
+    if let Some(legend_only) = &cli.legend_only {
+        let group_names: Vec<_> = group_files(&statistics_files, group_name_fn)
+            .into_keys()
+            .collect();
+        legend_image(
+            legend_only,
+            &group_names,
+            cli.legend_layout,
+            cli.output_format,
+            &render_options.theme,
+            render_options.max_label_length,
+        )?;
+    }
+
+    if cli.histogram {
+        grouped_histogram(
+            &cli.output_directory,
+            format!("{metric_identifier}_histogram"),
+            metric_name,
+            cli.histogram_weight
+                .map(|weight| format!("Σ {weight}"))
+                .unwrap_or_else(|| "Frequency".to_string()),
+            (cli.width, cli.height),
+            &render_options,
+            &statistics_files,
+            group_name_fn,
+            value_fn,
+            |file| {
+                cli.histogram_weight
+                    .map(|weight| weight.value(file))
+                    .unwrap_or(1.0)
+            },
+            cli.bin_width,
+            cli.histogram_round_to.unwrap_or(1),
+            cli.bucket_intervals.as_ref(),
+        )?;
+        return Ok(());
+    }
+
+    if cli.ts_proportion {
+        grouped_proportion_plot(
+            &cli.output_directory,
+            "template_switch_proportion",
+            "TS",
+            (cli.width, cli.height),
+            &render_options,
+            &statistics_files,
+            group_name_fn,
+            StatisticsFile::template_switch_amount,
+        )?;
+        return Ok(());
+    }
+
+    let (name, key_name) = if let Some(first_timestamp) = first_timestamp {
+        (
+            format!("{metric_identifier}_by_timestamp"),
+            format!("Time since {} [s]", first_timestamp.to_rfc3339()),
+        )
+    } else if cli.metric == Metric::Cost {
+        (
+            format!("{metric_identifier}_by_length"),
+            "Sequence Length".to_string(),
+        )
+    } else {
+        (
+            format!("{metric_identifier}_by_cost"),
+            "Alignment Cost".to_string(),
+        )
+    };
+    if cli.raw_line {
+        grouped_raw_line_plot(
+            &cli.output_directory,
+            format!("{name}_raw"),
+            key_name,
+            &metric_name,
+            (cli.width, cli.height),
+            AxisTransform::new(
+                cli.value_axis,
+                cli.value_polynomial_degree,
+                cli.value_log_base,
+            ),
+            &render_options,
+            &statistics_files,
+            group_name_fn,
+            key_fn,
+            value_fn,
+        )?;
+        return Ok(());
+    }
+
+    let data = prepare_grouped_plot(
+        &statistics_files,
+        cli.key_bucket_amount,
+        cli.key_bucket_scale,
+        &merged_percentiles,
+        cli.top_groups,
+        cli.require_balanced_groups,
+        key_fn,
+        group_name_fn,
+        merge_key_fn,
+        value_fn,
+    );
+    if let Some(dump_merged) = &cli.dump_merged {
+        data_export::write_merged_statistics(dump_merged, &data.groups);
+    }
+    if cli.report_auc {
+        print!("{}", auc_report(&name, &data, value_fn));
+    }
+    if let Some(monotonicity) = cli.check_monotonic {
+        check_monotonicity(&name, &data, monotonicity, value_fn);
+    }
+    if cli.summary_json {
+        let summary = summary_json(
+            &data,
+            value_fn,
+            &render_options.percentiles,
+            render_options.value_unit.as_deref(),
+        );
+        println!("{}", serde_json::json!({ name: summary }));
+        return Ok(());
+    }
+    if let Some(facet) = cli.facet {
+        let mut facet_files: BTreeMap<_, Vec<_>> = Default::default();
+        for file in &statistics_files {
+            facet_files
+                .entry(facet.format_value(&file.parameters))
+                .or_default()
+                .push(file.clone());
+        }
+        let facets = facet_files
+            .into_iter()
+            .map(|(facet_name, files)| {
+                let data = prepare_grouped_plot(
+                    &files,
+                    cli.key_bucket_amount,
+                    cli.key_bucket_scale,
+                    &merged_percentiles,
+                    cli.top_groups,
+                    cli.require_balanced_groups,
+                    key_fn,
+                    group_name_fn,
+                    merge_key_fn,
+                    value_fn,
+                );
+                (facet_name, data)
+            })
+            .collect();
+        grouped_faceted_plot(
+            &cli.output_directory,
+            format!("{name}_by_{}", facet.identifier()),
+            key_name,
+            &metric_name,
+            (cli.width, cli.height),
+            AxisTransform::new(
+                cli.value_axis,
+                cli.value_polynomial_degree,
+                cli.value_log_base,
+            ),
+            &render_options,
+            facets,
+            value_fn,
+        )?;
+        return Ok(());
+    }
+    let baseline_data = baseline_files.as_ref().map(|baseline_files| {
+        prepare_grouped_plot(
+            baseline_files,
+            cli.key_bucket_amount,
+            cli.key_bucket_scale,
+            &merged_percentiles,
+            cli.top_groups,
+            cli.require_balanced_groups,
+            key_fn,
+            group_name_fn,
+            merge_key_fn,
+            value_fn,
+        )
+    });
+    grouped_linear_bar_plot(
+        &cli.output_directory,
+        name,
+        key_name,
+        metric_name,
+        (cli.width, cli.height),
+        AxisTransform::new(
+            cli.value_axis,
+            cli.value_polynomial_degree,
+            cli.value_log_base,
+        ),
+        &render_options,
+        &data,
+        baseline_data.as_ref(),
+        value_fn,
+    )?;
+    Ok(())
+}
+
+/// The width of the boxes of a boxplot.
+const BOX_WIDTH_PIXELS: u32 = 10;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum PlotStyle {
+    /// A box with whiskers for each key.
+    Boxplot,
+    /// A line through the median of each key.
+    Line,
+    /// A line through the median of each key, surrounded by nested ribbons between percentiles.
+    /// Wider ribbons are drawn more transparent.
+    Fan,
+    /// A density curve of all values for each group, stacked vertically.
+    /// The keys are ignored.
+    Ridgeline,
+    /// A point for each file, spread around the position of the box of its group to keep overlapping points visible.
+    Scatter,
+    /// An error bar for each key, spanning one standard deviation around the mean.
+    /// Suited better than boxes if there are only a few seeds.
+    ErrorBar,
+    /// A density curve of the values for each key, mirrored around the position of the box of its group.
+    /// The density is estimated on the value axis, i.e. after transforming the values.
+    Violin,
+}
+
+/// The direction in which a metric is expected to change with the key.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum Monotonicity {
+    Increasing,
+    Decreasing,
+}
+
+/// The file format of the plots.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum OutputFormat {
+    Svg,
+    /// Requires the `png` feature.
+    Png,
+}
+
+impl OutputFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Png => "png",
+        }
+    }
+}
+
+/// How groups without any finite value are shown.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum EmptyGroupPolicy {
+    /// Leave the group out of the legend.
+    Omit,
+    /// Show the group in grey in the legend, marked with "(no data)".
+    Grey,
+}
+
+/// The legend color of groups without data under [`EmptyGroupPolicy::Grey`].
+const EMPTY_GROUP_COLOR: RGBColor = RGBColor(160, 160, 160);
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum LegendLayout {
+    Horizontal,
+    Vertical,
+}
+
+/// Options that apply to the rendering of all plots.
+#[derive(Clone)]
+struct RenderOptions {
+    theme: Theme,
+    output_format: OutputFormat,
+    auto_caption: bool,
+    caption_template: Option<String>,
+    caption: Option<String>,
+    /// The unit into which the plotted values were scaled, if any, such that exported values are not misread.
+    value_unit: Option<String>,
+    plot_style: PlotStyle,
+    interpolate_keys: bool,
+    ridgeline_offset: f64,
+    violin_bandwidth: Option<f64>,
+    fan_percentiles: Vec<f64>,
+    percentiles: Vec<f64>,
+    reference_curve: Option<ReferenceCurve>,
+    overall_median_line: bool,
+    baseline_group: Option<String>,
+    trendline: bool,
+    auto_transform: bool,
+    log_zero_policy: LogZeroPolicy,
+    log_epsilon: Option<f64>,
+    on_top: Vec<String>,
+    empty_group_policy: EmptyGroupPolicy,
+    key_ticks: Option<KeyTicks>,
+    show_trajectories: bool,
+    annotate_total_runtime: bool,
+    annotate_facet_n: bool,
+    hide_transform_in_label: bool,
+    emit_data: bool,
+    debug_layout: bool,
+    drop_constant_columns: bool,
+    annotate_bucket_n: bool,
+    significance: bool,
+    mark_suboptimal: bool,
+    max_points_per_box: Option<usize>,
+    min_box_pixels: Option<f64>,
+    min_samples_for_reliable: Option<usize>,
+    max_relative_standard_error: Option<f64>,
+    compact: bool,
+    notched: bool,
+    width_by_sample_count: bool,
+    label_outliers: bool,
+    max_label_length: Option<usize>,
+}
+
+impl RenderOptions {
+    /// The render options given on the command line, with the theme resolved from the theme file and its overriding flags.
+    fn new(cli: &Cli, theme: Theme) -> Self {
+        Self {
+            theme,
+            output_format: cli.output_format,
+            auto_caption: cli.auto_caption,
+            caption_template: cli.caption_template.clone(),
+            value_unit: None,
+            caption: cli.caption.clone(),
+            plot_style: cli.plot_style,
+            interpolate_keys: cli.interpolate_keys,
+            ridgeline_offset: cli.ridgeline_offset,
+            violin_bandwidth: cli.violin_bandwidth,
+            fan_percentiles: cli.fan_percentiles.clone(),
+            percentiles: cli.percentile.clone(),
+            reference_curve: cli.reference_curve.clone(),
+            overall_median_line: cli.overall_median_line,
+            baseline_group: cli.baseline.clone(),
+            trendline: cli.trendline,
+            auto_transform: cli.auto_transform,
+            log_zero_policy: LogZeroPolicy::resolve(cli.log_zero_policy, cli.log_epsilon),
+            log_epsilon: cli.log_epsilon,
+            on_top: cli.on_top.clone(),
+            empty_group_policy: cli.empty_group_policy,
+            key_ticks: cli.key_ticks.clone(),
+            show_trajectories: cli.show_trajectories,
+            annotate_total_runtime: cli.annotate_total_runtime,
+            annotate_facet_n: cli.annotate_facet_n,
+            hide_transform_in_label: cli.hide_transform_in_label,
+            emit_data: cli.emit_data,
+            debug_layout: cli.debug_layout,
+            drop_constant_columns: cli.drop_constant_columns,
+            annotate_bucket_n: cli.annotate_bucket_n,
+            significance: cli.significance,
+            mark_suboptimal: cli.mark_suboptimal,
+            max_points_per_box: cli.max_points_per_box,
+            min_box_pixels: cli.min_box_pixels,
+            min_samples_for_reliable: cli.min_samples_for_reliable,
+            max_relative_standard_error: cli.max_relative_standard_error,
+            compact: cli.compact,
+            notched: cli.notched,
+            width_by_sample_count: cli.width_by_sample_count,
+            label_outliers: cli.label_outliers,
+            max_label_length: cli.max_label_length,
+        }
+    }
+}
+
+/// The merged and sorted groups of a grouped plot, together with their key and value ranges.
+struct GroupedPlotData<GroupName> {
+    groups: BTreeMap<GroupName, Vec<MergedStatisticsFile>>,
+    key_bucket_amount: Option<usize>,
+    min_key: f64,
+    max_key: f64,
+    min_value: f64,
+    max_value: f64,
+    /// The smallest positive value, or infinity if there is none.
+    min_positive_value: f64,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prepare_grouped_plot<GroupName: Ord + ToString + for<'a> From<&'a str>>(
+    statistics_files: &[StatisticsFile],
+    key_bucket_amount: Option<usize>,
+    key_bucket_scale: KeyBucketScale,
+    percentiles: &[R64],
+    top_groups: Option<usize>,
+    require_balanced_groups: bool,
+    key_fn: impl Fn(&AlignmentParameters) -> f64,
+    group_name_fn: impl Fn(&StatisticsFile) -> GroupName,
+    merge_key_fn: impl Fn(&StatisticsFile) -> AlignmentParameters,
+    value_fn: impl Fn(&StatisticsFile) -> f64,
+) -> GroupedPlotData<GroupName> {
+    let groups = group_files(statistics_files, group_name_fn);
+    let (mut groups, min_key, max_key) = merge_and_sort_files_in_groups(
+        groups,
+        key_bucket_amount,
+        key_bucket_scale,
+        percentiles,
+        &key_fn,
+        merge_key_fn,
+    );
+    if let Some(top_groups) = top_groups {
+        groups = keep_top_groups(groups, top_groups, percentiles, &value_fn);
+    }
+    // Only the groups that are plotted need to be balanced.
+    check_equal_group_sizes(&groups, require_balanced_groups);
+
+    let (min_value, max_value) = groups
+        .values()
+        .flat_map(|group| group.iter())
+        .flat_map(|file| file.contained_files.iter())
+        .map(&value_fn)
+        .filter(|value| value.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+    let min_positive_value = min_positive_value(
+        groups
+            .values()
+            .flatten()
+            .flat_map(|file| &file.contained_files)
+            .map(&value_fn),
+    );
+
+    GroupedPlotData {
+        groups,
+        key_bucket_amount,
+        min_key,
+        max_key,
+        min_value,
+        max_value,
+        min_positive_value,
+    }
+}
+
+/// Keep the `amount` groups with the most files, and among those with equally many files the ones with the highest median value.
+/// The remaining groups are combined into a single group named `other`,
+/// merging all files with the same key.
+fn keep_top_groups<GroupName: Ord + ToString + for<'a> From<&'a str>>(
+    groups: BTreeMap<GroupName, Vec<MergedStatisticsFile>>,
+    amount: usize,
+    percentiles: &[R64],
+    value_fn: impl Fn(&StatisticsFile) -> f64,
+) -> BTreeMap<GroupName, Vec<MergedStatisticsFile>> {
+    if groups.len() <= amount {
+        return groups;
+    }
+
+    let mut ranked_groups: Vec<_> = groups
+        .into_iter()
+        .map(|(group_name, group)| {
+            let values: Vec<_> = group
+                .iter()
+                .flat_map(|file| &file.contained_files)
+                .map(&value_fn)
+                .filter(|value| value.is_finite())
+                .collect();
+            let median = if values.is_empty() {
+                f64::MIN
+            } else {
+                Quartiles::new(&values).median()
+            };
+            let file_amount: usize = group.iter().map(|file| file.contained_files.len()).sum();
+            (file_amount, R64::new(median), group_name, group)
+        })
+        .collect();
+    ranked_groups
+        .sort_by_key(|(file_amount, median, _, _)| std::cmp::Reverse((*file_amount, *median)));
+
+    let other_groups = ranked_groups.split_off(amount);
+    info!(
+        "Combining {} groups into group other: {}",
+        other_groups.len(),
+        other_groups
+            .iter()
+            .map(|(_, _, group_name, _)| group_name.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let mut other_files: BTreeMap<R64, Vec<StatisticsFile>> = Default::default();
+    for file in other_groups.into_iter().flat_map(|(_, _, _, group)| group) {
+        other_files
+            .entry(file.key)
+            .or_default()
+            .extend(file.contained_files);
+    }
+
+    let mut groups: BTreeMap<_, _> = ranked_groups
+        .into_iter()
+        .map(|(_, _, group_name, group)| (group_name, group))
+        .collect();
+    let other_name = GroupName::from("other");
+    if groups.contains_key(&other_name) {
+        panic!("Cannot combine groups into group other, since a group with that name exists.");
+    }
+    groups.insert(
+        other_name,
+        other_files
+            .into_iter()
+            .map(|(key, files)| {
+                MergedStatisticsFile::from_statistics_files(key, files, percentiles)
+            })
+            .collect(),
+    );
+    groups
+}
+
+/// The smallest positive value, or infinity if there is none.
+fn min_positive_value(values: impl IntoIterator<Item = f64>) -> f64 {
+    values
+        .into_iter()
+        .filter(|&value| value > 0.0)
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Set the value range of all given plots to the union of their value ranges.
+fn share_value_range<GroupName>(plots: &mut [GroupedPlotData<GroupName>]) {
+    let (min_value, max_value) = plots
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), plot| {
+            (min.min(plot.min_value), max.max(plot.max_value))
+        });
+    let min_positive_value = min_positive_value(plots.iter().map(|plot| plot.min_positive_value));
+    info!(
+        "Sharing value range {min_value}..{max_value} between {} plots",
+        plots.len()
+    );
+
+    for plot in plots {
+        plot.min_value = min_value;
+        plot.max_value = max_value;
+        plot.min_positive_value = min_positive_value;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn grouped_linear_bar_plot<GroupName: Ord + ToString>(
+    output_directory: impl AsRef<Path>,
+    name: impl ToString,
+    key_name: impl ToString,
+    value_name: impl ToString,
+    size: (u32, u32),
+    value_transform: AxisTransform,
+    render_options: &RenderOptions,
+    data: &GroupedPlotData<GroupName>,
+    baseline: Option<&GroupedPlotData<GroupName>>,
+    value_fn: impl Fn(&StatisticsFile) -> f64,
+) -> anyhow::Result<()> {
+    if render_options.plot_style == PlotStyle::Ridgeline {
+        return grouped_ridgeline_plot(
+            output_directory,
+            name,
+            value_name,
+            size,
+            value_transform,
+            render_options,
+            data,
+            value_fn,
+        );
+    }
+
+    let name = name.to_string();
+    let output_file = output_file(output_directory, &name)
+        .with_extension(render_options.output_format.extension());
+    with_drawing_area!(
+        &output_file,
+        size,
+        render_options.output_format,
+        &render_options.theme,
+        |root| draw_grouped_linear_bar_chart(
+            &root,
+            &output_file,
+            &name,
+            key_name,
+            value_name,
+            size,
+            value_transform,
+            render_options,
+            data,
+            baseline,
+            value_fn,
+        )?
+    )
+    .with_context(|| format!("plot {name} to {output_file:?}"))
+}
+
+/// Draw the chart of [`grouped_linear_bar_plot`] into the given drawing area of the given size.
+/// The data written with `emit_data` is named after `output_file`.
+#[allow(clippy::too_many_arguments)]
+fn draw_grouped_linear_bar_chart<GroupName: Ord + ToString, DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    output_file: &Path,
+    name: impl ToString,
+    key_name: impl ToString,
+    value_name: impl ToString,
+    size: (u32, u32),
+    value_transform: AxisTransform,
+    render_options: &RenderOptions,
+    data: &GroupedPlotData<GroupName>,
+    baseline: Option<&GroupedPlotData<GroupName>>,
+    value_fn: impl Fn(&StatisticsFile) -> f64,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let skipped = &SkippedCoordinates::new(&name);
+    let GroupedPlotData {
+        groups,
+        key_bucket_amount,
+        min_key,
+        max_key,
+        min_value,
+        max_value,
+        min_positive_value,
+    } = data;
+    let (
+        key_bucket_amount,
+        mut min_key,
+        mut max_key,
+        mut min_value,
+        mut max_value,
+        mut min_positive_value,
+    ) = (
+        *key_bucket_amount,
+        *min_key,
+        *max_key,
+        *min_value,
+        *max_value,
+        *min_positive_value,
+    );
+    if let Some(baseline) = baseline {
+        min_key = min_key.min(baseline.min_key);
+        max_key = max_key.max(baseline.max_key);
+        min_value = min_value.min(baseline.min_value);
+        max_value = max_value.max(baseline.max_value);
+        min_positive_value = min_positive_value.min(baseline.min_positive_value);
+    }
+    let value_transform = suggest_value_transform(
+        &name,
+        value_transform,
+        min_positive_value,
+        max_value,
+        render_options.auto_transform,
+    );
+    let RenderOptions {
+        theme,
+        plot_style,
+        reference_curve,
+        show_trajectories,
+        width_by_sample_count,
+        ..
+    } = render_options;
+    let (plot_style, show_trajectories) = (*plot_style, *show_trajectories);
+
+    let value_epsilon = min_value
+        .abs()
+        .max(max_value.abs())
+        .max(max_value - min_value)
+        * 1e-12;
+    let (log_zero_policy, log_epsilon) =
+        (render_options.log_zero_policy, render_options.log_epsilon);
+    let to_chart_value = |value: f64| match value_transform {
+        AxisTransform::PolynomialRoot { .. } if value < value_epsilon => 0.0,
+        AxisTransform::Log { .. } if is_log_zero(value, value_epsilon, log_epsilon) => {
+            value_transform.apply(log_zero_policy.replacement(min_positive_value, log_epsilon))
+        }
+        value_transform => value_transform.apply(value),
+    };
+    report_log_zeros(
+        &name.to_string(),
+        value_transform,
+        log_zero_policy,
+        groups
+            .values()
+            .chain(
+                baseline
+                    .iter()
+                    .flat_map(|baseline| baseline.groups.values()),
+            )
+            .flatten()
+            .flat_map(|file| &file.contained_files)
+            .map(&value_fn)
+            .filter(|&value| is_log_zero(value, value_epsilon, log_epsilon))
+            .count(),
+    );
+    // The transform may reverse the order of the values or be undefined for some of them,
+    // so the chart range is computed from all transformed values.
+    // If there are no finite values, then the minimum is larger than the maximum.
+    let (min_chart_value, max_chart_value) = Some([min_value, max_value])
+        .filter(|_| min_value <= max_value)
+        .into_iter()
+        .flatten()
+        .chain(
+            groups
+                .values()
+                .chain(
+                    baseline
+                        .iter()
+                        .flat_map(|baseline| baseline.groups.values()),
+                )
+                .flatten()
+                .flat_map(|file| &file.contained_files)
+                .map(&value_fn),
+        )
+        .map(to_chart_value)
+        .filter(|value| value.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+    if min_chart_value > max_chart_value {
+        bail!(
+            "No value of plot {} can be placed on a {value_transform} axis.",
+            name.to_string()
+        );
+    }
 
     info!("Creating chart context with key range {min_key}..{max_key} and value range {min_chart_value}..{max_chart_value}");
 
-    let key_range_len = max_key - min_key;
+    let (chart_min_key, chart_max_key) = widen_degenerate_range(min_key, max_key);
+    let (min_chart_value, max_chart_value) =
+        widen_degenerate_range(min_chart_value, max_chart_value);
+    let key_range_len = chart_max_key - chart_min_key;
     let key_margin = key_range_len / 20.0;
     let chart_value_range_len = max_chart_value - min_chart_value;
     let chart_value_margin = chart_value_range_len / 20.0;
+    // Significance brackets are stacked above the boxes, one level per pair of groups.
+    let chart_value_top_margin = if render_options.significance {
+        chart_value_margin * (1 + groups.len() * groups.len().saturating_sub(1) / 2) as f64
+    } else {
+        chart_value_margin
+    };
 
-    let mut chart = ChartBuilder::on(&root)
-        .caption(name.to_string(), ("sans-serif", 24).into_font())
-        .margin(5)
-        .x_label_area_size(30)
-        .y_label_area_size(50)
+    let caption = caption(
+        &name,
+        &value_name,
+        groups.len(),
+        render_options,
+        groups
+            .values()
+            .flatten()
+            .flat_map(|file| &file.contained_files),
+    );
+    let layout = ChartLayout::new(size, render_options.compact);
+    let mut chart = ChartBuilder::on(root)
+        .caption(
+            caption,
+            (theme.font_family.as_str(), theme.caption_font_size).into_font(),
+        )
+        .margin(layout.scale(5))
+        .x_label_area_size(layout.scale(30))
+        .y_label_area_size(layout.scale(50))
         .build_cartesian_2d(
-            min_key - key_margin..max_key + key_margin,
+            chart_min_key - key_margin..chart_max_key + key_margin,
             (min_chart_value - chart_value_margin) as f32
-                ..(max_chart_value + chart_value_margin) as f32,
+                ..(max_chart_value + chart_value_top_margin) as f32,
+        )?;
+
+    let value_ticks = value_ticks(
+        value_transform,
+        min_chart_value - chart_value_margin,
+        max_chart_value + chart_value_top_margin,
+    );
+    draw_mesh(
+        &mut chart,
+        theme,
+        groups.len(),
+        render_options.key_ticks.as_ref().map(KeyTicks::ticks),
+        value_ticks.as_deref(),
+        &format_value,
+        &|value| format_transformed_value(value_transform, *value as f64),
+        key_name.to_string(),
+        value_axis_label(value_name, value_transform, render_options),
+    )?;
+
+    let key_range = key_bucket_amount
+        .map(|key_bucket_amount| key_range_len / key_bucket_amount as f64)
+        .unwrap_or(1.0);
+    let box_half_width = {
+        let x_range = chart.x_range();
+        let (plotting_width, _) = chart.plotting_area().dim_in_pixel();
+        (x_range.end - x_range.start) * 0.5 * BOX_WIDTH_PIXELS as f64 / plotting_width as f64
+    };
+    let all_keys: Vec<_> = groups
+        .values()
+        .flat_map(|group| group.iter().map(|file| file.key))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|key| key.raw())
+        .collect();
+    let box_width_factor = theme.box_width_factor;
+    // With a baseline, the boxes of the baseline are drawn left of the boxes of the current run.
+    let source_shift = if baseline.is_some() {
+        key_range * box_width_factor / groups.len() as f64 * 0.25
+    } else {
+        0.0
+    };
+    // The amount of files of each group at each key, if the boxes are sized by it.
+    let sample_counts = width_by_sample_count.then(|| {
+        let mut sample_counts: BTreeMap<R64, Vec<usize>> = Default::default();
+        for (group_index, group) in groups.values().enumerate() {
+            for file in group {
+                sample_counts
+                    .entry(file.key)
+                    .or_insert_with(|| vec![0; groups.len()])[group_index] =
+                    file.contained_files.len();
+            }
+        }
+        sample_counts
+    });
+    // The shift of the box of a group from its key, and the width of the box relative to the default width.
+    // By default, the groups divide the space around a key evenly.
+    let box_layout = |group_index: usize, key: f64| {
+        let slot_width = key_range * box_width_factor;
+        let (offset, width, total) = match sample_counts
+            .as_ref()
+            .and_then(|sample_counts| sample_counts.get(&R64::new(key)))
+        {
+            Some(counts) => (
+                counts[..group_index].iter().sum::<usize>(),
+                counts[group_index],
+                counts.iter().sum::<usize>(),
+            ),
+            None => (group_index, 1, groups.len()),
+        };
+        let (offset, width, total) = (offset as f64, width as f64, total as f64);
+        (
+            (offset + width * 0.5) / total * slot_width - slot_width * 0.5 + source_shift,
+            width * groups.len() as f64 / total,
         )
-        .unwrap();
+    };
+    if let (Some(min_box_pixels), PlotStyle::Boxplot) = (render_options.min_box_pixels, plot_style)
+    {
+        let x_range = chart.x_range();
+        let (plotting_width, _) = chart.plotting_area().dim_in_pixel();
+        let pixels_per_key = plotting_width as f64 / (x_range.end - x_range.start);
+        let min_key_gap = all_keys
+            .windows(2)
+            .map(|window| window[1] - window[0])
+            .fold(f64::INFINITY, f64::min);
+        let box_distance = (key_range * box_width_factor).min(min_key_gap) / groups.len() as f64;
+        let min_width_factor = groups
+            .values()
+            .enumerate()
+            .flat_map(|(group_index, group)| {
+                group
+                    .iter()
+                    .map(move |file| box_layout(group_index, file.key.raw()).1)
+            })
+            .fold(f64::INFINITY, f64::min);
+        let box_pixels = estimate_box_pixels(box_distance, pixels_per_key, min_width_factor);
+        if box_pixels < min_box_pixels {
+            diagnostics::report(
+                Level::Warn,
+                DiagnosticCategory::NarrowBoxes,
+                "Boxes are too narrow to be read, consider key buckets or a line plot",
+                [
+                    ("plot", name.to_string()),
+                    ("box_pixels", format!("{box_pixels:.1}")),
+                    ("min_box_pixels", min_box_pixels.to_string()),
+                ],
+            );
+        }
+    }
+    let mut draw_order: Vec<_> = groups
+        .iter()
+        .zip(theme.group_colors(groups.len()))
+        .enumerate()
+        .collect();
+    // Empty groups keep their color, such that the colors of groups stay the same across charts.
+    let has_data = |group: &[MergedStatisticsFile]| {
+        group
+            .iter()
+            .flat_map(|file| &file.contained_files)
+            .any(|file| value_fn(file).is_finite())
+    };
+    // The legend entries are added in the order of the groups, independently of the order in which they are drawn.
+    for (_, ((group_name, group), style)) in &draw_order {
+        let mut label = group_label(&group_name.to_string(), render_options.max_label_length);
+        let style = if has_data(group) {
+            *style
+        } else {
+            match render_options.empty_group_policy {
+                EmptyGroupPolicy::Omit => continue,
+                EmptyGroupPolicy::Grey => {
+                    label.push_str(" (no data)");
+                    EMPTY_GROUP_COLOR
+                }
+            }
+        };
+        let series = chart
+            .draw_series(std::iter::empty::<Circle<(f64, f32), u32>>())?
+            .label(label);
+        match plot_style {
+            PlotStyle::Boxplot => {
+                series.legend(move |(x, y)| Rectangle::new([(x - 5, y - 5), (x + 5, y + 5)], style))
+            }
+            PlotStyle::Line => {
+                series.legend(move |(x, y)| PathElement::new([(x - 5, y), (x + 5, y)], style))
+            }
+            PlotStyle::Fan | PlotStyle::Violin => series.legend(move |(x, y)| {
+                Rectangle::new([(x - 5, y - 5), (x + 5, y + 5)], style.mix(0.4).filled())
+            }),
+            PlotStyle::Scatter => {
+                series.legend(move |(x, y)| Circle::new((x, y), 3, style.filled()))
+            }
+            PlotStyle::ErrorBar => series.legend(move |(x, y)| {
+                ErrorBar::new_vertical(x, y - 5, y, y + 5, style.filled(), 6)
+            }),
+            PlotStyle::Ridgeline => unreachable!(),
+        };
+    }
+    // Groups that are drawn on top come last, and otherwise the order of the groups is kept.
+    draw_order.sort_by_key(|(_, ((group_name, _), _))| {
+        render_options.on_top.contains(&group_name.to_string())
+    });
+    let context = BarChartContext {
+        groups,
+        render_options,
+        skipped,
+        value_fn: &value_fn,
+        to_chart_value: &to_chart_value,
+        box_layout: &box_layout,
+        box_half_width,
+        value_transform,
+        chart_min_key,
+        key_range_len,
+        min_chart_value,
+        max_chart_value,
+        chart_value_margin,
+    };
+    let mut rows = ChartRows::default();
+    for (group_index, ((group_name, group), style)) in draw_order {
+        if !has_data(group) {
+            info!(
+                "Skipping group {} because it has no data",
+                group_name.to_string()
+            );
+            continue;
+        }
+        info!("Drawing group {}", group_name.to_string());
+        let group = BarChartGroup {
+            context: &context,
+            name: group_name,
+            index: group_index,
+            style,
+            coordinates: group
+                .iter()
+                .map(|file| {
+                    let key = file.key.raw();
+                    let values = finite_values(file, &value_fn, group_name);
+                    (key, file, values)
+                })
+                .collect(),
+        };
+
+        if show_trajectories {
+            draw_trajectories(&mut chart, &group)?;
+        }
+        match plot_style {
+            PlotStyle::Boxplot => {
+                draw_boxes(&mut chart, &group, baseline, source_shift, &mut rows)?
+            }
+            PlotStyle::Line => draw_median_line(&mut chart, &group, &all_keys, &mut rows)?,
+            PlotStyle::Fan => draw_fan(&mut chart, &group, &mut rows)?,
+            PlotStyle::Scatter => draw_scatter(&mut chart, &group, &mut rows)?,
+            PlotStyle::ErrorBar => draw_error_bars(&mut chart, &group, &mut rows)?,
+            PlotStyle::Violin => draw_violins(&mut chart, &group, &mut rows)?,
+            PlotStyle::Ridgeline => unreachable!(),
+        }
+        draw_group_markers(&mut chart, &group)?;
+    }
+
+    if render_options.emit_data {
+        let value_header = match plot_style {
+            PlotStyle::Line | PlotStyle::Fan => "median",
+            PlotStyle::ErrorBar => "mean",
+            PlotStyle::Boxplot | PlotStyle::Ridgeline | PlotStyle::Scatter | PlotStyle::Violin => {
+                "value"
+            }
+        };
+        data_export::write_data_csv(
+            output_file,
+            &["group", "key", &with_unit(value_header, render_options)],
+            rows.data,
+            render_options.drop_constant_columns,
+        );
+    }
+    if render_options.debug_layout {
+        data_export::write_layout_csv(
+            output_file,
+            &[
+                "primitive",
+                "group",
+                "key",
+                "key_shift",
+                "x",
+                "lower_fence",
+                "lower_quartile",
+                "median",
+                "upper_quartile",
+                "upper_fence",
+                "width",
+            ],
+            rows.layout,
+        );
+    }
+
+    if baseline.is_some() {
+        chart
+            .draw_series(std::iter::empty::<Rectangle<(f64, f32)>>())?
+            .label("baseline")
+            .legend(|(x, y)| {
+                Rectangle::new([(x - 5, y - 5), (x + 5, y + 5)], BLACK.mix(0.3).filled())
+            });
+        chart
+            .draw_series(std::iter::empty::<Rectangle<(f64, f32)>>())?
+            .label("current")
+            .legend(|(x, y)| {
+                EmptyElement::at((x, y))
+                    + Rectangle::new([(-5, -5), (5, 5)], BLACK)
+                    + PathElement::new([(-5, 5), (5, -5)], BLACK)
+            });
+    }
+
+    if render_options.annotate_total_runtime {
+        draw_total_runtime(
+            &mut chart,
+            theme,
+            groups
+                .values()
+                .flatten()
+                .flat_map(|file| &file.contained_files),
+        )?;
+    }
+    if render_options.annotate_facet_n {
+        draw_file_count(
+            &mut chart,
+            theme,
+            groups
+                .values()
+                .flatten()
+                .map(|file| file.contained_files.len())
+                .sum(),
+        )?;
+    }
+
+    if let Some(reference_curve) = reference_curve {
+        draw_reference_curve(&mut chart, &context, reference_curve)?;
+    }
+
+    let overall_median = render_options
+        .overall_median_line
+        .then(|| {
+            let mut values: Vec<_> = groups
+                .values()
+                .flatten()
+                .flat_map(|file| &file.contained_files)
+                .map(&value_fn)
+                .filter(|value| value.is_finite())
+                .collect();
+            values.sort_by(f64::total_cmp);
+            let overall_median = stats::percentile(&values, 50.0);
+            let chart_overall_median = to_chart_value(overall_median) as f32;
+            if !chart_overall_median.is_finite() {
+                diagnostics::report(
+                    Level::Warn,
+                    DiagnosticCategory::NonFiniteValue,
+                    "Skipping overall median line, since there is no finite median to draw",
+                    [
+                        ("plot", name.to_string()),
+                        ("value_amount", values.len().to_string()),
+                    ],
+                );
+            }
+            (overall_median, chart_overall_median)
+        })
+        .filter(|(_, chart_overall_median)| chart_overall_median.is_finite());
+    if let Some((overall_median, chart_overall_median)) = overall_median {
+        info!("Drawing overall median line at {overall_median}");
+
+        let style = BLACK.mix(0.6);
+        chart
+            .draw_series(DashedLineSeries::new(
+                [
+                    (chart_min_key, chart_overall_median),
+                    (chart_min_key + key_range_len, chart_overall_median),
+                ],
+                6,
+                4,
+                style.into(),
+            ))?
+            .label("overall median")
+            .legend(move |(x, y)| PathElement::new([(x - 5, y), (x + 5, y)], style));
+    }
+
+    if let Some(baseline_group) = &render_options.baseline_group {
+        let chart_one = to_chart_value(1.0) as f32;
+        let style = BLACK.mix(0.6);
+        chart
+            .draw_series(DashedLineSeries::new(
+                [
+                    (chart_min_key, chart_one),
+                    (chart_min_key + key_range_len, chart_one),
+                ],
+                6,
+                4,
+                style.into(),
+            ))?
+            .label(format!("= {baseline_group}"))
+            .legend(move |(x, y)| PathElement::new([(x - 5, y), (x + 5, y)], style));
+    }
+
+    if render_options.trendline {
+        draw_trendlines(&mut chart, &context)?;
+    }
+    if render_options.significance {
+        draw_significance(&mut chart, &context)?;
+    }
+
+    draw_legend(&mut chart, theme)?;
+    Ok(())
+}
+
+/// The state of a chart of [`draw_grouped_linear_bar_chart`] that is shared by the functions drawing its parts.
+struct BarChartContext<'a, GroupName> {
+    groups: &'a BTreeMap<GroupName, Vec<MergedStatisticsFile>>,
+    render_options: &'a RenderOptions,
+    skipped: &'a SkippedCoordinates,
+    value_fn: &'a dyn Fn(&StatisticsFile) -> f64,
+    /// Convert a value into a value coordinate of the chart.
+    to_chart_value: &'a dyn Fn(f64) -> f64,
+    /// The shift of the box of a group from its key, and the width of the box relative to the default width.
+    box_layout: &'a dyn Fn(usize, f64) -> (f64, f64),
+    /// Half the default width of a box in key coordinates.
+    box_half_width: f64,
+    value_transform: AxisTransform,
+    chart_min_key: f64,
+    key_range_len: f64,
+    min_chart_value: f64,
+    max_chart_value: f64,
+    chart_value_margin: f64,
+}
+
+/// A group of a chart of [`draw_grouped_linear_bar_chart`] together with its finite values at each key.
+struct BarChartGroup<'a, GroupName> {
+    context: &'a BarChartContext<'a, GroupName>,
+    name: &'a GroupName,
+    index: usize,
+    style: RGBColor,
+    coordinates: Vec<(f64, &'a MergedStatisticsFile, Vec<f64>)>,
+}
+
+impl<GroupName: ToString> BarChartGroup<'_, GroupName> {
+    /// The shift of the elements of this group from their key.
+    fn key_shift(&self, key: f64) -> f64 {
+        match self.context.render_options.plot_style {
+            PlotStyle::Boxplot | PlotStyle::Scatter | PlotStyle::ErrorBar | PlotStyle::Violin => {
+                (self.context.box_layout)(self.index, key).0
+            }
+            PlotStyle::Line | PlotStyle::Fan => 0.0,
+            PlotStyle::Ridgeline => unreachable!(),
+        }
+    }
+
+    /// The width of the box of this group at the given key relative to the default width.
+    fn box_width(&self, key: f64) -> f64 {
+        (self.context.box_layout)(self.index, key).1
+    }
+
+    fn data_row(&self, key: f64, value: f64) -> [String; 3] {
+        [self.name.to_string(), key.to_string(), value.to_string()]
+    }
+
+    /// A row of the csv written with `debug_layout` for a box or point of this group in chart coordinates.
+    fn layout_row(
+        &self,
+        primitive: &str,
+        key: f64,
+        shifted_key: f64,
+        chart_values: [Option<f32>; 5],
+        width: Option<f64>,
+    ) -> Vec<String> {
+        [
+            primitive.to_string(),
+            self.name.to_string(),
+            key.to_string(),
+            (shifted_key - key).to_string(),
+            shifted_key.to_string(),
+        ]
+        .into_iter()
+        .chain(
+            chart_values
+                .into_iter()
+                .chain([width.map(|width| width as f32)])
+                .map(|value| value.map(|value| value.to_string()).unwrap_or_default()),
+        )
+        .collect()
+    }
+}
+
+/// The rows of the csv files written with `emit_data` and `debug_layout`.
+#[derive(Default)]
+struct ChartRows {
+    data: Vec<[String; 3]>,
+    layout: Vec<Vec<String>>,
+}
+
+/// Connect the values of each seed of the group across the keys.
+fn draw_trajectories<GroupName: ToString, DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    group: &BarChartGroup<GroupName>,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let &BarChartContext {
+        render_options,
+        skipped,
+        value_fn,
+        to_chart_value,
+        ..
+    } = group.context;
+    // The files of each seed, connected across the keys. Merged files are sorted by key.
+    let mut trajectories: BTreeMap<_, Vec<_>> = Default::default();
+    for &(key, file, _) in &group.coordinates {
+        for contained_file in &file.contained_files {
+            if let Some(coordinate) = skipped.finite_or_skip(
+                "trajectory point",
+                key,
+                to_chart_value(value_fn(contained_file)),
+            ) {
+                trajectories
+                    .entry(contained_file.parameters.seed)
+                    .or_default()
+                    .push(coordinate);
+            }
+        }
+    }
+
+    let trajectories: Vec<_> = trajectories.into_values().collect();
+    // Each trajectory passes through each box at most once.
+    let trajectories = match render_options.max_points_per_box {
+        Some(max_points_per_box) => {
+            sampling::subsample(&trajectories, max_points_per_box, group.index as u64)
+                .into_iter()
+                .cloned()
+                .collect()
+        }
+        None => trajectories,
+    };
+    for trajectory in trajectories {
+        chart.draw_series(LineSeries::new(trajectory, group.style.mix(0.3)))?;
+    }
+    Ok(())
+}
+
+/// Draw a box for each key of the group.
+/// With a baseline, the boxes of the group are hatched, and the boxes of the same group of the baseline
+/// are drawn solid and shifted left by twice the `source_shift`.
+fn draw_boxes<GroupName: Ord + ToString, DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    group: &BarChartGroup<GroupName>,
+    baseline: Option<&GroupedPlotData<GroupName>>,
+    source_shift: f64,
+    rows: &mut ChartRows,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let &BarChartContext {
+        render_options,
+        skipped,
+        value_fn,
+        to_chart_value,
+        box_half_width,
+        ..
+    } = group.context;
+    let theme = &render_options.theme;
+    let style = group.style;
+    let whisker_style = theme.whisker_style(group.index);
+    let chart_quartiles = |key: f64, values: &[f64]| {
+        let values: Vec<_> = values
+            .iter()
+            .copied()
+            .filter(|&value| {
+                skipped
+                    .finite_or_skip("box value", key, to_chart_value(value))
+                    .is_some()
+            })
+            .collect();
+        (!values.is_empty()).then(|| (transformed_quartiles(&values, to_chart_value), values.len()))
+    };
+    let boxplot = |key: f64, quartiles: &Quartiles, width: f64| {
+        Boxplot::new_vertical(key, quartiles)
+            .width((BOX_WIDTH_PIXELS as f64 * width).round() as u32)
+            .whisker_width(whisker_style.whisker_width())
+            .style(style.stroke_width(whisker_style.stroke_width()))
+    };
+
+    if let Some(baseline_group) = baseline.and_then(|baseline| baseline.groups.get(group.name)) {
+        for file in baseline_group {
+            let width = group.box_width(file.key.raw());
+            let half_width = box_half_width * width;
+            let key = file.key.raw() + group.key_shift(file.key.raw()) - 2.0 * source_shift;
+            let values = finite_values(file, value_fn, group.name);
+            let Some((quartiles, sample_size)) = chart_quartiles(key, &values) else {
+                continue;
+            };
+            rows.layout.push(group.layout_row(
+                "baseline_box",
+                file.key.raw(),
+                key,
+                quartiles.values().map(Some),
+                Some(2.0 * half_width),
+            ));
+            let [_, lower_quartile, _, upper_quartile, _] = quartiles.values();
+            chart.draw_series([Rectangle::new(
+                [
+                    (key - half_width, lower_quartile),
+                    (key + half_width, upper_quartile),
+                ],
+                style.mix(0.3).filled(),
+            )])?;
+            if render_options.notched {
+                chart.draw_series(notched_boxplot(
+                    key,
+                    &quartiles,
+                    sample_size,
+                    half_width,
+                    whisker_style.whisker_width(),
+                    style.stroke_width(whisker_style.stroke_width()),
+                ))?;
+            } else {
+                chart.draw_series([boxplot(key, &quartiles, width)])?;
+            }
+        }
+    }
+
+    for (key, _, values) in &group.coordinates {
+        rows.data
+            .extend(values.iter().map(|&value| group.data_row(*key, value)));
+    }
+
+    let boxes: Vec<_> = group
+        .coordinates
+        .iter()
+        .filter_map(|&(key, _, ref values)| {
+            let shifted_key = key + group.key_shift(key);
+            let (quartiles, sample_size) = chart_quartiles(shifted_key, values)?;
+            Some((
+                key,
+                shifted_key,
+                quartiles,
+                sample_size,
+                group.box_width(key),
+            ))
+        })
+        .collect();
+    for (key, shifted_key, quartiles, _, width) in &boxes {
+        rows.layout.push(group.layout_row(
+            "box",
+            *key,
+            *shifted_key,
+            quartiles.values().map(Some),
+            Some(2.0 * box_half_width * width),
+        ));
+    }
+    if render_options.notched {
+        chart.draw_series(
+            boxes
+                .iter()
+                .flat_map(|(_, key, quartiles, sample_size, width)| {
+                    notched_boxplot(
+                        *key,
+                        quartiles,
+                        *sample_size,
+                        box_half_width * width,
+                        whisker_style.whisker_width(),
+                        style.stroke_width(whisker_style.stroke_width()),
+                    )
+                }),
+        )?;
+    } else {
+        chart.draw_series(
+            boxes
+                .iter()
+                .map(|(_, key, quartiles, _, width)| boxplot(*key, quartiles, *width)),
+        )?;
+    }
+    if render_options.label_outliers {
+        for &(key, file, _) in &group.coordinates {
+            let shifted_key = key + group.key_shift(key);
+            let values = finite_values(file, value_fn, group.name);
+            let Some((quartiles, _)) = chart_quartiles(shifted_key, &values) else {
+                continue;
+            };
+            let [lower_fence, _, _, _, upper_fence] = quartiles.values();
+            chart.draw_series(
+                file.contained_files
+                    .iter()
+                    .map(|file| (file.parameters.seed, to_chart_value(value_fn(file))))
+                    .filter(|&(_, value)| {
+                        value.is_finite()
+                            && !(lower_fence as f64..=upper_fence as f64).contains(&value)
+                    })
+                    .map(|(seed, value)| {
+                        EmptyElement::at((shifted_key, value as f32))
+                            + Cross::new((0, 0), 3, style.stroke_width(1))
+                            + Text::new(
+                                format!("seed {seed}"),
+                                (5, 0),
+                                (theme.font_family.as_str(), theme.annotation_font_size)
+                                    .into_font()
+                                    .color(&style)
+                                    .pos(Pos::new(HPos::Left, VPos::Center)),
+                            )
+                    }),
+            )?;
+        }
+    }
+    if !render_options.percentiles.is_empty() {
+        chart.draw_series(
+            group
+                .coordinates
+                .iter()
+                .filter(|(_, _, values)| !values.is_empty())
+                .flat_map(|&(key, _, ref values)| {
+                    let shifted_key = key + group.key_shift(key);
+                    let half_width = box_half_width * group.box_width(key);
+                    let mut values = values.clone();
+                    values.sort_by(f64::total_cmp);
+                    render_options
+                        .percentiles
+                        .iter()
+                        .filter_map(move |&percentile| {
+                            skipped.finite_or_skip(
+                                "percentile marker",
+                                shifted_key,
+                                to_chart_value(stats::percentile(&values, percentile)),
+                            )
+                        })
+                        .map(move |(key, value)| {
+                            PathElement::new(
+                                [(key - half_width, value), (key + half_width, value)],
+                                style.stroke_width(3),
+                            )
+                        })
+                }),
+        )?;
+    }
+    if baseline.is_some() {
+        for (_, key, quartiles, _, width) in &boxes {
+            let [_, lower_quartile, _, upper_quartile, _] = quartiles.values();
+            chart.draw_series(hatch_lines(
+                (key - box_half_width * width, key + box_half_width * width),
+                (lower_quartile, upper_quartile),
+                style,
+            ))?;
+        }
+    }
+    Ok(())
+}
+
+/// Draw a line through the medians of the group, and if `interpolate_keys` is set,
+/// through its interpolated medians at the keys of all groups.
+fn draw_median_line<GroupName: ToString, DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    group: &BarChartGroup<GroupName>,
+    all_keys: &[f64],
+    rows: &mut ChartRows,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let &BarChartContext {
+        render_options,
+        skipped,
+        to_chart_value,
+        ..
+    } = group.context;
+    let points: Vec<_> = group
+        .coordinates
+        .iter()
+        .filter(|(_, _, values)| !values.is_empty())
+        .map(|(key, _, values)| (*key, Quartiles::new(values).median()))
+        .collect();
+    let line_points = if render_options.interpolate_keys {
+        interpolation::interpolate_onto_keys(&points, all_keys.iter().copied())
+    } else {
+        points.clone()
+    };
+    rows.data.extend(
+        line_points
+            .iter()
+            .map(|&(key, value)| group.data_row(key, value)),
+    );
+    rows.layout.extend(line_points.iter().map(|&(key, value)| {
+        group.layout_row(
+            "line_point",
+            key,
+            key,
+            [None, None, Some(to_chart_value(value) as f32), None, None],
+            None,
+        )
+    }));
+
+    chart.draw_series(LineSeries::new(
+        line_points.iter().filter_map(|&(key, value)| {
+            skipped.finite_or_skip("line point", key, to_chart_value(value))
+        }),
+        group.style,
+    ))?;
+    chart.draw_series(
+        points
+            .iter()
+            .filter_map(|&(key, value)| {
+                skipped.finite_or_skip("line point", key, to_chart_value(value))
+            })
+            .map(|coordinate| Circle::new(coordinate, 3, group.style.filled())),
+    )?;
+    Ok(())
+}
+
+/// Draw the median line of the group inside ribbons between the pairs of `fan_percentiles`.
+fn draw_fan<GroupName: ToString, DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    group: &BarChartGroup<GroupName>,
+    rows: &mut ChartRows,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let &BarChartContext {
+        render_options,
+        skipped,
+        to_chart_value,
+        ..
+    } = group.context;
+    let fan_percentiles = &render_options.fan_percentiles;
+    let sorted_values: Vec<_> = group
+        .coordinates
+        .iter()
+        .filter(|(_, _, values)| !values.is_empty())
+        .map(|(key, _, values)| {
+            let mut values = values.clone();
+            values.sort_by(f64::total_cmp);
+            (*key, values)
+        })
+        .collect();
+    let percentile_points = |percentile: f64| {
+        sorted_values
+            .iter()
+            .filter_map(move |(key, values)| {
+                skipped.finite_or_skip(
+                    "fan point",
+                    *key,
+                    to_chart_value(stats::percentile(values, percentile)),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let ribbon_amount = fan_percentiles.len() / 2;
+    for ribbon_index in 0..ribbon_amount {
+        let lower = percentile_points(fan_percentiles[ribbon_index]);
+        let upper = percentile_points(fan_percentiles[fan_percentiles.len() - 1 - ribbon_index]);
+        // The innermost ribbon is the most opaque.
+        let opacity = 0.4 * (ribbon_index + 1) as f64 / ribbon_amount as f64;
+        chart.draw_series(std::iter::once(Polygon::new(
+            lower
+                .into_iter()
+                .chain(upper.into_iter().rev())
+                .collect::<Vec<_>>(),
+            group.style.mix(opacity).filled(),
+        )))?;
+    }
+
+    rows.layout
+        .extend(sorted_values.iter().map(|(key, values)| {
+            let chart_percentile =
+                |percentile| Some(to_chart_value(stats::percentile(values, percentile)) as f32);
+            group.layout_row(
+                "fan_point",
+                *key,
+                *key,
+                [
+                    chart_percentile(fan_percentiles[0]),
+                    None,
+                    chart_percentile(50.0),
+                    None,
+                    chart_percentile(fan_percentiles[fan_percentiles.len() - 1]),
+                ],
+                None,
+            )
+        }));
+    rows.data.extend(
+        sorted_values
+            .iter()
+            .map(|(key, values)| group.data_row(*key, stats::percentile(values, 50.0))),
+    );
+    chart.draw_series(LineSeries::new(percentile_points(50.0), group.style))?;
+    Ok(())
+}
+
+/// Draw the values of the group as points, jittered within the width of its boxes.
+fn draw_scatter<GroupName: ToString, DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    group: &BarChartGroup<GroupName>,
+    rows: &mut ChartRows,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let &BarChartContext {
+        render_options,
+        skipped,
+        to_chart_value,
+        box_half_width,
+        ..
+    } = group.context;
+    let group_index = group.index;
+    let points: Vec<_> = group
+        .coordinates
+        .iter()
+        .flat_map(|(key, _, values)| {
+            let values = match render_options.max_points_per_box {
+                Some(max_points_per_box) => sampling::subsample(
+                    values,
+                    max_points_per_box,
+                    key.to_bits() ^ group_index as u64,
+                ),
+                None => values.iter().collect(),
+            };
+            values.into_iter().enumerate().map(move |(index, &value)| {
+                let jitter =
+                    sampling::jitter(key.to_bits() ^ ((group_index as u64) << 32) ^ index as u64);
+                (*key, jitter * box_half_width * group.box_width(*key), value)
+            })
+        })
+        .collect();
+    rows.data.extend(
+        points
+            .iter()
+            .map(|&(key, _, value)| group.data_row(key, value)),
+    );
+    rows.layout
+        .extend(points.iter().map(|&(key, jitter, value)| {
+            group.layout_row(
+                "scatter_point",
+                key,
+                key + group.key_shift(key) + jitter,
+                [None, None, Some(to_chart_value(value) as f32), None, None],
+                None,
+            )
+        }));
+
+    chart.draw_series(
+        points
+            .iter()
+            .filter_map(|&(key, jitter, value)| {
+                skipped.finite_or_skip(
+                    "scatter point",
+                    key + group.key_shift(key) + jitter,
+                    to_chart_value(value),
+                )
+            })
+            .map(|coordinate| Circle::new(coordinate, 2, group.style.mix(0.7).filled())),
+    )?;
+    Ok(())
+}
+
+/// Draw the mean of the group with an error bar of one standard deviation at each key.
+fn draw_error_bars<GroupName: ToString, DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    group: &BarChartGroup<GroupName>,
+    rows: &mut ChartRows,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let &BarChartContext {
+        skipped,
+        to_chart_value,
+        box_half_width,
+        min_chart_value,
+        ..
+    } = group.context;
+    let error_bars: Vec<_> = group
+        .coordinates
+        .iter()
+        .filter(|(_, _, values)| !values.is_empty())
+        .map(|(key, _, values)| {
+            let (mean, standard_deviation) = stats::mean_and_standard_deviation(values);
+            (*key, mean, standard_deviation)
+        })
+        .collect();
+    rows.data.extend(
+        error_bars
+            .iter()
+            .map(|&(key, mean, _)| group.data_row(key, mean)),
+    );
+    rows.layout
+        .extend(error_bars.iter().map(|&(key, mean, standard_deviation)| {
+            group.layout_row(
+                "error_bar",
+                key,
+                key + group.key_shift(key),
+                [
+                    Some(to_chart_value(mean - standard_deviation) as f32),
+                    None,
+                    Some(to_chart_value(mean) as f32),
+                    None,
+                    Some(to_chart_value(mean + standard_deviation) as f32),
+                ],
+                Some(2.0 * box_half_width * group.box_width(key)),
+            )
+        }));
+
+    chart.draw_series(
+        error_bars
+            .iter()
+            .filter_map(|&(key, mean, standard_deviation)| {
+                let key = key + group.key_shift(key);
+                // The lower end may be undefined, e.g. if it is negative on a log axis,
+                // and then the error bar extends to the bottom of the chart.
+                let lower = Some(to_chart_value(mean - standard_deviation))
+                    .filter(|lower| lower.is_finite())
+                    .unwrap_or(min_chart_value);
+                let [lower, mean, upper] = [
+                    lower,
+                    to_chart_value(mean),
+                    to_chart_value(mean + standard_deviation),
+                ]
+                .map(|value| skipped.finite_or_skip("error bar", key, value));
+                Some(ErrorBar::new_vertical(
+                    key,
+                    lower?.1,
+                    mean?.1,
+                    upper?.1,
+                    group.style.filled(),
+                    BOX_WIDTH_PIXELS,
+                ))
+            }),
+    )?;
+    Ok(())
+}
+
+/// Draw the kernel density estimate of the values of the group at each key as a violin.
+fn draw_violins<GroupName: ToString, DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    group: &BarChartGroup<GroupName>,
+    rows: &mut ChartRows,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    const VIOLIN_POINTS: usize = 50;
+    let &BarChartContext {
+        render_options,
+        skipped,
+        to_chart_value,
+        box_half_width,
+        ..
+    } = group.context;
+    let style = group.style;
+    for (key, _, values) in &group.coordinates {
+        rows.data
+            .extend(values.iter().map(|&value| group.data_row(*key, value)));
+        let chart_values: Vec<_> = values
+            .iter()
+            .filter_map(|&value| {
+                skipped.finite_or_skip("violin value", *key, to_chart_value(value))
+            })
+            .map(|(_, value)| value as f64)
+            .collect();
+        if chart_values.is_empty() {
+            continue;
+        }
+        let (min, max) = chart_values
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+                (min.min(value), max.max(value))
+            });
+        let center = key + group.key_shift(*key);
+        let half_width = box_half_width * group.box_width(*key);
+        rows.layout.push(group.layout_row(
+            "violin",
+            *key,
+            center,
+            [Some(min as f32), None, None, None, Some(max as f32)],
+            Some(2.0 * half_width),
+        ));
+
+        // Without spread, e.g. for a single sample, there is no density to draw.
+        if min == max {
+            chart.draw_series([PathElement::new(
+                [
+                    (center - half_width, min as f32),
+                    (center + half_width, min as f32),
+                ],
+                style.stroke_width(2),
+            )])?;
+            continue;
+        }
+
+        let bandwidth = render_options
+            .violin_bandwidth
+            .unwrap_or_else(|| density::silverman_bandwidth(&chart_values));
+        let density = density::gaussian_kernel_density(
+            &chart_values,
+            (0..VIOLIN_POINTS)
+                .map(|index| min + (max - min) * index as f64 / (VIOLIN_POINTS - 1) as f64),
+            bandwidth,
+        );
+        let max_density = density
+            .iter()
+            .map(|&(_, density)| density)
+            .fold(0.0, f64::max);
+        let outline: Vec<_> = density
+            .iter()
+            .map(|&(value, density)| (center + density / max_density * half_width, value))
+            .chain(
+                density
+                    .iter()
+                    .rev()
+                    .map(|&(value, density)| (center - density / max_density * half_width, value)),
+            )
+            .map(|(key, value)| (key, value as f32))
+            .collect();
+        chart.draw_series([Polygon::new(outline.clone(), style.mix(0.4).filled())])?;
+        chart.draw_series([PathElement::new(
+            outline
+                .iter()
+                .copied()
+                .chain(outline.first().copied())
+                .collect::<Vec<_>>(),
+            style,
+        )])?;
+    }
+    Ok(())
+}
+
+/// Draw the markers of the group that are independent of the plot style:
+/// unreliable points, censored files of boxes, the amount of files of each bucket and suboptimal files.
+fn draw_group_markers<GroupName: ToString, DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    group: &BarChartGroup<GroupName>,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let &BarChartContext {
+        render_options,
+        skipped,
+        value_fn,
+        to_chart_value,
+        min_chart_value,
+        ..
+    } = group.context;
+    let RenderOptions {
+        theme,
+        min_samples_for_reliable,
+        max_relative_standard_error,
+        max_points_per_box,
+        ..
+    } = render_options;
+    let style = group.style;
+
+    if min_samples_for_reliable.is_some() || max_relative_standard_error.is_some() {
+        let unreliable_points: Vec<_> = group
+            .coordinates
+            .iter()
+            .filter(|(_, _, values)| !values.is_empty())
+            .filter_map(|(key, _, values)| {
+                let relative_standard_error = stats::relative_standard_error(values);
+                let unreliable = min_samples_for_reliable
+                    .is_some_and(|min_samples| values.len() < min_samples)
+                    || max_relative_standard_error.is_some_and(|max_relative_standard_error| {
+                        // An undefined error, e.g. from a single sample, is unreliable as well.
+                        relative_standard_error.is_nan()
+                            || relative_standard_error > max_relative_standard_error
+                    });
+                unreliable.then(|| {
+                    diagnostics::report(
+                        Level::Warn,
+                        DiagnosticCategory::UnreliablePoint,
+                        "Point is unreliable, consider adding more seeds",
+                        [
+                            ("group", group.name.to_string()),
+                            ("key", key.to_string()),
+                            ("n", values.len().to_string()),
+                            (
+                                "relative_standard_error",
+                                relative_standard_error.to_string(),
+                            ),
+                        ],
+                    );
+                    let mut values = values.clone();
+                    values.sort_by(f64::total_cmp);
+                    skipped.finite_or_skip(
+                        "unreliability marker",
+                        key + group.key_shift(*key),
+                        to_chart_value(stats::percentile(&values, 50.0)),
+                    )
+                })?
+            })
+            .collect();
+        chart.draw_series(
+            unreliable_points
+                .into_iter()
+                .map(|coordinate| Circle::new(coordinate, 7, style.stroke_width(1))),
+        )?;
+    }
+
+    if render_options.plot_style == PlotStyle::Boxplot {
+        chart.draw_series(group.coordinates.iter().filter_map(|&(key, file, _)| {
+            file.contained_files
+                .iter()
+                .filter(|file| file.censored)
+                .filter_map(|file| {
+                    skipped.finite_or_skip(
+                        "censoring marker",
+                        key + group.key_shift(key),
+                        to_chart_value(value_fn(file)),
+                    )
+                })
+                .reduce(|a, b| if a.1 >= b.1 { a } else { b })
+                .map(|coordinate| TriangleMarker::new(coordinate, 5, style.stroke_width(1)))
+        }))?;
+    }
+
+    if render_options.annotate_bucket_n {
+        chart.draw_series(group.coordinates.iter().map(|(key, file, values)| {
+            let coordinate = values
+                .iter()
+                .filter_map(|&value| {
+                    skipped.finite_or_skip(
+                        "annotation",
+                        key + group.key_shift(*key),
+                        to_chart_value(value),
+                    )
+                })
+                .fold(
+                    (key + group.key_shift(*key), min_chart_value as f32),
+                    |a, b| if a.1 >= b.1 { a } else { b },
+                );
+            Text::new(
+                format!("n={}", file.bucket_file_count),
+                coordinate,
+                (theme.font_family.as_str(), theme.annotation_font_size)
+                    .into_font()
+                    .color(&style)
+                    .pos(Pos::new(HPos::Center, VPos::Bottom)),
+            )
+        }))?;
+    }
+
+    if render_options.mark_suboptimal {
+        chart.draw_series(group.coordinates.iter().flat_map(|&(key, file, _)| {
+            let suboptimal_files: Vec<_> = file
+                .contained_files
+                .iter()
+                .filter(|file| !file.optimal)
+                .collect();
+            let suboptimal_files = match max_points_per_box {
+                Some(max_points_per_box) => sampling::subsample(
+                    &suboptimal_files,
+                    *max_points_per_box,
+                    key.to_bits() ^ group.index as u64,
+                )
+                .into_iter()
+                .copied()
+                .collect(),
+                None => suboptimal_files,
+            };
+            suboptimal_files
+                .into_iter()
+                .filter_map(move |file| {
+                    skipped.finite_or_skip(
+                        "suboptimal marker",
+                        key + group.key_shift(key),
+                        to_chart_value(value_fn(file)),
+                    )
+                })
+                .map(move |coordinate| Circle::new(coordinate, 3, style.stroke_width(1)))
+        }))?;
+    }
+    Ok(())
+}
+
+/// Draw the reference curve, scaled to fit the values of all groups, as a dashed line across the chart.
+fn draw_reference_curve<GroupName, DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    context: &BarChartContext<GroupName>,
+    reference_curve: &ReferenceCurve,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let &BarChartContext {
+        groups,
+        value_fn,
+        to_chart_value,
+        chart_min_key,
+        key_range_len,
+        min_chart_value,
+        max_chart_value,
+        chart_value_margin,
+        ..
+    } = context;
+    let scale = reference_curve.fit_scale(groups.values().flatten().flat_map(|file| {
+        file.contained_files
+            .iter()
+            .map(|contained_file| (file.key.raw(), value_fn(contained_file)))
+    }));
+    info!("Drawing reference curve {scale}*{reference_curve}");
+
+    const REFERENCE_CURVE_POINTS: usize = 100;
+    let chart_value_range =
+        min_chart_value - chart_value_margin..max_chart_value + chart_value_margin;
+    chart
+        .draw_series(DashedLineSeries::new(
+            (0..REFERENCE_CURVE_POINTS)
+                .map(|index| {
+                    let key = chart_min_key
+                        + key_range_len * index as f64 / (REFERENCE_CURVE_POINTS - 1) as f64;
+                    (key, to_chart_value(scale * reference_curve.evaluate(key)))
+                })
+                .filter(|(_, value)| chart_value_range.contains(value))
+                .map(|(key, value)| (key, value as f32)),
+            4,
+            4,
+            BLACK.into(),
+        ))?
+        .label(format!("{scale:.3e}*{reference_curve}"))
+        .legend(|(x, y)| PathElement::new([(x - 5, y), (x + 5, y)], BLACK));
+    Ok(())
+}
+
+/// Draw a dashed line for each group, fitted to its medians in chart coordinates.
+fn draw_trendlines<GroupName: ToString, DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    context: &BarChartContext<GroupName>,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let &BarChartContext {
+        groups,
+        render_options,
+        value_fn,
+        to_chart_value,
+        value_transform,
+        ..
+    } = context;
+    for ((group_name, group), style) in groups
+        .iter()
+        .zip(render_options.theme.group_colors(groups.len()))
+    {
+        let medians: Vec<_> = group
+            .iter()
+            .filter_map(|file| {
+                let mut values: Vec<_> = file
+                    .contained_files
+                    .iter()
+                    .map(value_fn)
+                    .filter(|value| value.is_finite())
+                    .collect();
+                values.sort_by(f64::total_cmp);
+                let median = to_chart_value(stats::percentile(&values, 50.0));
+                median.is_finite().then_some((file.key.raw(), median))
+            })
+            .collect();
+        let Some((slope, intercept)) = stats::linear_regression(&medians) else {
+            debug!(
+                "Skipping trendline of group {} because it has fewer than two distinct keys",
+                group_name.to_string()
+            );
+            continue;
+        };
+        let unit = render_options
+            .value_unit
+            .as_ref()
+            .map(|unit| format!(" of values in {unit}"))
+            .unwrap_or_default();
+        info!(
+            "Trendline of group {} on the {value_transform} axis{unit}: slope {slope:.4e}, intercept {intercept:.4e}",
+            group_name.to_string()
+        );
+
+        let (first_key, last_key) = (medians[0].0, medians[medians.len() - 1].0);
+        chart.draw_series(DashedLineSeries::new(
+            [first_key, last_key].map(|key| (key, (slope * key + intercept) as f32)),
+            6,
+            3,
+            style.into(),
+        ))?;
+    }
+    Ok(())
+}
+
+/// Annotate each pair of groups at each key with the significance of the difference of their values,
+/// using brackets that are stacked above the values.
+fn draw_significance<GroupName: ToString, DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    context: &BarChartContext<GroupName>,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let &BarChartContext {
+        groups,
+        render_options,
+        value_fn,
+        to_chart_value,
+        box_layout,
+        min_chart_value,
+        chart_value_margin,
+        ..
+    } = context;
+    let theme = &render_options.theme;
+    // The finite values of each group at each key.
+    let mut key_values: BTreeMap<R64, Vec<Vec<f64>>> = Default::default();
+    for (group_index, group) in groups.values().enumerate() {
+        for file in group {
+            key_values
+                .entry(file.key)
+                .or_insert_with(|| vec![Vec::new(); groups.len()])[group_index] = file
+                .contained_files
+                .iter()
+                .map(value_fn)
+                .filter(|value| value.is_finite())
+                .collect();
+        }
+    }
+    let group_names: Vec<_> = groups.keys().map(ToString::to_string).collect();
+
+    for (key, values) in key_values {
+        let key = key.raw();
+        let present_groups: Vec<_> = (0..groups.len())
+            .filter(|&group_index| !values[group_index].is_empty())
+            .collect();
+        let top = values
+            .iter()
+            .flatten()
+            .map(|&value| to_chart_value(value))
+            .filter(|value| value.is_finite())
+            .fold(min_chart_value, f64::max);
+
+        let mut level = 0;
+        for (position, &a) in present_groups.iter().enumerate() {
+            for &b in &present_groups[position + 1..] {
+                let significance = match significance::mann_whitney_u_test(&values[a], &values[b]) {
+                    Some(p_value) => {
+                        info!(
+                            "Mann-Whitney U test of {} and {} at key {key}: p = {p_value:.4}",
+                            group_names[a], group_names[b]
+                        );
+                        Significance::from_p_value(p_value)
+                    }
+                    None => {
+                        debug!(
+                            "Annotating {} and {} at key {key} as not significant, since one has fewer than {} values",
+                            group_names[a],
+                            group_names[b],
+                            significance::MIN_SAMPLE_SIZE
+                        );
+                        Significance::NotSignificant
+                    }
+                };
+
+                level += 1;
+                let y = (top + chart_value_margin * level as f64) as f32;
+                let tick_y = y - (chart_value_margin * 0.3) as f32;
+                let (x_a, x_b) = (key + box_layout(a, key).0, key + box_layout(b, key).0);
+                chart.draw_series([PathElement::new(
+                    [(x_a, tick_y), (x_a, y), (x_b, y), (x_b, tick_y)],
+                    BLACK,
+                )])?;
+                chart.draw_series([Text::new(
+                    significance.symbol(),
+                    ((x_a + x_b) * 0.5, y),
+                    (theme.font_family.as_str(), theme.annotation_font_size)
+                        .into_font()
+                        .color(&BLACK)
+                        .pos(Pos::new(HPos::Center, VPos::Bottom)),
+                )])?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Draw the chart of [`grouped_linear_bar_plot`] once for each facet, in a grid of charts of the given size.
+/// All charts share the same key and value ranges, such that they can be compared with each other.
+#[allow(clippy::too_many_arguments)]
+fn grouped_faceted_plot<GroupName: Ord + Clone + ToString>(
+    output_directory: impl AsRef<Path>,
+    name: impl ToString,
+    key_name: impl ToString,
+    value_name: impl ToString,
+    size: (u32, u32),
+    value_transform: AxisTransform,
+    render_options: &RenderOptions,
+    mut facets: BTreeMap<String, GroupedPlotData<GroupName>>,
+    value_fn: impl Fn(&StatisticsFile) -> f64,
+) -> anyhow::Result<()> {
+    let (min_key, max_key, min_value, max_value, min_positive_value) = facets.values().fold(
+        (
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+        ),
+        |(min_key, max_key, min_value, max_value, min_positive_value), data| {
+            (
+                min_key.min(data.min_key),
+                max_key.max(data.max_key),
+                min_value.min(data.min_value),
+                max_value.max(data.max_value),
+                min_positive_value.min(data.min_positive_value),
+            )
+        },
+    );
+    // Every facet contains every group, such that the groups have the same colors in all facets.
+    let group_names: BTreeSet<_> = facets
+        .values()
+        .flat_map(|data| data.groups.keys().cloned())
+        .collect();
+    for data in facets.values_mut() {
+        for group_name in &group_names {
+            data.groups.entry(group_name.clone()).or_default();
+        }
+        data.min_key = min_key;
+        data.max_key = max_key;
+        data.min_value = min_value;
+        data.max_value = max_value;
+        data.min_positive_value = min_positive_value;
+    }
+
+    let columns = (facets.len() as f64).sqrt().ceil() as usize;
+    let rows = facets.len().div_ceil(columns);
+    let name = name.to_string();
+    let output_file = output_file(output_directory, &name)
+        .with_extension(render_options.output_format.extension());
+    // A fixed caption is the caption of the whole figure, while each facet is captioned with its name.
+    let facet_render_options = RenderOptions {
+        caption: None,
+        ..render_options.clone()
+    };
+    let theme = &render_options.theme;
+    with_drawing_area!(
+        &output_file,
+        (size.0 * columns as u32, size.1 * rows as u32),
+        render_options.output_format,
+        theme,
+        |root| {
+            let area = match &render_options.caption {
+                Some(caption) => root.titled(
+                    caption,
+                    (theme.font_family.as_str(), theme.caption_font_size).into_font(),
+                )?,
+                None => root.clone(),
+            };
+            for ((facet_name, data), area) in facets.iter().zip(area.split_evenly((rows, columns)))
+            {
+                info!("Drawing facet {facet_name}");
+                let facet_file_name = format!(
+                    "{name}_{}.{}",
+                    file_name_component(facet_name),
+                    render_options.output_format.extension()
+                );
+                draw_grouped_linear_bar_chart(
+                    &area,
+                    &output_file.with_file_name(facet_file_name),
+                    facet_name,
+                    key_name.to_string(),
+                    value_name.to_string(),
+                    size,
+                    value_transform,
+                    &facet_render_options,
+                    data,
+                    None,
+                    &value_fn,
+                )?;
+            }
+        }
+    )
+    .with_context(|| format!("plot {name} to {output_file:?}"))
+}
+
+/// Plot the density of all values of each group as curves stacked on top of each other.
+#[allow(clippy::too_many_arguments)]
+fn grouped_ridgeline_plot<GroupName: Ord + ToString>(
+    output_directory: impl AsRef<Path>,
+    name: impl ToString,
+    value_name: impl ToString,
+    size: (u32, u32),
+    value_transform: AxisTransform,
+    render_options: &RenderOptions,
+    data: &GroupedPlotData<GroupName>,
+    value_fn: impl Fn(&StatisticsFile) -> f64,
+) -> anyhow::Result<()> {
+    let skipped = &SkippedCoordinates::new(&name);
+    let theme = &render_options.theme;
+    let groups = &data.groups;
+    let value_epsilon = data
+        .min_value
+        .abs()
+        .max(data.max_value.abs())
+        .max(data.max_value - data.min_value)
+        * 1e-12;
+    let (log_zero_policy, log_epsilon, min_positive_value) = (
+        render_options.log_zero_policy,
+        render_options.log_epsilon,
+        data.min_positive_value,
+    );
+    let to_chart_value = |value: f64| match value_transform {
+        AxisTransform::PolynomialRoot { .. } if value < value_epsilon => 0.0,
+        AxisTransform::Log { .. } if is_log_zero(value, value_epsilon, log_epsilon) => {
+            value_transform.apply(log_zero_policy.replacement(min_positive_value, log_epsilon))
+        }
+        value_transform => value_transform.apply(value),
+    };
+    report_log_zeros(
+        &name.to_string(),
+        value_transform,
+        log_zero_policy,
+        groups
+            .values()
+            .flatten()
+            .flat_map(|file| &file.contained_files)
+            .map(&value_fn)
+            .filter(|&value| is_log_zero(value, value_epsilon, log_epsilon))
+            .count(),
+    );
+
+    let group_values: Vec<(&GroupName, Vec<f64>)> = groups
+        .iter()
+        .map(|(group_name, group)| {
+            let values = group
+                .iter()
+                .flat_map(|file| finite_values(file, &value_fn, group_name))
+                .map(to_chart_value)
+                .filter(|value| value.is_finite())
+                .collect();
+            (group_name, values)
+        })
+        .collect();
+    let (min_chart_value, max_chart_value) = group_values
+        .iter()
+        .flat_map(|(_, values)| values)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+            (min.min(value), max.max(value))
+        });
+    if min_chart_value > max_chart_value {
+        bail!(
+            "No value of plot {} can be placed on a {value_transform} axis.",
+            name.to_string()
+        );
+    }
+    let (min_chart_value, max_chart_value) =
+        widen_degenerate_range(min_chart_value, max_chart_value);
+    let chart_value_margin = (max_chart_value - min_chart_value) / 20.0;
+    let (min_chart_value, max_chart_value) = (
+        min_chart_value - chart_value_margin,
+        max_chart_value + chart_value_margin,
+    );
+
+    // The first group is drawn at the top.
+    let ridgeline_offset = render_options.ridgeline_offset;
+    let baseline =
+        |group_index: usize| ((groups.len() - group_index - 1) as f64 * ridgeline_offset) as f32;
+    let max_height = baseline(0) + 1.0;
+
+    let name = name.to_string();
+    let output_file = output_file(output_directory, &name)
+        .with_extension(render_options.output_format.extension());
+    with_drawing_area!(
+        &output_file,
+        size,
+        render_options.output_format,
+        theme,
+        |root| {
+            info!("Creating chart context with value range {min_chart_value}..{max_chart_value}");
+            let caption = caption(
+                &name,
+                &value_name,
+                groups.len(),
+                render_options,
+                groups
+                    .values()
+                    .flatten()
+                    .flat_map(|file| &file.contained_files),
+            );
+            let layout = ChartLayout::new(size, render_options.compact);
+            let mut chart = ChartBuilder::on(&root)
+                .caption(
+                    caption,
+                    (theme.font_family.as_str(), theme.caption_font_size).into_font(),
+                )
+                .margin(layout.scale(5))
+                .x_label_area_size(layout.scale(30))
+                .y_label_area_size(layout.scale(10))
+                .build_cartesian_2d(min_chart_value..max_chart_value, 0f32..max_height * 1.05)?;
+
+            let value_ticks = value_ticks(value_transform, min_chart_value, max_chart_value);
+            draw_mesh(
+                &mut chart,
+                theme,
+                10,
+                value_ticks.as_deref(),
+                None,
+                &|value| format_transformed_value(value_transform, *value),
+                &|_| String::new(),
+                value_axis_label(value_name, value_transform, render_options),
+                String::new(),
+            )?;
+
+            // The rows of the csv written with `emit_data`.
+            let mut data_rows = Vec::new();
+            const DENSITY_POINTS: usize = 200;
+            let points: Vec<_> = (0..DENSITY_POINTS)
+                .map(|index| {
+                    min_chart_value
+                        + (max_chart_value - min_chart_value) * index as f64
+                            / (DENSITY_POINTS - 1) as f64
+                })
+                .collect();
+            for (group_index, ((group_name, values), style)) in group_values
+                .iter()
+                .zip(theme.group_colors(group_values.len()))
+                .enumerate()
+            {
+                info!("Drawing group {}", group_name.to_string());
+                let density = density::kernel_density_estimate(
+                    values,
+                    points.iter().copied(),
+                    (max_chart_value - min_chart_value) / 100.0,
+                );
+                let max_density = density
+                    .iter()
+                    .map(|&(_, density)| density)
+                    .fold(0.0, f64::max);
+                let baseline = baseline(group_index);
+                data_rows.extend(density.iter().map(|(x, density)| {
+                    [group_name.to_string(), x.to_string(), density.to_string()]
+                }));
+
+                if max_density > 0.0 {
+                    chart.draw_series(
+                        AreaSeries::new(
+                            density.iter().filter_map(|&(x, density)| {
+                                skipped.finite_or_skip(
+                                    "density point",
+                                    x,
+                                    baseline as f64 + density / max_density,
+                                )
+                            }),
+                            baseline,
+                            style.mix(0.5),
+                        )
+                        .border_style(style),
+                    )?;
+                }
+                chart.draw_series([Text::new(
+                    group_label(&group_name.to_string(), render_options.max_label_length),
+                    (min_chart_value, baseline),
+                    (theme.font_family.as_str(), theme.annotation_font_size)
+                        .into_font()
+                        .color(&style)
+                        .pos(Pos::new(HPos::Left, VPos::Bottom)),
+                )])?;
+            }
+
+            if render_options.emit_data {
+                data_export::write_data_csv(
+                    &output_file,
+                    &[
+                        "group",
+                        &with_unit("transformed_value", render_options),
+                        "density",
+                    ],
+                    data_rows,
+                    render_options.drop_constant_columns,
+                );
+            }
+
+            if render_options.annotate_total_runtime {
+                draw_total_runtime(
+                    &mut chart,
+                    theme,
+                    groups
+                        .values()
+                        .flatten()
+                        .flat_map(|file| &file.contained_files),
+                )?;
+            }
+            if render_options.annotate_facet_n {
+                draw_file_count(
+                    &mut chart,
+                    theme,
+                    groups
+                        .values()
+                        .flatten()
+                        .map(|file| file.contained_files.len())
+                        .sum(),
+                )?;
+            }
+        }
+    )
+    .with_context(|| format!("plot {name} to {output_file:?}"))
+}
+
+/// Plot how often each key occurs in each group, as bars next to each other.
+/// Each file is counted with the weight given by `weight_fn`.
+///
+/// With `bucket_intervals`, the keys are bucketed into the given intervals, and keys outside of all intervals are dropped.
+/// Otherwise, without `bin_width`, the keys are rounded to the nearest multiple of `round_to`.
+/// Otherwise, the keys are bucketed into bins of width `bin_width`, starting at the minimum key.
+#[allow(clippy::too_many_arguments)]
+fn grouped_histogram<GroupName: Ord + ToString>(
+    output_directory: impl AsRef<Path>,
+    name: impl ToString,
+    key_name: impl ToString,
+    value_name: impl ToString,
+    size: (u32, u32),
+    render_options: &RenderOptions,
+    statistics_files: &[StatisticsFile],
+    group_name_fn: impl Fn(&StatisticsFile) -> GroupName,
+    key_fn: impl Fn(&StatisticsFile) -> f64,
+    weight_fn: impl Fn(&StatisticsFile) -> f64,
+    bin_width: Option<f64>,
+    round_to: u64,
+    bucket_intervals: Option<&BucketIntervals>,
+) -> anyhow::Result<()> {
+    let skipped = &SkippedCoordinates::new(&name);
+    let theme = &render_options.theme;
+    let bins = HistogramBins::new(
+        bucket_intervals,
+        bin_width,
+        round_to,
+        statistics_files.iter().map(&key_fn),
+    );
+
+    let mut unbinned_amount = 0;
+    let groups: BTreeMap<_, BTreeMap<_, f64>> = group_files(statistics_files, group_name_fn)
+        .into_iter()
+        .map(|(group_name, group)| {
+            let mut aggregated: BTreeMap<_, f64> = Default::default();
+            for (key, weight) in group
+                .iter()
+                .map(|file| (key_fn(file), weight_fn(file)))
+                .filter(|(key, weight)| key.is_finite() && weight.is_finite())
+            {
+                if let Some(bin) = bins.bin(key) {
+                    *aggregated.entry(bin).or_default() += weight;
+                } else {
+                    unbinned_amount += 1;
+                }
+            }
+            (group_name, aggregated)
+        })
+        .collect();
+    if unbinned_amount > 0 {
+        diagnostics::report(
+            Level::Warn,
+            DiagnosticCategory::UnbinnedValue,
+            "Dropping values that lie outside of all bucket intervals",
+            [
+                ("plot", name.to_string()),
+                ("amount", unbinned_amount.to_string()),
+            ],
+        );
+    }
+
+    let (min_bin, max_bin) = groups
+        .values()
+        .flat_map(|group| group.keys())
+        .fold((i64::MAX, i64::MIN), |(min, max), &bin| {
+            (min.min(bin), max.max(bin))
+        });
+    if min_bin > max_bin {
+        bail!("No finite keys to plot in histogram {}.", name.to_string());
+    }
+    let (min_key, max_key) = (bins.bounds(min_bin).0, bins.bounds(max_bin).1);
+    // The bars start at zero, so the value range always contains zero.
+    let (min_value, max_value) = groups
+        .values()
+        .flat_map(|group| group.values())
+        .fold((0.0, 0.0), |(min, max): (f64, f64), &value| {
+            (min.min(value), max.max(value))
+        });
+    let (min_value, max_value) = widen_degenerate_range(min_value, max_value);
+    let value_margin = (max_value - min_value) * 0.05;
+
+    let name = name.to_string();
+    let output_file = output_file(output_directory, &name)
+        .with_extension(render_options.output_format.extension());
+    with_drawing_area!(
+        &output_file,
+        size,
+        render_options.output_format,
+        theme,
+        |root| {
+            info!("Creating chart context with key range {min_key}..{max_key} and value range {min_value}..{max_value}");
+            let caption = caption(
+                &name,
+                &value_name,
+                groups.len(),
+                render_options,
+                statistics_files,
+            );
+            let layout = ChartLayout::new(size, render_options.compact);
+            let mut chart = ChartBuilder::on(&root)
+                .caption(
+                    caption,
+                    (theme.font_family.as_str(), theme.caption_font_size).into_font(),
+                )
+                .margin(layout.scale(5))
+                .x_label_area_size(layout.scale(30))
+                .y_label_area_size(layout.scale(50))
+                .build_cartesian_2d(
+                    min_key..max_key,
+                    min_value as f32..(max_value + value_margin) as f32,
+                )?;
+
+            draw_mesh(
+                &mut chart,
+                theme,
+                ((max_bin - min_bin) as usize + 2).min(20),
+                None,
+                None,
+                &|key| bins.label(*key),
+                &|value| format_value(&(*value as f64)),
+                key_name.to_string(),
+                value_name.to_string(),
+            )?;
+
+            for (group_index, ((group_name, group), style)) in groups
+                .iter()
+                .zip(theme.group_colors(groups.len()))
+                .enumerate()
+            {
+                info!("Drawing group {}", group_name.to_string());
+
+                chart
+                    .draw_series(group.iter().filter_map(|(&bin, &frequency)| {
+                        let (lower, upper) = bins.bounds(bin);
+                        let bin_width = upper - lower;
+                        let bar_width = theme.box_width_factor * bin_width / groups.len() as f64;
+                        let key_shift = (group_index as f64 + 0.5) * bar_width
+                            - theme.box_width_factor * bin_width * 0.5;
+                        let (x, frequency) = skipped.finite_or_skip(
+                            "histogram bar",
+                            bins.center(bin) + key_shift,
+                            frequency,
+                        )?;
+                        Some(Rectangle::new(
+                            [(x - bar_width * 0.5, 0.0), (x + bar_width * 0.5, frequency)],
+                            style.filled(),
+                        ))
+                    }))?
+                    .label(group_label(
+                        &group_name.to_string(),
+                        render_options.max_label_length,
+                    ))
+                    .legend(move |(x, y)| {
+                        Rectangle::new([(x - 5, y - 5), (x + 5, y + 5)], style.filled())
+                    });
+            }
+
+            if render_options.emit_data {
+                data_export::write_data_csv(
+                    &output_file,
+                    &["group", &with_unit("bin_center", render_options), "weight"],
+                    groups.iter().flat_map(|(group_name, group)| {
+                        group.iter().map(move |(&bin, weight)| {
+                            [
+                                group_name.to_string(),
+                                bins.center(bin).to_string(),
+                                weight.to_string(),
+                            ]
+                        })
+                    }),
+                    render_options.drop_constant_columns,
+                );
+            }
+
+            if render_options.annotate_total_runtime {
+                draw_total_runtime(&mut chart, theme, statistics_files)?;
+            }
+            if render_options.annotate_facet_n {
+                draw_file_count(&mut chart, theme, statistics_files.len())?;
+            }
+
+            draw_legend(&mut chart, theme)?;
+        }
+    )
+    .with_context(|| format!("plot {name} to {output_file:?}"))
+}
+
+/// How the keys of a histogram are assigned to bins, which are numbered by integers.
+#[derive(Debug, Clone, Copy)]
+enum HistogramBins<'a> {
+    /// The intervals, numbered in order.
+    Intervals(&'a BucketIntervals),
+    /// The multiples of `round_to`, each centered on its multiple.
+    Rounded { round_to: f64 },
+    /// Bins of the given width, starting at the minimum key.
+    Uniform {
+        min_key: f64,
+        bin_width: f64,
+        bin_amount: i64,
+    },
+}
+
+impl<'a> HistogramBins<'a> {
+    /// The bins as documented at [`grouped_histogram`], spanning the finite ones of the given keys.
+    fn new(
+        bucket_intervals: Option<&'a BucketIntervals>,
+        bin_width: Option<f64>,
+        round_to: u64,
+        keys: impl IntoIterator<Item = f64>,
+    ) -> Self {
+        match (bucket_intervals, bin_width) {
+            (Some(bucket_intervals), _) => Self::Intervals(bucket_intervals),
+            (None, None) => Self::Rounded {
+                round_to: round_to as f64,
+            },
+            (None, Some(bin_width)) => {
+                let (min_key, max_key) = keys
+                    .into_iter()
+                    .filter(|key| key.is_finite())
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), key| {
+                        (min.min(key), max.max(key))
+                    });
+                Self::Uniform {
+                    min_key,
+                    bin_width,
+                    // The maximum key belongs to the last bin, even if it lies on its upper edge.
+                    bin_amount: (((max_key - min_key) / bin_width).ceil() as i64).max(1),
+                }
+            }
+        }
+    }
+
+    /// The bin of a key, or `None` if the key lies in no bin.
+    fn bin(&self, key: f64) -> Option<i64> {
+        match *self {
+            Self::Intervals(bucket_intervals) => {
+                bucket_intervals.index_of(key).map(|index| index as i64)
+            }
+            Self::Rounded { round_to } => Some((key / round_to).round() as i64),
+            Self::Uniform {
+                min_key,
+                bin_width,
+                bin_amount,
+            } => Some((((key - min_key) / bin_width).floor() as i64).min(bin_amount - 1)),
+        }
+    }
+
+    /// The lower and upper bound of a bin.
+    fn bounds(&self, bin: i64) -> (f64, f64) {
+        match *self {
+            Self::Intervals(bucket_intervals) => bucket_intervals.intervals()[bin as usize],
+            Self::Rounded { round_to } => {
+                let center = bin as f64 * round_to;
+                (center - 0.5 * round_to, center + 0.5 * round_to)
+            }
+            Self::Uniform {
+                min_key, bin_width, ..
+            } => {
+                let lower = min_key + bin as f64 * bin_width;
+                (lower, lower + bin_width)
+            }
+        }
+    }
+
+    fn center(&self, bin: i64) -> f64 {
+        let (lower, upper) = self.bounds(bin);
+        (lower + upper) * 0.5
+    }
+
+    /// The label of the key axis at the given key.
+    /// Rounded bins are only labelled at their multiples.
+    fn label(&self, key: f64) -> String {
+        // Negative zero should not be labelled with a sign.
+        let key = if key == 0.0 { 0.0 } else { key };
+        match *self {
+            Self::Rounded { round_to } => {
+                if (key / round_to).fract() == 0.0 {
+                    format!("{key:.0}")
+                } else {
+                    String::new()
+                }
+            }
+            _ => {
+                let label = format!("{key:.3}");
+                label
+                    .trim_end_matches('0')
+                    .trim_end_matches('.')
+                    .to_string()
+            }
+        }
+    }
+}
+
+/// Plot a single bar per group, stacked from the proportions of the files of the group in each category.
+#[allow(clippy::too_many_arguments)]
+fn grouped_proportion_plot<GroupName: Ord + ToString>(
+    output_directory: impl AsRef<Path>,
+    name: impl ToString,
+    category_name: impl ToString,
+    size: (u32, u32),
+    render_options: &RenderOptions,
+    statistics_files: &[StatisticsFile],
+    group_name_fn: impl Fn(&StatisticsFile) -> GroupName,
+    category_fn: impl Fn(&StatisticsFile) -> usize,
+) -> anyhow::Result<()> {
+    let theme = &render_options.theme;
+    let groups: Vec<(GroupName, BTreeMap<usize, f64>)> =
+        group_files(statistics_files, group_name_fn)
+            .into_iter()
+            .map(|(group_name, group)| {
+                let mut proportions: BTreeMap<_, f64> = Default::default();
+                for file in &group {
+                    *proportions.entry(category_fn(file)).or_default() += 1.0;
+                }
+                for proportion in proportions.values_mut() {
+                    *proportion /= group.len() as f64;
+                }
+                (group_name, proportions)
+            })
+            .collect();
+    let categories: BTreeSet<_> = groups
+        .iter()
+        .flat_map(|(_, proportions)| proportions.keys().copied())
+        .collect();
+    let category_name = category_name.to_string();
+
+    let name = name.to_string();
+    let output_file = output_file(output_directory, &name)
+        .with_extension(render_options.output_format.extension());
+    with_drawing_area!(
+        &output_file,
+        size,
+        render_options.output_format,
+        theme,
+        |root| {
+            info!(
+                "Creating chart context with {} groups and {} categories",
+                groups.len(),
+                categories.len()
+            );
+            let caption = caption(
+                &name,
+                &category_name,
+                groups.len(),
+                render_options,
+                statistics_files,
+            );
+            let layout = ChartLayout::new(size, render_options.compact);
+            let mut chart = ChartBuilder::on(&root)
+                .caption(
+                    caption,
+                    (theme.font_family.as_str(), theme.caption_font_size).into_font(),
+                )
+                .margin(layout.scale(5))
+                .x_label_area_size(layout.scale(30))
+                .y_label_area_size(layout.scale(50))
+                .build_cartesian_2d(-0.5..groups.len() as f64 - 0.5, 0f32..1.05)?;
+
+            draw_mesh(
+                &mut chart,
+                theme,
+                groups.len(),
+                None,
+                None,
+                &|x| {
+                    // Only the centers of the bars are labelled.
+                    if x.fract() == 0.0 && *x >= 0.0 {
+                        groups
+                            .get(*x as usize)
+                            .map(|(group_name, _)| {
+                                group_label(
+                                    &group_name.to_string(),
+                                    render_options.max_label_length,
+                                )
+                            })
+                            .unwrap_or_default()
+                    } else {
+                        String::new()
+                    }
+                },
+                &|value| format!("{:.0}%", value * 100.0),
+                "Group".to_string(),
+                "Proportion of Runs".to_string(),
+            )?;
+
+            let colors: BTreeMap<usize, RGBColor> = categories
+                .iter()
+                .copied()
+                .zip(theme.group_colors(categories.len()))
+                .collect();
+            let color = |category: usize| colors[&category];
+            for &category in &categories {
+                let style = color(category);
+                chart
+                    .draw_series(std::iter::empty::<Rectangle<(f64, f32)>>())?
+                    .label(format!("{category} {category_name}"))
+                    .legend(move |(x, y)| {
+                        Rectangle::new([(x - 5, y - 5), (x + 5, y + 5)], style.filled())
+                    });
+            }
+
+            let bar_width = theme.box_width_factor;
+            for (group_index, (group_name, proportions)) in groups.iter().enumerate() {
+                info!("Drawing group {}", group_name.to_string());
+                let x = group_index as f64;
+                let mut bottom = 0.0;
+                chart.draw_series(proportions.iter().map(|(&category, &proportion)| {
+                    let top = bottom + proportion;
+                    let bar = Rectangle::new(
+                        [
+                            (x - bar_width * 0.5, bottom as f32),
+                            (x + bar_width * 0.5, top as f32),
+                        ],
+                        color(category).filled(),
+                    );
+                    bottom = top;
+                    bar
+                }))?;
+            }
+
+            if render_options.emit_data {
+                data_export::write_data_csv(
+                    &output_file,
+                    &["group", "category", "proportion"],
+                    groups.iter().flat_map(|(group_name, proportions)| {
+                        proportions.iter().map(move |(category, proportion)| {
+                            [
+                                group_name.to_string(),
+                                category.to_string(),
+                                proportion.to_string(),
+                            ]
+                        })
+                    }),
+                    render_options.drop_constant_columns,
+                );
+            }
+
+            if render_options.annotate_total_runtime {
+                draw_total_runtime(&mut chart, theme, statistics_files)?;
+            }
+            if render_options.annotate_facet_n {
+                draw_file_count(&mut chart, theme, statistics_files.len())?;
+            }
+
+            draw_legend(&mut chart, theme)?;
+        }
+    )
+    .with_context(|| format!("plot {name} to {output_file:?}"))
+}
+
+/// Draw one point per file, connected in the order of their keys within each group, without merging any files.
+#[allow(clippy::too_many_arguments)]
+fn grouped_raw_line_plot<GroupName: Ord + ToString>(
+    output_directory: impl AsRef<Path>,
+    name: impl ToString,
+    key_name: impl ToString,
+    value_name: impl ToString,
+    size: (u32, u32),
+    value_transform: AxisTransform,
+    render_options: &RenderOptions,
+    statistics_files: &[StatisticsFile],
+    group_name_fn: impl Fn(&StatisticsFile) -> GroupName,
+    key_fn: impl Fn(&AlignmentParameters) -> f64,
+    value_fn: impl Fn(&StatisticsFile) -> f64,
+) -> anyhow::Result<()> {
+    let skipped = &SkippedCoordinates::new(&name);
+    let theme = &render_options.theme;
+    let (log_zero_policy, log_epsilon) =
+        (render_options.log_zero_policy, render_options.log_epsilon);
+    let min_positive_value = min_positive_value(statistics_files.iter().map(&value_fn));
+    let (min_value, max_value) = statistics_files
+        .iter()
+        .map(&value_fn)
+        .filter(|value| value.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+    let value_epsilon = min_value
+        .abs()
+        .max(max_value.abs())
+        .max(max_value - min_value)
+        * 1e-12;
+    let to_chart_value = |value: f64| match value_transform {
+        AxisTransform::Log { .. } if is_log_zero(value, value_epsilon, log_epsilon) => {
+            value_transform.apply(log_zero_policy.replacement(min_positive_value, log_epsilon))
+        }
+        value_transform => value_transform.apply(value),
+    };
+    report_log_zeros(
+        &name.to_string(),
+        value_transform,
+        log_zero_policy,
+        statistics_files
+            .iter()
+            .filter(|file| is_log_zero(value_fn(file), value_epsilon, log_epsilon))
+            .count(),
+    );
+
+    let groups: BTreeMap<_, Vec<_>> = group_files(statistics_files, group_name_fn)
+        .into_iter()
+        .map(|(group_name, group)| {
+            // The original value is kept next to the chart coordinate for the emitted data.
+            let mut points: Vec<_> = group
+                .iter()
+                .filter_map(|file| {
+                    let value = value_fn(file);
+                    skipped
+                        .finite_or_skip(
+                            "raw line point",
+                            key_fn(&file.parameters),
+                            to_chart_value(value),
+                        )
+                        .map(|(key, chart_value)| (key, chart_value, value))
+                })
+                .collect();
+            points.sort_by(|a, b| a.0.total_cmp(&b.0));
+            (group_name, points)
+        })
+        .collect();
+
+    let ((min_key, max_key), (min_chart_value, max_chart_value)) = groups.values().flatten().fold(
+        (
+            (f64::INFINITY, f64::NEG_INFINITY),
+            (f64::INFINITY, f64::NEG_INFINITY),
+        ),
+        |((min_key, max_key), (min_value, max_value)), &(key, value, _)| {
+            (
+                (min_key.min(key), max_key.max(key)),
+                (min_value.min(value as f64), max_value.max(value as f64)),
+            )
+        },
+    );
+    if min_key > max_key {
+        bail!("No finite values to plot in {}.", name.to_string());
+    }
+    let (min_key, max_key) = widen_degenerate_range(min_key, max_key);
+    let (min_chart_value, max_chart_value) =
+        widen_degenerate_range(min_chart_value, max_chart_value);
+    let key_margin = (max_key - min_key) / 20.0;
+    let chart_value_margin = (max_chart_value - min_chart_value) / 20.0;
+
+    let name = name.to_string();
+    let output_file = output_file(output_directory, &name)
+        .with_extension(render_options.output_format.extension());
+    with_drawing_area!(
+        &output_file,
+        size,
+        render_options.output_format,
+        theme,
+        |root| {
+            info!("Creating chart context with key range {min_key}..{max_key} and value range {min_chart_value}..{max_chart_value}");
+            let caption = caption(
+                &name,
+                &value_name,
+                groups.len(),
+                render_options,
+                statistics_files,
+            );
+            let layout = ChartLayout::new(size, render_options.compact);
+            let mut chart = ChartBuilder::on(&root)
+                .caption(
+                    caption,
+                    (theme.font_family.as_str(), theme.caption_font_size).into_font(),
+                )
+                .margin(layout.scale(5))
+                .x_label_area_size(layout.scale(30))
+                .y_label_area_size(layout.scale(50))
+                .build_cartesian_2d(
+                    min_key - key_margin..max_key + key_margin,
+                    (min_chart_value - chart_value_margin) as f32
+                        ..(max_chart_value + chart_value_margin) as f32,
+                )?;
+
+            let value_ticks = value_ticks(
+                value_transform,
+                min_chart_value - chart_value_margin,
+                max_chart_value + chart_value_margin,
+            );
+            draw_mesh(
+                &mut chart,
+                theme,
+                10,
+                render_options.key_ticks.as_ref().map(KeyTicks::ticks),
+                value_ticks.as_deref(),
+                &format_value,
+                &|value| format_transformed_value(value_transform, *value as f64),
+                key_name.to_string(),
+                value_axis_label(value_name, value_transform, render_options),
+            )?;
+
+            for ((group_name, points), style) in groups.iter().zip(theme.group_colors(groups.len()))
+            {
+                info!("Drawing group {}", group_name.to_string());
+                let coordinates = points
+                    .iter()
+                    .map(|&(key, chart_value, _)| (key, chart_value));
+                chart
+                    .draw_series(LineSeries::new(coordinates.clone(), style))?
+                    .label(group_label(
+                        &group_name.to_string(),
+                        render_options.max_label_length,
+                    ))
+                    .legend(move |(x, y)| PathElement::new([(x - 5, y), (x + 5, y)], style));
+                chart.draw_series(
+                    coordinates.map(|coordinate| Circle::new(coordinate, 2, style.filled())),
+                )?;
+            }
+
+            if render_options.emit_data {
+                data_export::write_data_csv(
+                    &output_file,
+                    &["group", "key", &with_unit("value", render_options)],
+                    groups.iter().flat_map(|(group_name, points)| {
+                        points.iter().map(move |(key, _, value)| {
+                            [group_name.to_string(), key.to_string(), value.to_string()]
+                        })
+                    }),
+                    render_options.drop_constant_columns,
+                );
+            }
+
+            if render_options.annotate_total_runtime {
+                draw_total_runtime(&mut chart, theme, statistics_files)?;
+            }
+            if render_options.annotate_facet_n {
+                draw_file_count(&mut chart, theme, statistics_files.len())?;
+            }
+
+            draw_legend(&mut chart, theme)?;
+        }
+    )
+    .with_context(|| format!("plot {name} to {output_file:?}"))
+}
+
+/// Plot each file as a point of two metrics, and connect the Pareto front of each group by a stepped line.
+#[allow(clippy::too_many_arguments)]
+fn grouped_pareto_plot<GroupName: Ord + ToString>(
+    output_directory: impl AsRef<Path>,
+    name: impl ToString,
+    metric_pair: MetricPair,
+    size: (u32, u32),
+    render_options: &RenderOptions,
+    statistics_files: &[StatisticsFile],
+    group_name_fn: impl Fn(&StatisticsFile) -> GroupName,
+) -> anyhow::Result<()> {
+    let skipped = &SkippedCoordinates::new(&name);
+    let theme = &render_options.theme;
+    let groups: BTreeMap<_, (Vec<_>, Vec<_>)> = group_files(statistics_files, group_name_fn)
+        .into_iter()
+        .map(|(group_name, group)| {
+            let points: Vec<_> = group
+                .iter()
+                .filter_map(|file| {
+                    let point = (metric_pair.x.value(file), metric_pair.y.value(file));
+                    skipped
+                        .finite_or_skip("pareto point", point.0, point.1)
+                        .map(|(x, y)| (x, y as f64))
+                })
+                .collect();
+            let front = stats::pareto_front(&points);
+            (group_name, (points, front))
+        })
+        .collect();
+
+    let ((min_x, max_x), (min_y, max_y)) = groups.values().flat_map(|(points, _)| points).fold(
+        (
+            (f64::INFINITY, f64::NEG_INFINITY),
+            (f64::INFINITY, f64::NEG_INFINITY),
+        ),
+        |((min_x, max_x), (min_y, max_y)), &(x, y)| {
+            ((min_x.min(x), max_x.max(x)), (min_y.min(y), max_y.max(y)))
+        },
+    );
+    if min_x > max_x {
+        bail!("No finite values to plot in {}.", name.to_string());
+    }
+    let (min_x, max_x) = widen_degenerate_range(min_x, max_x);
+    let (min_y, max_y) = widen_degenerate_range(min_y, max_y);
+    let x_margin = (max_x - min_x) / 20.0;
+    let y_margin = (max_y - min_y) / 20.0;
+
+    let name = name.to_string();
+    let output_file = output_file(output_directory, &name)
+        .with_extension(render_options.output_format.extension());
+    with_drawing_area!(
+        &output_file,
+        size,
+        render_options.output_format,
+        theme,
+        |root| {
+            info!(
+                "Creating chart context with x range {min_x}..{max_x} and y range {min_y}..{max_y}"
+            );
+            let caption = caption(
+                &name,
+                &format!("{} / {}", metric_pair.y, metric_pair.x),
+                groups.len(),
+                render_options,
+                statistics_files,
+            );
+            let layout = ChartLayout::new(size, render_options.compact);
+            let mut chart = ChartBuilder::on(&root)
+                .caption(
+                    caption,
+                    (theme.font_family.as_str(), theme.caption_font_size).into_font(),
+                )
+                .margin(layout.scale(5))
+                .x_label_area_size(layout.scale(30))
+                .y_label_area_size(layout.scale(50))
+                .build_cartesian_2d(
+                    min_x - x_margin..max_x + x_margin,
+                    (min_y - y_margin) as f32..(max_y + y_margin) as f32,
+                )?;
+
+            draw_mesh(
+                &mut chart,
+                theme,
+                10,
+                None,
+                None,
+                &format_value,
+                &|value| format_value(&(*value as f64)),
+                metric_pair.x.to_string(),
+                metric_pair.y.to_string(),
+            )?;
+
+            for ((group_name, (points, front)), style) in
+                groups.iter().zip(theme.group_colors(groups.len()))
+            {
+                info!(
+                    "Drawing group {} with {} of {} points on the Pareto front",
+                    group_name.to_string(),
+                    front.len(),
+                    points.len()
+                );
+                chart.draw_series(points.iter().map(|&(x, y)| {
+                    Circle::new((x, y as f32), 2, style.mix(0.5).stroke_width(1))
+                }))?;
+
+                // Each point of the front holds until the next one improves the y metric.
+                let steps = front
+                    .iter()
+                    .zip(front.iter().skip(1).map(Some).chain([None]))
+                    .flat_map(|(&(x, y), next)| {
+                        [Some((x, y)), next.map(|&(next_x, _)| (next_x, y))]
+                            .into_iter()
+                            .flatten()
+                    })
+                    .map(|(x, y)| (x, y as f32));
+                chart
+                    .draw_series(LineSeries::new(steps, style))?
+                    .label(group_label(
+                        &group_name.to_string(),
+                        render_options.max_label_length,
+                    ))
+                    .legend(move |(x, y)| PathElement::new([(x - 5, y), (x + 5, y)], style));
+                chart.draw_series(
+                    front
+                        .iter()
+                        .map(|&(x, y)| Circle::new((x, y as f32), 4, style.filled())),
+                )?;
+            }
+
+            if render_options.emit_data {
+                data_export::write_data_csv(
+                    &output_file,
+                    &[
+                        "group",
+                        &metric_pair.x.identifier(),
+                        &metric_pair.y.identifier(),
+                        "on_pareto_front",
+                    ],
+                    groups.iter().flat_map(|(group_name, (points, front))| {
+                        points.iter().map(move |point| {
+                            [
+                                group_name.to_string(),
+                                point.0.to_string(),
+                                point.1.to_string(),
+                                front.contains(point).to_string(),
+                            ]
+                        })
+                    }),
+                    render_options.drop_constant_columns,
+                );
+            }
+
+            if render_options.annotate_total_runtime {
+                draw_total_runtime(&mut chart, theme, statistics_files)?;
+            }
+            if render_options.annotate_facet_n {
+                draw_file_count(&mut chart, theme, statistics_files.len())?;
+            }
+
+            draw_legend(&mut chart, theme)?;
+        }
+    )
+    .with_context(|| format!("plot {name} to {output_file:?}"))
+}
+
+/// The name of a group as shown in legends and labels, shortened to `max_label_length` characters if given.
+fn group_label(group_name: &str, max_label_length: Option<usize>) -> String {
+    match max_label_length {
+        Some(max_label_length) if group_name.chars().count() > max_label_length => {
+            let mut label: String = group_name.chars().take(max_label_length - 1).collect();
+            label.push('…');
+            label
+        }
+        _ => group_name.to_string(),
+    }
+}
+
+/// Draw only the legend of the given groups, with the colors assigned to them in the plots.
+/// The extension of `output_file` is replaced by the one of the output format.
+fn legend_image(
+    output_file: &Path,
+    group_names: &[impl ToString],
+    layout: LegendLayout,
+    output_format: OutputFormat,
+    theme: &Theme,
+    max_label_length: Option<usize>,
+) -> anyhow::Result<()> {
+    let output_file = output_file.with_extension(output_format.extension());
+    info!("Writing legend to {output_file:?}");
+    const FONT_SIZE: u32 = 12;
+    const MARGIN: i32 = 5;
+    const SWATCH_SIZE: i32 = 10;
+    const ENTRY_HEIGHT: i32 = 20;
+
+    let labels: Vec<_> = group_names
+        .iter()
+        .map(|group_name| group_label(&group_name.to_string(), max_label_length))
+        .collect();
+    // Without a font backend the text cannot be measured, so its width is estimated.
+    let label_width = |label: &str| label.chars().count() as i32 * FONT_SIZE as i32 * 6 / 10;
+    let entry_width = |label: &str| SWATCH_SIZE + MARGIN + label_width(label) + 2 * MARGIN;
+    let size = match layout {
+        LegendLayout::Horizontal => (
+            labels.iter().map(|label| entry_width(label)).sum::<i32>() + MARGIN,
+            ENTRY_HEIGHT + 2 * MARGIN,
+        ),
+        LegendLayout::Vertical => (
+            labels
+                .iter()
+                .map(|label| entry_width(label))
+                .max()
+                .unwrap_or(0)
+                + MARGIN,
+            labels.len() as i32 * ENTRY_HEIGHT + 2 * MARGIN,
+        ),
+    };
+
+    with_drawing_area!(
+        &output_file,
+        (size.0 as u32, size.1 as u32),
+        output_format,
+        theme,
+        |root| {
+            let mut position = (MARGIN, MARGIN);
+            for (label, style) in labels.iter().zip(theme.group_colors(labels.len())) {
+                let center_y = position.1 + ENTRY_HEIGHT / 2;
+                root.draw(&Rectangle::new(
+                    [
+                        (position.0 + MARGIN, center_y - SWATCH_SIZE / 2),
+                        (
+                            position.0 + MARGIN + SWATCH_SIZE,
+                            center_y + SWATCH_SIZE / 2,
+                        ),
+                    ],
+                    style.filled(),
+                ))?;
+                root.draw(&Text::new(
+                    label.as_str(),
+                    (position.0 + 2 * MARGIN + SWATCH_SIZE, center_y),
+                    (theme.font_family.as_str(), FONT_SIZE)
+                        .into_font()
+                        .color(&BLACK)
+                        .pos(Pos::new(HPos::Left, VPos::Center)),
+                ))?;
+
+                match layout {
+                    LegendLayout::Horizontal => position.0 += entry_width(label),
+                    LegendLayout::Vertical => position.1 += ENTRY_HEIGHT,
+                }
+            }
+        }
+    )
+    .with_context(|| format!("draw legend to {output_file:?}"))
+}
+
+/// Build the caption of a plot, from the fixed caption or the caption template if given, or from its name otherwise.
+/// If `auto_caption` is set, the parameters that are equal in all files are appended as `key=value` pairs.
+fn caption<'a>(
+    name: &impl ToString,
+    metric: &impl ToString,
+    group_count: usize,
+    render_options: &RenderOptions,
+    statistics_files: impl IntoIterator<Item = &'a StatisticsFile>,
+) -> String {
+    let constant_parameters = AlignmentParameterName::constant_parameters(
+        statistics_files.into_iter().map(|file| &file.parameters),
+    );
+    let mut caption = match (&render_options.caption, &render_options.caption_template) {
+        (Some(caption), _) => caption.clone(),
+        (None, Some(caption_template)) => interpolate_caption_template(
+            caption_template,
+            &[
+                ("name", name.to_string()),
+                ("metric", metric.to_string()),
+                ("group_count", group_count.to_string()),
+            ],
+            &constant_parameters,
+        ),
+        (None, None) => name.to_string(),
+    };
+    if render_options.auto_caption && !constant_parameters.is_empty() {
+        caption.push_str(" (");
+        caption.push_str(
+            &constant_parameters
+                .iter()
+                .map(|(name, value)| format!("{}={value}", name.identifier()))
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+        caption.push(')');
+    }
+    caption
+}
+
+/// Replace each `{placeholder}` in `template` with its value, looking it up first in `values`
+/// and then among the identifiers of the constant parameters.
+/// Unknown placeholders are kept as they are.
+fn interpolate_caption_template(
+    template: &str,
+    values: &[(&str, String)],
+    constant_parameters: &[(AlignmentParameterName, String)],
+) -> String {
+    let mut caption = String::new();
+    let mut rest = template;
+    while let Some((before, after)) = rest.split_once('{') {
+        caption.push_str(before);
+        let Some((placeholder, after)) = after.split_once('}') else {
+            // An unterminated placeholder is kept as it is.
+            caption.push('{');
+            caption.push_str(after);
+            rest = "";
+            break;
+        };
+        let value = values
+            .iter()
+            .find(|(name, _)| *name == placeholder)
+            .map(|(_, value)| value.clone())
+            .or_else(|| {
+                constant_parameters
+                    .iter()
+                    .find(|(name, _)| name.identifier() == placeholder)
+                    .map(|(_, value)| value.clone())
+            });
+        match value {
+            Some(value) => caption.push_str(&value),
+            None => {
+                diagnostics::report(
+                    Level::Warn,
+                    DiagnosticCategory::CaptionTemplate,
+                    "Unknown caption placeholder or parameter that is not equal in all plotted files",
+                    [("placeholder", placeholder)],
+                );
+                caption.push('{');
+                caption.push_str(placeholder);
+                caption.push('}');
+            }
+        }
+        rest = after;
+    }
+    caption.push_str(rest);
+    caption
+}
+
+type Chart<'a, DB> = ChartContext<'a, DB, Cartesian2d<RangedCoordf64, RangedCoordf32>>;
+
+fn output_file(output_directory: impl AsRef<Path>, name: &impl ToString) -> PathBuf {
+    let mut output_file_name = name.to_string();
+    output_file_name.push_str(".svg");
+    let mut output_file = output_directory.as_ref().to_owned();
+    output_file.push(output_file_name);
+    output_file
+}
+
+/// Make the given name safe to use within a file name.
+/// Path separators and other unsafe characters are replaced by underscores, as are the dots of `..`.
+fn file_name_component(name: &str) -> String {
+    name.chars()
+        .map(|character| {
+            if character.is_alphanumeric() || "-_.=+,".contains(character) {
+                character
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .replace("..", "__")
+}
+
+fn create_drawing_area<'a>(
+    output_file: &'a Path,
+    size: (u32, u32),
+    theme: &Theme,
+) -> anyhow::Result<DrawingArea<SVGBackend<'a>, Shift>> {
+    info!("Creating drawing area");
+    let root = SVGBackend::new(output_file, size).into_drawing_area();
+    // Without a background, nothing is drawn, such that the svg stays transparent.
+    if let Some(background) = theme.background {
+        root.fill(&background.rgb())?;
+    }
+    Ok(root)
+}
+
+/// Like [`create_drawing_area`], but for a png file.
+/// Bitmaps have no transparency, so without a background in the theme they are filled white.
+#[cfg(feature = "png")]
+fn create_bitmap_drawing_area<'a>(
+    output_file: &'a Path,
+    size: (u32, u32),
+    theme: &Theme,
+) -> anyhow::Result<DrawingArea<BitMapBackend<'a>, Shift>> {
+    info!("Creating drawing area");
+    let root = BitMapBackend::new(output_file, size).into_drawing_area();
+    root.fill(
+        &theme
+            .background
+            .map(|background| background.rgb())
+            .unwrap_or(WHITE),
+    )?;
+    Ok(root)
+}
+
+/// The width in pixels that a box can be drawn with without overlapping its neighbours.
+///
+/// `box_distance` is the distance between the centers of neighbouring boxes in key units,
+/// and `width_factor` is the width of the narrowest box relative to the default width.
+fn estimate_box_pixels(box_distance: f64, pixels_per_key: f64, width_factor: f64) -> f64 {
+    (BOX_WIDTH_PIXELS as f64 * width_factor).min(box_distance * pixels_per_key)
+}
+
+/// Scales the margin and label areas of a chart with its size.
+struct ChartLayout {
+    factor: f64,
+}
+
+impl ChartLayout {
+    fn new(size: (u32, u32), compact: bool) -> Self {
+        // The unscaled sizes are chosen for the default size of 400x400 pixels.
+        let factor = size.0.min(size.1) as f64 / 400.0;
+        Self {
+            factor: if compact { factor * 0.6 } else { factor },
+        }
+    }
+
+    fn scale(&self, pixels: u32) -> u32 {
+        ((pixels as f64 * self.factor).round() as u32).max(1)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_mesh<DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    theme: &Theme,
+    x_labels: usize,
+    x_ticks: Option<&[(f64, String)]>,
+    y_ticks: Option<&[(f64, String)]>,
+    x_label_formatter: &dyn Fn(&f64) -> String,
+    y_label_formatter: &dyn Fn(&f32) -> String,
+    x_desc: String,
+    y_desc: String,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    info!("Configuring chart mesh");
+    // Dashed grid lines are not supported by the mesh, so they are drawn separately.
+    // The same holds for explicit ticks, since the mesh places its own ticks.
+    let solid_grid = theme.grid_style == GridStyle::Solid;
+    let y_labels = 10;
+    let x_range = chart.x_range();
+    let y_range = chart.y_range();
+    let x_ticks: Option<Vec<_>> =
+        x_ticks.map(|ticks| ticks.iter().filter(|(x, _)| x_range.contains(x)).collect());
+    let y_ticks: Option<Vec<_>> = y_ticks.map(|ticks| {
+        ticks
+            .iter()
+            .map(|(y, label)| (*y as f32, label))
+            .filter(|(y, _)| y_range.contains(y))
+            .collect()
+    });
+    let x_grid_positions: Vec<_> = if let Some(x_ticks) = &x_ticks {
+        x_ticks.iter().map(|(x, _)| *x).collect()
+    } else {
+        RangedCoordf64::from(x_range.clone()).key_points(x_labels)
+    };
+    let y_grid_positions: Vec<_> = if let Some(y_ticks) = &y_ticks {
+        y_ticks.iter().map(|(y, _)| *y).collect()
+    } else {
+        RangedCoordf32::from(y_range.clone()).key_points(y_labels)
+    };
+
+    let mut mesh = chart.configure_mesh();
+    if !(theme.grid.x() && solid_grid) || x_ticks.is_some() {
+        mesh.disable_x_mesh();
+    }
+    if !(theme.grid.y() && solid_grid) || y_ticks.is_some() {
+        mesh.disable_y_mesh();
+    }
+    mesh.x_labels(if x_ticks.is_some() { 0 } else { x_labels })
+        .y_labels(if y_ticks.is_some() { 0 } else { y_labels })
+        .x_label_formatter(x_label_formatter)
+        .y_label_formatter(y_label_formatter)
+        .x_desc(x_desc)
+        .y_desc(y_desc)
+        .draw()?;
+
+    let grid_line_style = BLACK.mix(0.3);
+    let mut draw_grid_line = |line: [(f64, f32); 2]| -> anyhow::Result<()> {
+        if solid_grid {
+            chart.draw_series(LineSeries::new(line, grid_line_style))?;
+        } else {
+            chart.draw_series(DashedLineSeries::new(line, 4, 4, grid_line_style.into()))?;
+        }
+        Ok(())
+    };
+    if theme.grid.x() && (!solid_grid || x_ticks.is_some()) {
+        for &x in &x_grid_positions {
+            draw_grid_line([(x, y_range.start), (x, y_range.end)])?;
+        }
+    }
+    if theme.grid.y() && (!solid_grid || y_ticks.is_some()) {
+        for &y in &y_grid_positions {
+            draw_grid_line([(x_range.start, y), (x_range.end, y)])?;
+        }
+    }
+
+    // Only the given positions receive a tick and a label.
+    let label_font = (theme.font_family.as_str(), 12).into_font().color(&BLACK);
+    if let Some(x_ticks) = x_ticks {
+        chart.draw_series(x_ticks.into_iter().map(|(x, label)| {
+            EmptyElement::at((*x, y_range.start))
+                + PathElement::new([(0, 0), (0, 5)], BLACK)
+                + Text::new(
+                    label.clone(),
+                    (0, 8),
+                    label_font.pos(Pos::new(HPos::Center, VPos::Top)),
+                )
+        }))?;
+    }
+    if let Some(y_ticks) = y_ticks {
+        chart.draw_series(y_ticks.into_iter().map(|(y, label)| {
+            EmptyElement::at((x_range.start, y))
+                + PathElement::new([(0, 0), (-5, 0)], BLACK)
+                + Text::new(
+                    label.clone(),
+                    (-8, 0),
+                    label_font.pos(Pos::new(HPos::Right, VPos::Center)),
+                )
+        }))?;
+    }
+    Ok(())
+}
+
+fn draw_legend<'a, DB: DrawingBackend + 'a>(
+    chart: &mut Chart<'a, DB>,
+    theme: &Theme,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .position(theme.legend_position.into())
+        .draw()?;
+    Ok(())
+}
+
+/// Write the sum of the runtimes of the given files into a top corner of the chart that is not covered by the legend.
+fn draw_total_runtime<'a, DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    theme: &Theme,
+    statistics_files: impl IntoIterator<Item = &'a StatisticsFile>,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let total_runtime: f64 = statistics_files
+        .into_iter()
+        .map(|file| file.statistics.statistics.duration_seconds.raw())
+        .sum();
+    let x_range = chart.x_range();
+    let y_range = chart.y_range();
+    let (x, horizontal_position) = if theme.legend_position == LegendPosition::TopRight {
+        (x_range.start, HPos::Left)
+    } else {
+        (x_range.end, HPos::Right)
+    };
+
+    chart.draw_series([Text::new(
+        format!("Σ runtime: {}", format_duration(total_runtime)),
+        (x, y_range.end),
+        (theme.font_family.as_str(), theme.annotation_font_size)
+            .into_font()
+            .color(&BLACK)
+            .pos(Pos::new(horizontal_position, VPos::Top)),
+    )])?;
+    Ok(())
+}
+
+/// Write the number of plotted files unobtrusively into the bottom corner that is not covered by the legend.
+fn draw_file_count<DB: DrawingBackend>(
+    chart: &mut Chart<DB>,
+    theme: &Theme,
+    file_count: usize,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let x_range = chart.x_range();
+    let y_range = chart.y_range();
+    let (x, horizontal_position) = if theme.legend_position == LegendPosition::BottomRight {
+        (x_range.start, HPos::Left)
+    } else {
+        (x_range.end, HPos::Right)
+    };
+
+    chart.draw_series([Text::new(
+        format!("n={file_count}"),
+        (x, y_range.start),
+        (theme.font_family.as_str(), theme.annotation_font_size)
+            .into_font()
+            .color(&RGBColor(128, 128, 128))
+            .pos(Pos::new(horizontal_position, VPos::Bottom)),
+    )])?;
+    Ok(())
+}
+
+/// The quartiles of the given values in chart space.
+/// They are computed in data space and then mapped, which keeps them quartiles since every value transform is monotone.
+/// Transforms that reverse the order of values swap the lower and the upper quartile.
+/// The fences are computed from the interquartile range in chart space.
+fn transformed_quartiles(values: &[f64], to_chart_value: impl Fn(f64) -> f64) -> Quartiles {
+    let mut values = values.to_vec();
+    values.sort_by(f64::total_cmp);
+    let [lower, median, upper] =
+        [25.0, 50.0, 75.0].map(|percentile| to_chart_value(stats::percentile(&values, percentile)));
+    // The 25th, 50th and 75th percentile of these values are exactly the mapped quartiles.
+    Quartiles::new(&[lower, lower, median, upper, upper])
+}
+
+/// A box like [`Boxplot`], but narrowed around the median to the confidence interval of the median.
+/// The quartiles are given in chart space and the half width in key units.
+fn notched_boxplot(
+    key: f64,
+    quartiles: &Quartiles,
+    sample_size: usize,
+    half_width: f64,
+    whisker_width: f64,
+    style: ShapeStyle,
+) -> Vec<PathElement<(f64, f32)>> {
+    let [lower_fence, lower_quartile, median, upper_quartile, upper_fence] = quartiles.values();
+    let notch = stats::median_confidence_half_width(
+        lower_quartile.into(),
+        upper_quartile.into(),
+        sample_size,
+    ) as f32;
+    let (notch_lower, notch_upper) = (
+        (median - notch).max(lower_quartile),
+        (median + notch).min(upper_quartile),
+    );
+    let (left, right) = (key - half_width, key + half_width);
+    let (notch_left, notch_right) = (key - half_width / 2.0, key + half_width / 2.0);
+    let whisker_half_width = half_width * whisker_width;
+
+    vec![
+        PathElement::new(
+            [
+                (left, lower_quartile),
+                (left, notch_lower),
+                (notch_left, median),
+                (left, notch_upper),
+                (left, upper_quartile),
+                (right, upper_quartile),
+                (right, notch_upper),
+                (notch_right, median),
+                (right, notch_lower),
+                (right, lower_quartile),
+                (left, lower_quartile),
+            ],
+            style,
+        ),
+        PathElement::new([(notch_left, median), (notch_right, median)], style),
+        PathElement::new([(key, lower_quartile), (key, lower_fence)], style),
+        PathElement::new([(key, upper_quartile), (key, upper_fence)], style),
+        PathElement::new(
+            [
+                (key - whisker_half_width, lower_fence),
+                (key + whisker_half_width, lower_fence),
+            ],
+            style,
+        ),
+        PathElement::new(
+            [
+                (key - whisker_half_width, upper_fence),
+                (key + whisker_half_width, upper_fence),
+            ],
+            style,
+        ),
+    ]
+}
+
+/// Diagonal lines covering the given rectangle, to distinguish a box from a filled one.
+fn hatch_lines(
+    (left, right): (f64, f64),
+    (bottom, top): (f32, f32),
+    style: RGBColor,
+) -> Vec<PathElement<(f64, f32)>> {
+    const HATCH_LINE_AMOUNT: usize = 4;
+
+    (1..2 * HATCH_LINE_AMOUNT)
+        .map(|index| {
+            // The line `u + v = c` in coordinates normalised to the rectangle.
+            let c = index as f64 / HATCH_LINE_AMOUNT as f64;
+            let point = |u: f64| {
+                (
+                    left + u * (right - left),
+                    bottom + (c - u) as f32 * (top - bottom),
+                )
+            };
+            PathElement::new([point((c - 1.0).max(0.0)), point(c.min(1.0))], style)
+        })
+        .collect()
+}
+
+/// Report how the given amount of zero values of a plot are placed on a logarithmic axis.
+fn report_log_zeros(
+    name: &str,
+    value_transform: AxisTransform,
+    log_zero_policy: LogZeroPolicy,
+    zero_amount: usize,
+) {
+    if !value_transform.is_log() || zero_amount == 0 {
+        return;
+    }
+
+    if log_zero_policy == LogZeroPolicy::Skip {
+        diagnostics::report(
+            Level::Warn,
+            DiagnosticCategory::NonFiniteValue,
+            "Skipping zero values that cannot be placed on a logarithmic axis",
+            [
+                ("plot", name.to_string()),
+                ("amount", zero_amount.to_string()),
+            ],
+        );
+    } else {
+        info!("Replacing {zero_amount} zero values of plot {name} according to policy {log_zero_policy:?}");
+    }
+}
+
+/// Counts the non-finite coordinates skipped while drawing a plot, and reports them once when dropped.
+struct SkippedCoordinates {
+    plot: String,
+    amounts: RefCell<BTreeMap<&'static str, usize>>,
+}
+
+impl SkippedCoordinates {
+    fn new(plot: &impl ToString) -> Self {
+        Self {
+            plot: plot.to_string(),
+            amounts: Default::default(),
+        }
+    }
+
+    /// Convert a coordinate into chart coordinates, or skip and count it if it is not finite.
+    ///
+    /// Non-finite coordinates are rendered as gaps or may make `plotters` panic, so they must never be drawn.
+    fn finite_or_skip(&self, element: &'static str, key: f64, value: f64) -> Option<(f64, f32)> {
+        let coordinate = (key, value as f32);
+        if coordinate.0.is_finite() && coordinate.1.is_finite() {
+            Some(coordinate)
+        } else {
+            debug!("Skipping non-finite {element} at key {key} with value {value}");
+            *self.amounts.borrow_mut().entry(element).or_default() += 1;
+            None
+        }
+    }
+}
+
+impl Drop for SkippedCoordinates {
+    fn drop(&mut self) {
+        let amounts = self.amounts.get_mut();
+        if amounts.is_empty() {
+            return;
+        }
+
+        diagnostics::report(
+            Level::Warn,
+            DiagnosticCategory::NonFiniteValue,
+            "Skipped non-finite coordinates",
+            [
+                ("plot", self.plot.clone()),
+                ("amount", amounts.values().sum::<usize>().to_string()),
+                (
+                    "elements",
+                    amounts
+                        .iter()
+                        .map(|(element, amount)| format!("{amount} {element}"))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+            ],
+        );
+    }
+}
+
+/// Widen an empty range around its value, such that a chart built on it does not collapse.
+fn widen_degenerate_range(min: f64, max: f64) -> (f64, f64) {
+    let epsilon = min.abs().max(max.abs()) * 1e-12;
+    if max - min > epsilon {
+        (min, max)
+    } else if min.abs() > epsilon {
+        (min - min.abs() * 0.5, max + min.abs() * 0.5)
+    } else {
+        (min - 0.5, max + 0.5)
+    }
+}
+
+/// Extract the values of the files merged into `file`, skipping non-finite values with a warning.
+fn finite_values(
+    file: &MergedStatisticsFile,
+    value_fn: impl Fn(&StatisticsFile) -> f64,
+    group_name: &impl ToString,
+) -> Vec<f64> {
+    file.contained_files
+        .iter()
+        .map(value_fn)
+        .filter(|value| {
+            if value.is_finite() {
+                true
+            } else {
+                diagnostics::report(
+                    Level::Warn,
+                    DiagnosticCategory::NonFiniteValue,
+                    "Skipping non-finite value",
+                    [
+                        ("group", group_name.to_string()),
+                        ("key", file.key.to_string()),
+                        ("value", value.to_string()),
+                    ],
+                );
+                false
+            }
+        })
+        .collect()
+}
+
+/// Load the given statistics files, and if `load_sidecar_metadata` is set, also their metadata sidecars.
+///
+/// Unless `skip_errors` is set, all files that cannot be loaded, or whose sidecar cannot be loaded,
+/// are listed before panicking.
+fn load_statistics_files(
+    paths: &[PathBuf],
+    load_sidecar_metadata: bool,
+    tolerate_truncated: bool,
+    skip_errors: bool,
+    cache: Option<&StatisticsCache>,
+) -> Vec<StatisticsFile> {
+    let mut statistics_files = Vec::new();
+    let mut missing_sidecar_amount = 0;
+    let mut failures = Vec::new();
+    for (path, parsed) in paths.iter().zip(parse_statistics_files(paths, cache)) {
+        let mut statistics_file = match parsed {
+            Ok(statistics_file) => statistics_file,
+            Err(LoadFailure::Read(error)) => {
+                failures.push((path, "read", error.to_string()));
+                continue;
+            }
+            Err(LoadFailure::Parse { source, error }) if tolerate_truncated => {
+                let Some((statistics_file, defaulted_fields)) =
+                    StatisticsFile::recover_truncated(&source)
+                else {
+                    diagnostics::report(
+                        Level::Warn,
+                        DiagnosticCategory::DroppedFile,
+                        "Skipping statistics file that cannot be parsed or recovered",
+                        [
+                            ("path", format!("{path:?}")),
+                            ("error", error.message().replace('\n', " ")),
+                        ],
+                    );
+                    continue;
+                };
+                diagnostics::report(
+                    Level::Warn,
+                    DiagnosticCategory::TruncatedFile,
+                    "Recovered truncated statistics file",
+                    [
+                        ("path", format!("{path:?}")),
+                        ("defaulted_fields", defaulted_fields.join(" ")),
+                    ],
+                );
+                statistics_file
+            }
+            Err(LoadFailure::Parse { error, .. }) => {
+                failures.push((path, "parse", error.to_string()));
+                continue;
+            }
+        };
+        if load_sidecar_metadata {
+            match statistics_file.load_sidecar_metadata(path) {
+                Ok(true) => {}
+                Ok(false) => missing_sidecar_amount += 1,
+                Err(error) => {
+                    failures.push((path, "load the metadata of", error));
+                    continue;
+                }
+            }
+        }
+        statistics_files.push(statistics_file);
+    }
+
+    if !failures.is_empty() {
+        if !skip_errors {
+            panic!(
+                "Could not load {} of {} statistics files:\n{}",
+                failures.len(),
+                paths.len(),
+                failures
+                    .iter()
+                    .map(|(path, action, error)| format!("Could not {action} {path:?}: {error}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+        for (path, action, error) in &failures {
+            diagnostics::report(
+                Level::Warn,
+                DiagnosticCategory::DroppedFile,
+                format!("Could not {action} statistics file, skipping it"),
+                [
+                    ("path", format!("{path:?}")),
+                    ("error", error.replace('\n', " ")),
+                ],
+            );
+        }
+        info!(
+            "Skipped {} of {} statistics files",
+            failures.len(),
+            paths.len()
+        );
+    }
+    if missing_sidecar_amount > 0 {
+        diagnostics::report(
+            Level::Warn,
+            DiagnosticCategory::MissingMetadata,
+            "Some statistics files have no metadata sidecar",
+            [("amount", missing_sidecar_amount)],
+        );
+    }
+    statistics_files
+}
+
+/// Why a statistics file could not be loaded.
+enum LoadFailure {
+    Read(std::io::Error),
+    /// The source is kept such that truncated files can be recovered.
+    Parse {
+        source: String,
+        error: toml::de::Error,
+    },
+}
+
+/// Read and parse the given statistics files on all available cores, in the order of the paths.
+/// Unchanged files are loaded from the cache if given, and parsed files are stored in it.
+fn parse_statistics_files(
+    paths: &[PathBuf],
+    cache: Option<&StatisticsCache>,
+) -> Vec<Result<StatisticsFile, LoadFailure>> {
+    let parse = |path: &PathBuf| {
+        if let Some(statistics_file) = cache.and_then(|cache| cache.load(path)) {
+            return Ok(statistics_file);
+        }
+        let source = std::fs::read_to_string(path).map_err(LoadFailure::Read)?;
+        let statistics_file = StatisticsFile::from_toml_str(&source)
+            .map_err(|error| LoadFailure::Parse { source, error })?;
+        if let Some(cache) = cache {
+            cache.store(path, &statistics_file);
+        }
+        Ok(statistics_file)
+    };
+
+    // Each thread takes the next path from a shared index, such that the load is balanced even if the file sizes vary.
+    let next_index = AtomicUsize::new(0);
+    let thread_amount = std::thread::available_parallelism()
+        .map_or(1, usize::from)
+        .min(paths.len())
+        .max(1);
+    let mut results: Vec<_> = std::thread::scope(|scope| {
+        let threads: Vec<_> = (0..thread_amount)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next_index.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = paths.get(index) else {
+                            return results;
+                        };
+                        results.push((index, parse(path)));
+                    }
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .flat_map(|thread| {
+                thread
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// The toml files in the given directory, and if `recursive` is set also in its subdirectories, sorted by path.
+/// Directories that were already visited, e.g. through a symlink cycle, are skipped.
+fn statistics_files_in_directory(directory: &Path, recursive: bool) -> Vec<PathBuf> {
+    statistics_files_in_unvisited_directory(directory, recursive, &mut BTreeSet::new())
+}
+
+fn statistics_files_in_unvisited_directory(
+    directory: &Path,
+    recursive: bool,
+    visited_directories: &mut BTreeSet<PathBuf>,
+) -> Vec<PathBuf> {
+    let canonical_directory = directory
+        .canonicalize()
+        .unwrap_or_else(|error| panic!("Could not read directory {directory:?}: {error}"));
+    if !visited_directories.insert(canonical_directory) {
+        debug!("Skipping directory {directory:?}, since it was visited already");
+        return Vec::new();
+    }
+
+    let mut paths = Vec::new();
+    let mut skipped_amount = 0;
+    for entry in std::fs::read_dir(directory)
+        .unwrap_or_else(|error| panic!("Could not read directory {directory:?}: {error}"))
+    {
+        let path = entry
+            .unwrap_or_else(|error| panic!("Could not read directory {directory:?}: {error}"))
+            .path();
+        if path.is_dir() {
+            if recursive {
+                paths.extend(statistics_files_in_unvisited_directory(
+                    &path,
+                    recursive,
+                    visited_directories,
+                ));
+            }
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "toml")
+        {
+            paths.push(path);
+        } else {
+            skipped_amount += 1;
+        }
+    }
+    if skipped_amount > 0 {
+        info!("Skipping {skipped_amount} files that are not toml files in {directory:?}");
+    }
+    paths.sort();
+    paths
+}
+
+/// Replace directories by the statistics files in them,
+/// and paths containing the wildcards `*` or `?` by the matching toml files.
+/// Wildcards in directory names match directories, e.g. `runs/*/statistics.toml`.
+/// The argument `-` is replaced by the paths read from stdin, which are expanded in the same way.
+/// Other paths are kept as they are.
+fn expand_statistics_file_arguments(arguments: &[PathBuf], recursive: bool) -> Vec<PathBuf> {
+    let arguments: Vec<_> = arguments
+        .iter()
+        .flat_map(|argument| {
+            if argument.as_os_str() == "-" {
+                info!("Reading statistics file paths from stdin");
+                std::io::stdin()
+                    .lines()
+                    .map(|line| {
+                        line.unwrap_or_else(|error| {
+                            panic!("Could not read paths from stdin: {error}")
+                        })
+                    })
+                    .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+                    .map(PathBuf::from)
+                    .collect()
+            } else {
+                vec![argument.clone()]
+            }
+        })
+        .collect();
+
+    let mut paths = Vec::new();
+    for argument in &arguments {
+        let has_wildcards = argument
+            .to_str()
+            .is_some_and(|argument| argument.contains(['*', '?']));
+        if argument.is_dir() {
+            paths.extend(statistics_files_in_directory(argument, recursive));
+        } else if has_wildcards {
+            let matches = expand_wildcards(argument);
+            if matches.is_empty() {
+                panic!("No statistics files match {argument:?}");
+            }
+            paths.extend(matches);
+        } else {
+            paths.push(argument.clone());
+        }
+    }
+    info!("Found {} statistics files", paths.len());
+    paths
+}
+
+/// The toml files matching the given path, whose components may contain wildcards, sorted by path.
+/// Components before the last one match directories.
+fn expand_wildcards(pattern: &Path) -> Vec<PathBuf> {
+    let components: Vec<_> = pattern.components().collect();
+    let mut prefixes = vec![PathBuf::new()];
+    for (index, component) in components.iter().enumerate() {
+        let is_last = index + 1 == components.len();
+        let component_pattern = match component {
+            Component::Normal(name) => name.to_str().filter(|name| name.contains(['*', '?'])),
+            _ => None,
+        };
+        let Some(component_pattern) = component_pattern else {
+            for prefix in &mut prefixes {
+                prefix.push(component);
+            }
+            continue;
+        };
+
+        prefixes = prefixes
+            .iter()
+            .flat_map(|prefix| {
+                let directory = if prefix.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    prefix.as_path()
+                };
+                // Directories that cannot be read contain no matches.
+                std::fs::read_dir(directory)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Result::ok)
+                    .filter(|entry| {
+                        entry
+                            .file_name()
+                            .to_str()
+                            .is_some_and(|name| matches_wildcards(component_pattern, name))
+                    })
+                    .map(|entry| prefix.join(entry.file_name()))
+                    .filter(|path| is_last || path.is_dir())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+    }
+
+    let mut paths: Vec<_> = prefixes
+        .into_iter()
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension == "toml")
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// True if the name matches the pattern, where `*` matches any sequence of characters and `?` any single character.
+fn matches_wildcards(pattern: &str, name: &str) -> bool {
+    let (pattern, name): (Vec<_>, Vec<_>) = (pattern.chars().collect(), name.chars().collect());
+    // The positions after the last `*` in the pattern and the name, to backtrack to if a later character does not match.
+    let (mut pattern_index, mut name_index, mut backtrack) = (0, 0, None);
+    while name_index < name.len() {
+        match pattern.get(pattern_index) {
+            Some('*') => {
+                pattern_index += 1;
+                backtrack = Some((pattern_index, name_index));
+            }
+            Some(&character) if character == '?' || character == name[name_index] => {
+                pattern_index += 1;
+                name_index += 1;
+            }
+            _ => {
+                let Some((star_pattern_index, star_name_index)) = backtrack else {
+                    return false;
+                };
+                pattern_index = star_pattern_index;
+                name_index = star_name_index + 1;
+                backtrack = Some((star_pattern_index, name_index));
+            }
+        }
+    }
+    pattern[pattern_index..]
+        .iter()
+        .all(|&character| character == '*')
+}
+
+/// Apply the timeout and drop suboptimal files, as requested on the command line.
+fn filter_statistics_files(statistics_files: &mut Vec<StatisticsFile>, cli: &Cli) {
+    if cli.detect_unit_mismatch {
+        detect_unit_mismatches(statistics_files, &cli.group_by_meta);
+    }
+
+    if let Some(timeout) = cli.timeout {
+        for file in statistics_files.iter_mut() {
+            file.apply_timeout(timeout);
+        }
+        let censored_file_amount = statistics_files.iter().filter(|file| file.censored).count();
+        diagnostics::report(
+            if censored_file_amount > 0 {
+                Level::Warn
+            } else {
+                Level::Info
+            },
+            DiagnosticCategory::Censored,
+            "Clamped runtimes at the timeout",
+            [
+                ("amount", censored_file_amount.to_string()),
+                (
+                    "fraction",
+                    format!(
+                        "{:.3}",
+                        censored_file_amount as f64 / statistics_files.len() as f64
+                    ),
+                ),
+            ],
+        );
+    }
+
+    if cli.by_timestamp {
+        let file_amount = statistics_files.len();
+        statistics_files.retain(|file| file.parameters.parsed_timestamp().is_some());
+        let dropped_file_amount = file_amount - statistics_files.len();
+        if dropped_file_amount > 0 {
+            diagnostics::report(
+                Level::Warn,
+                DiagnosticCategory::DroppedFile,
+                "Dropped files without valid RFC3339 timestamp",
+                [("amount", dropped_file_amount)],
+            );
+        }
+        if statistics_files.is_empty() {
+            panic!("None of the statistics files has a valid RFC3339 timestamp to key by.");
+        }
+    }
+
+    if cli.drop_first_per_group {
+        let mut run_sets: BTreeMap<_, Vec<_>> = Default::default();
+        for (index, file) in statistics_files.iter().enumerate() {
+            run_sets
+                .entry(run_set(file, &cli.group_by_meta))
+                .or_default()
+                .push((file.parameters.seed, index));
+        }
+        let warmup_runs: BTreeSet<_> = run_sets
+            .into_values()
+            .filter(|runs| runs.len() > 1)
+            .map(|runs| runs.into_iter().min().unwrap().1)
+            .collect();
+
+        let mut index = 0;
+        statistics_files.retain(|_| {
+            index += 1;
+            !warmup_runs.contains(&(index - 1))
+        });
+        diagnostics::report(
+            Level::Info,
+            DiagnosticCategory::DroppedFile,
+            "Dropped the warmup run with the lowest seed of each set of repeated runs",
+            [("amount", warmup_runs.len())],
+        );
+    }
+
+    if !cli.filter.is_empty() {
+        let known_keys =
+            ParameterFilter::known_keys(statistics_files.iter().map(|file| &file.parameters));
+        if let Err(error) = ParameterFilter::check_keys(&cli.filter, &known_keys) {
+            panic!("{error}");
+        }
+
+        let file_amount = statistics_files.len();
+        statistics_files.retain(|file| {
+            cli.filter
+                .iter()
+                .all(|filter| filter.matches(&file.parameters))
+        });
+        let dropped_file_amount = file_amount - statistics_files.len();
+        if dropped_file_amount > 0 {
+            diagnostics::report(
+                Level::Warn,
+                DiagnosticCategory::DroppedFile,
+                "Dropped files that do not match the filters",
+                [("amount", dropped_file_amount)],
+            );
+        }
+    }
+
+    if cli.optimal_only {
+        let file_amount = statistics_files.len();
+        statistics_files.retain(|file| file.optimal);
+        let dropped_file_amount = file_amount - statistics_files.len();
+        if dropped_file_amount > 0 {
+            diagnostics::report(
+                Level::Warn,
+                DiagnosticCategory::DroppedFile,
+                "Dropped files with suboptimal alignments",
+                [("amount", dropped_file_amount)],
+            );
+        }
+    }
+}
+
+/// The parameters of `file` without those that differ between repetitions of the same run.
+/// Of the sidecar metadata, only the fields in `group_by_meta` are kept.
+fn run_set(file: &StatisticsFile, group_by_meta: &[String]) -> AlignmentParameters {
+    // Runs that differ only in their seed, and hence in the resulting cost, are repetitions.
+    // Sidecar fields such as the host differ between repetitions too, unless they are grouped by.
+    let mut parameters = file.parameters.clone();
+    parameters.seed = 0;
+    parameters.cost = 0;
+    parameters.timestamp = None;
+    parameters
+        .extra
+        .retain(|key, _| group_by_meta.contains(key));
+    parameters
+}
+
+/// Warn about files whose memory or runtime is off by a factor of at least a thousand
+/// from the median of their run set, since they were likely recorded in a different unit.
+fn detect_unit_mismatches(statistics_files: &[StatisticsFile], group_by_meta: &[String]) {
+    const SUSPICIOUS_FACTOR: f64 = 1000.0;
+
+    let mut run_sets: BTreeMap<_, Vec<_>> = Default::default();
+    for file in statistics_files {
+        run_sets
+            .entry(run_set(file, group_by_meta))
+            .or_default()
+            .push(file);
+    }
+
+    let mut mismatch_amount = 0;
+    for files in run_sets.values() {
+        for (name, value) in [
+            (
+                "memory_bytes",
+                (|file: &StatisticsFile| file.memory_bytes.map(|memory| memory as f64))
+                    as fn(&StatisticsFile) -> Option<f64>,
+            ),
+            ("duration_seconds", |file| {
+                Some(file.statistics.statistics.duration_seconds.raw())
+            }),
+        ] {
+            let mut values: Vec<_> = files
+                .iter()
+                .filter_map(|file| value(file))
+                .filter(|value| value.is_finite() && *value > 0.0)
+                .collect();
+            if values.len() < 3 {
+                // With less than three values, the median does not tell which one is off.
+                continue;
+            }
+            values.sort_by(f64::total_cmp);
+            let median = values[values.len() / 2];
+
+            for file in files {
+                let Some(value) = value(file).filter(|value| value.is_finite() && *value > 0.0)
+                else {
+                    continue;
+                };
+                let factor = value / median;
+                if !(1.0 / SUSPICIOUS_FACTOR..SUSPICIOUS_FACTOR).contains(&factor) {
+                    mismatch_amount += 1;
+                    diagnostics::report(
+                        Level::Warn,
+                        DiagnosticCategory::UnitMismatch,
+                        format!("Suspicious {name}, likely recorded in a different unit"),
+                        [
+                            (
+                                "test_sequence_name",
+                                file.parameters.test_sequence_name.clone(),
+                            ),
+                            ("length", file.parameters.length.to_string()),
+                            ("seed", file.parameters.seed.to_string()),
+                            ("value", value.to_string()),
+                            ("median", median.to_string()),
+                        ],
+                    );
+                }
+            }
+        }
+    }
+
+    if mismatch_amount == 0 {
+        info!("Found no suspicious memory or runtime magnitudes");
+    }
+}
+
+/// Report the seeds that are missing from or duplicated in each run set, and the seeds that are not expected at all.
+/// Returns the deviations of the reported run sets.
+fn check_expected_seeds(
+    statistics_files: &[StatisticsFile],
+    expected_seeds: &ExpectedSeeds,
+    group_by_meta: &[String],
+) -> Vec<(AlignmentParameters, SeedDeviations)> {
+    let mut run_sets: BTreeMap<_, BTreeMap<_, usize>> = Default::default();
+    for file in statistics_files {
+        *run_sets
+            .entry(run_set(file, group_by_meta))
+            .or_default()
+            .entry(file.parameters.seed)
+            .or_default() += 1;
+    }
+
+    let mut incomplete_run_sets = Vec::new();
+    for (run_set, seeds) in &run_sets {
+        let deviations = expected_seeds.deviations(seeds);
+        if deviations.is_empty() {
+            continue;
+        }
+
+        diagnostics::report(
+            Level::Warn,
+            DiagnosticCategory::UnexpectedSeeds,
+            "Run set does not contain exactly the expected seeds",
+            [
+                ("test_sequence_name", run_set.test_sequence_name.clone()),
+                ("length", run_set.length.to_string()),
+                ("alignment_config", run_set.alignment_config.clone()),
+                ("ts_node_ord_strategy", run_set.ts_node_ord_strategy.clone()),
+                ("missing", format!("{:?}", deviations.missing)),
+                ("duplicate", format!("{:?}", deviations.duplicate)),
+                ("unexpected", format!("{:?}", deviations.unexpected)),
+            ],
+        );
+        incomplete_run_sets.push((run_set.clone(), deviations));
+    }
+
+    info!(
+        "{} of {} run sets do not contain exactly the expected seeds",
+        incomplete_run_sets.len(),
+        run_sets.len()
+    );
+    incomplete_run_sets
+}
+
+/// The median value of the files of the baseline group at each key, omitting keys at which it is zero.
+/// Keys at which no ratio to the baseline can be formed are reported.
+fn baseline_medians<GroupName: ToString>(
+    statistics_files: &[StatisticsFile],
+    baseline_group: &str,
+    key_fn: impl Fn(&AlignmentParameters) -> f64,
+    group_name_fn: impl Fn(&StatisticsFile) -> GroupName,
+    value_fn: impl Fn(&StatisticsFile) -> f64,
+) -> BTreeMap<R64, f64> {
+    let mut group_names = BTreeSet::new();
+    let mut baseline_values: BTreeMap<_, Vec<_>> = Default::default();
+    for file in statistics_files {
+        let group_name = group_name_fn(file).to_string();
+        let value = value_fn(file);
+        if group_name == baseline_group && value.is_finite() {
+            baseline_values
+                .entry(R64::new(key_fn(&file.parameters)))
+                .or_default()
+                .push(value);
+        }
+        group_names.insert(group_name);
+    }
+    if !group_names.contains(baseline_group) {
+        panic!(
+            "The baseline group {baseline_group:?} does not exist. The groups are {group_names:?}."
+        );
+    }
+
+    let baseline_medians: BTreeMap<_, _> = baseline_values
+        .into_iter()
+        .map(|(key, mut values)| {
+            values.sort_by(f64::total_cmp);
+            (key, stats::percentile(&values, 50.0))
+        })
+        .filter(|(_, median)| *median != 0.0)
+        .collect();
+    let skipped_keys: BTreeSet<_> = statistics_files
+        .iter()
+        .map(|file| R64::new(key_fn(&file.parameters)))
+        .filter(|key| !baseline_medians.contains_key(key))
+        .collect();
+    for key in skipped_keys {
+        diagnostics::report(
+            Level::Warn,
+            DiagnosticCategory::MissingBaseline,
+            "Skipping key at which the baseline is missing or zero",
+            [("key", key.raw())],
+        );
+    }
+    baseline_medians
+}
+
+/// Append the parity of the seed of `file` to `group_name`, splitting each group in two.
+fn split_by_seed_parity(group_name: String, file: &StatisticsFile) -> String {
+    let parity = match file.parameters.seed % 2 {
+        0 => "even",
+        _ => "odd",
+    };
+    format!("{group_name} ({parity})")
+}
+
+/// Warn about run sets with unequally many even and odd seeds, e.g. an odd amount of seeds,
+/// since splitting them by seed parity yields groups of unequal size.
+fn check_seed_parity_balance(statistics_files: &[StatisticsFile], group_by_meta: &[String]) {
+    let mut run_sets: BTreeMap<_, [usize; 2]> = Default::default();
+    for file in statistics_files {
+        run_sets.entry(run_set(file, group_by_meta)).or_default()
+            [(file.parameters.seed % 2) as usize] += 1;
+    }
+
+    let unbalanced_run_set_amount = run_sets.values().filter(|[even, odd]| even != odd).count();
+    if unbalanced_run_set_amount > 0 {
+        diagnostics::report(
+            Level::Warn,
+            DiagnosticCategory::UnequalGroups,
+            "Run sets have unequally many even and odd seeds, so their halves differ in size",
+            [
+                ("amount", unbalanced_run_set_amount),
+                ("run_set_amount", run_sets.len()),
+            ],
+        );
+    }
+}
+
+fn group_files<GroupName: Ord + ToString>(
+    statistics_files: &[StatisticsFile],
+    group_name_fn: impl Fn(&StatisticsFile) -> GroupName,
+) -> BTreeMap<GroupName, Vec<StatisticsFile>> {
+    info!("Grouping files");
+
+    let mut groups: BTreeMap<_, Vec<_>> = Default::default();
+
+    for file in statistics_files {
+        let group_name = group_name_fn(file);
+        if let Some(group) = groups.get_mut(&group_name) {
+            group.push(file.clone());
+        } else {
+            groups.insert(group_name, vec![file.clone()]);
+        }
+    }
+
+    info!("Created {} groups", groups.len());
+
+    groups
+}
+
+/// Warn if the groups are not of equal size, e.g. because some runs failed or timed out.
+/// Merging aggregates per merge key, so uneven groups can still be plotted, unless `require_balanced_groups` is set.
+fn check_equal_group_sizes<GroupName: ToString>(
+    groups: &BTreeMap<GroupName, Vec<MergedStatisticsFile>>,
+    require_balanced_groups: bool,
+) {
+    let Some(first_group) = groups.values().next() else {
+        // Without groups, e.g. if the filters left no files, there is nothing to compare.
+        return;
+    };
+    let first_group_len = group_file_amount(first_group);
+    if groups
+        .values()
+        .any(|group| group_file_amount(group) != first_group_len)
+    {
+        diagnostics::report(
+            if require_balanced_groups {
+                Level::Error
+            } else {
+                Level::Warn
+            },
+            DiagnosticCategory::UnequalGroups,
+            "Groups are not of equal size",
+            groups
+                .iter()
+                .map(|(group_name, group)| (group_name.to_string(), group_file_amount(group))),
+        );
+        if require_balanced_groups {
+            panic!(
+                "groups are not of equal size:\n{}",
+                group_size_table(groups, false)
+            );
+        }
+        return;
+    }
+
+    info!("All groups have {first_group_len} elements");
+}
+
+/// The amount of files merged into the given group.
+fn group_file_amount(group: &[MergedStatisticsFile]) -> usize {
+    group.iter().map(|file| file.contained_files.len()).sum()
+}
+
+/// Format a table with the size of each group.
+/// If `per_key` is set, the amount of files merged into each key is listed as well.
+fn group_size_table<GroupName: ToString>(
+    groups: &BTreeMap<GroupName, Vec<MergedStatisticsFile>>,
+    per_key: bool,
+) -> String {
+    let group_names: Vec<_> = groups.keys().map(ToString::to_string).collect();
+    let name_width = group_names
+        .iter()
+        .map(String::len)
+        .chain(["group".len()])
+        .max()
+        .unwrap();
+
+    let mut table = format!("{:<name_width$}  files", "group");
+    if per_key {
+        table.push_str("  files per key");
+    }
+    table.push('\n');
+
+    for (group_name, group) in group_names.iter().zip(groups.values()) {
+        table.push_str(&format!(
+            "{group_name:<name_width$}  {:>5}",
+            group_file_amount(group)
+        ));
+        if per_key {
+            let key_counts: Vec<_> = group
+                .iter()
+                .map(|file| format!("{}: {}", file.key, file.contained_files.len()))
+                .collect();
+            table.push_str("  ");
+            table.push_str(&key_counts.join(", "));
+        }
+        table.push('\n');
+    }
 
-    info!("Configuring chart mesh");
-    chart
-        .configure_mesh()
-        .disable_x_mesh()
-        .x_labels(groups.len())
-        .x_label_formatter(&format_value)
-        .y_label_formatter(&|value| format_value(&((*value as f64).powf(value_polynomial_degree))))
-        .x_desc(key_name.to_string())
-        .y_desc(format!(
-            "{} [{}-th root]",
-            value_name.to_string(),
-            value_polynomial_degree
-        ))
-        .draw()
-        .unwrap();
+    table
+}
 
-    let key_range = key_bucket_amount
-        .map(|key_bucket_amount| key_range_len / key_bucket_amount as f64)
-        .unwrap_or(1.0);
-    for (group_index, ((group_name, group), style)) in groups
+/// The median, mean, minimum, maximum and amount of the finite values of each key in each group,
+/// as JSON object mapping group names to arrays of keys.
+fn summary_json<GroupName: ToString>(
+    data: &GroupedPlotData<GroupName>,
+    value_fn: impl Fn(&StatisticsFile) -> f64,
+    percentiles: &[f64],
+    unit: Option<&str>,
+) -> serde_json::Value {
+    data.groups
         .iter()
-        .zip([&RED, &GREEN, &BLUE, &MAGENTA, &CYAN, &RGBColor(10, 100, 10)])
-        .enumerate()
-    {
-        info!("Drawing group {}", group_name.to_string());
-        let coordinate_iterator = group.iter().map(|file| file.key.raw()).zip(group.iter());
-        let key_shift = (((group_index as f64 + 0.5) / groups.len() as f64) * key_range * 0.7)
-            - key_range * 0.5 * 0.7;
-
-        chart
-            .draw_series(coordinate_iterator.map(|(key, file)| {
-                let values: Vec<_> = file.contained_statistics.iter().map(&value_fn).collect();
-                let quartiles = Quartiles::new(&values);
-                let quartiles = Quartiles::new(&quartiles.values().map(|value| {
-                    if (value as f64) < value_epsilon {
-                        0.0
+        .map(|(group_name, group)| {
+            let keys = group
+                .iter()
+                .map(|file| {
+                    let values = finite_values(file, &value_fn, group_name);
+                    let (median, mean, min, max) = if values.is_empty() {
+                        (None, None, None, None)
                     } else {
-                        R64::new(value as f64)
-                            .powf(R64::new(1.0 / value_polynomial_degree))
-                            .raw()
+                        (
+                            Some(Quartiles::new(&values).median()),
+                            Some(values.iter().sum::<f64>() / values.len() as f64),
+                            values.iter().copied().reduce(f64::min),
+                            values.iter().copied().reduce(f64::max),
+                        )
+                    };
+                    let mut summary = serde_json::json!({
+                        "key": file.key.raw(),
+                        "n": values.len(),
+                        "median": median,
+                        "mean": mean,
+                        "min": min,
+                        "max": max,
+                    });
+                    let mut sorted_values = values;
+                    sorted_values.sort_by(f64::total_cmp);
+                    for percentile in percentiles {
+                        summary[format!("p{percentile}")] = serde_json::json!((!sorted_values
+                            .is_empty())
+                        .then(|| stats::percentile(&sorted_values, *percentile)));
                     }
-                }));
-                Boxplot::new_vertical(key + key_shift, &quartiles).style(style)
-            }))
-            .unwrap()
-            .label(group_name.to_string())
-            .legend(move |(x, y)| Rectangle::new([(x - 5, y - 5), (x + 5, y + 5)], style));
-    }
+                    if let Some(unit) = unit {
+                        summary["unit"] = unit.into();
+                    }
+                    summary
+                })
+                .collect();
+            (group_name.to_string(), serde_json::Value::Array(keys))
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
 
-    chart
-        .configure_series_labels()
-        .background_style(WHITE.mix(0.8))
-        .border_style(BLACK)
-        .position(SeriesLabelPosition::LowerRight)
-        .draw()
+/// A table with the area under the curve through the medians of each group, in untransformed space.
+/// Keys without finite values are skipped.
+fn auc_report<GroupName: ToString>(
+    name: &str,
+    data: &GroupedPlotData<GroupName>,
+    value_fn: impl Fn(&StatisticsFile) -> f64,
+) -> String {
+    let rows: Vec<_> = data
+        .groups
+        .iter()
+        .map(|(group_name, group)| {
+            let points: Vec<_> = group
+                .iter()
+                .filter_map(|file| {
+                    let values = finite_values(file, &value_fn, group_name);
+                    (!values.is_empty()).then(|| (file.key.raw(), Quartiles::new(&values).median()))
+                })
+                .collect();
+            (group_name.to_string(), stats::trapezoidal_area(&points))
+        })
+        .collect();
+    let name_width = rows
+        .iter()
+        .map(|(group_name, _)| group_name.len())
+        .chain(["group".len()])
+        .max()
         .unwrap();
-}
 
-fn group_files<GroupName: Ord>(
-    statistics_files: &[StatisticsFile],
-    group_name_fn: impl Fn(&StatisticsFile) -> GroupName,
-) -> BTreeMap<GroupName, Vec<StatisticsFile>> {
-    info!("Grouping files");
+    let mut table = format!(
+        "Area under the curve of {name}\n{:<name_width$}  auc\n",
+        "group"
+    );
+    for (group_name, area) in rows {
+        table.push_str(&format!("{group_name:<name_width$}  {area}\n"));
+    }
+    table
+}
 
-    let mut groups: BTreeMap<_, Vec<_>> = Default::default();
+/// Report each pair of adjacent keys of a group whose medians violate the given monotonicity.
+/// Equal medians are not a violation, and keys without finite values are skipped.
+fn check_monotonicity<GroupName: ToString>(
+    name: &str,
+    data: &GroupedPlotData<GroupName>,
+    monotonicity: Monotonicity,
+    value_fn: impl Fn(&StatisticsFile) -> f64,
+) {
+    let direction = match monotonicity {
+        Monotonicity::Increasing => "increasing",
+        Monotonicity::Decreasing => "decreasing",
+    };
+    let mut violation_amount = 0;
+    for (group_name, group) in &data.groups {
+        let points: Vec<_> = group
+            .iter()
+            .filter_map(|file| {
+                let values = finite_values(file, &value_fn, group_name);
+                (!values.is_empty()).then(|| (file.key.raw(), Quartiles::new(&values).median()))
+            })
+            .collect();
 
-    for file in statistics_files {
-        let group_name = group_name_fn(file);
-        if let Some(group) = groups.get_mut(&group_name) {
-            group.push(file.clone());
-        } else {
-            groups.insert(group_name, vec![file.clone()]);
+        for window in points.windows(2) {
+            let [(key, value), (next_key, next_value)] = [window[0], window[1]];
+            let violated = match monotonicity {
+                Monotonicity::Increasing => next_value < value,
+                Monotonicity::Decreasing => next_value > value,
+            };
+            if violated {
+                violation_amount += 1;
+                diagnostics::report(
+                    Level::Warn,
+                    DiagnosticCategory::NonMonotonic,
+                    format!("Median of {name} is not {direction} between adjacent keys"),
+                    [
+                        ("group", group_name.to_string()),
+                        ("key", key.to_string()),
+                        ("next_key", next_key.to_string()),
+                        ("value", value.to_string()),
+                        ("next_value", next_value.to_string()),
+                    ],
+                );
+            }
         }
     }
+    info!("Found {violation_amount} monotonicity violations in {name}");
+}
 
-    assert!(
-        groups
-            .values()
-            .skip(1)
-            .fold(
-                (groups.values().next().unwrap().len(), true),
-                |(len, truth), group| (len, truth && group.len() == len)
-            )
-            .1,
-        "groups are not of equal size"
-    );
+/// A table with the Pearson and Spearman correlation of the two metrics over all files and within each group.
+/// Files for which one of the metrics is not finite are ignored.
+fn correlation_report<GroupName: Ord + ToString>(
+    statistics_files: &[StatisticsFile],
+    metric_pair: MetricPair,
+    group_name_fn: impl Fn(&StatisticsFile) -> GroupName,
+) -> String {
+    let pairs = |files: &[StatisticsFile]| -> Vec<_> {
+        files
+            .iter()
+            .map(|file| (metric_pair.x.value(file), metric_pair.y.value(file)))
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .collect()
+    };
+    let rows: Vec<_> = [("all".to_string(), pairs(statistics_files))]
+        .into_iter()
+        .chain(
+            group_files(statistics_files, group_name_fn)
+                .into_iter()
+                .map(|(group_name, group)| (group_name.to_string(), pairs(&group))),
+        )
+        .collect();
+    let name_width = rows
+        .iter()
+        .map(|(group_name, _)| group_name.len())
+        .chain(["group".len()])
+        .max()
+        .unwrap();
 
-    info!(
-        "Created {} groups with {} elements each",
-        groups.len(),
-        groups.values().next().unwrap().len()
+    let mut table = format!(
+        "Correlation of {} (x) and {} (y)\n{:<name_width$}  {:>5}  {:>8}  {:>8}\n",
+        metric_pair.x.identifier(),
+        metric_pair.y.identifier(),
+        "group",
+        "files",
+        "pearson",
+        "spearman"
     );
+    let mut undefined = false;
+    for (group_name, pairs) in &rows {
+        let pearson = stats::pearson_correlation(pairs);
+        let spearman = stats::spearman_correlation(pairs);
+        undefined |= pearson.is_nan() || spearman.is_nan();
+        table.push_str(&format!(
+            "{group_name:<name_width$}  {:>5}  {pearson:>8.4}  {spearman:>8.4}\n",
+            pairs.len()
+        ));
+    }
+    if undefined {
+        table.push_str(
+            "NaN: the correlation is undefined, since a metric is constant or there are fewer than two files.\n",
+        );
+    }
 
-    groups
+    table
+}
+
+/// Group and merge the files like [`prepare_grouped_plot`], but instead of checking that the groups
+/// are of equal size, return a table with the sizes of the groups and the amount of files merged into each key.
+fn group_size_report<GroupName: Ord + ToString>(
+    statistics_files: &[StatisticsFile],
+    key_bucket_amount: Option<usize>,
+    key_bucket_scale: KeyBucketScale,
+    key_fn: impl Fn(&AlignmentParameters) -> f64,
+    group_name_fn: impl Fn(&StatisticsFile) -> GroupName,
+    merge_key_fn: impl Fn(&StatisticsFile) -> AlignmentParameters,
+) -> String {
+    let groups = group_files(statistics_files, group_name_fn);
+    let (merged_groups, _, _) = merge_and_sort_files_in_groups(
+        groups,
+        key_bucket_amount,
+        key_bucket_scale,
+        &[],
+        &key_fn,
+        merge_key_fn,
+    );
+    group_size_table(&merged_groups, true)
 }
 
-fn merge_and_sort_files_in_groups<GroupName: Ord>(
+fn merge_and_sort_files_in_groups<GroupName: Ord + ToString>(
     groups: BTreeMap<GroupName, Vec<StatisticsFile>>,
     key_bucket_amount: Option<usize>,
+    key_bucket_scale: KeyBucketScale,
+    percentiles: &[R64],
     key_fn: impl Fn(&AlignmentParameters) -> f64,
     merge_key_fn: impl Fn(&StatisticsFile) -> AlignmentParameters,
 ) -> (BTreeMap<GroupName, Vec<MergedStatisticsFile>>, f64, f64) {
@@ -266,24 +6113,28 @@ fn merge_and_sort_files_in_groups<GroupName: Ord>(
         .values()
         .flat_map(|group| group.iter())
         .map(|file| key_fn(&file.parameters))
-        .fold((f64::INFINITY, 0.0), |(min, max), value| {
-            let min = if min > value { value } else { min };
-            let max = if max < value { value } else { max };
-            (min, max)
+        .filter(|key| key.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), key| {
+            (min.min(key), max.max(key))
         });
+    if key_bucket_amount.is_some() && key_bucket_scale == KeyBucketScale::Log && min_key <= 0.0 {
+        panic!("Logarithmic key buckets require positive keys, but the smallest key is {min_key}.");
+    }
 
     let mut merged_groups: BTreeMap<_, Vec<MergedStatisticsFile>> = Default::default();
 
     for (group_name, group) in groups {
+        let group_len = group.len();
         let mut merged_group: BTreeMap<_, Vec<_>> = Default::default();
 
         for file in group {
             let bucket_index = key_bucket_amount.map(|key_bucket_amount| {
-                let key = key_fn(&file.parameters);
-                let bucket_index = (key - min_key) * key_bucket_amount as f64 / (max_key - min_key);
-                (bucket_index.floor() as usize)
-                    .max(0)
-                    .min(key_bucket_amount - 1)
+                key_bucket_scale.bucket_index(
+                    key_fn(&file.parameters),
+                    min_key,
+                    max_key,
+                    key_bucket_amount,
+                )
             });
 
             let merge_key = (merge_key_fn(&file), bucket_index);
@@ -294,6 +6145,24 @@ fn merge_and_sort_files_in_groups<GroupName: Ord>(
             }
         }
 
+        diagnostics::report(
+            Level::Info,
+            DiagnosticCategory::Merge,
+            "Merged files in group",
+            [
+                ("group", group_name.to_string()),
+                ("files", group_len.to_string()),
+                ("merged_files", merged_group.len().to_string()),
+            ],
+        );
+
+        let mut bucket_file_counts: BTreeMap<_, usize> = Default::default();
+        for ((_, bucket_index), merge_files) in &merged_group {
+            if bucket_index.is_some() {
+                *bucket_file_counts.entry(*bucket_index).or_default() += merge_files.len();
+            }
+        }
+
         merged_groups.insert(
             group_name,
             merged_group
@@ -301,12 +6170,23 @@ fn merge_and_sort_files_in_groups<GroupName: Ord>(
                 .map(|((parameters, bucket_index), merge_files)| {
                     let key = bucket_index
                         .map(|bucket_index| {
-                            ((bucket_index as f64 + 0.5) / key_bucket_amount.unwrap() as f64
-                                * (max_key - min_key))
-                                + min_key
+                            key_bucket_scale.bucket_key(
+                                bucket_index,
+                                min_key,
+                                max_key,
+                                key_bucket_amount.unwrap(),
+                            )
                         })
                         .unwrap_or(key_fn(&parameters));
-                    MergedStatisticsFile::from_statistics_files(R64::new(key), merge_files)
+                    let mut merged_file = MergedStatisticsFile::from_statistics_files(
+                        R64::new(key),
+                        merge_files,
+                        percentiles,
+                    );
+                    if let Some(bucket_file_count) = bucket_file_counts.get(&bucket_index) {
+                        merged_file.bucket_file_count = *bucket_file_count;
+                    }
+                    merged_file
                 })
                 .collect(),
         );
@@ -329,36 +6209,873 @@ fn sort_groups<GroupName: Ord, SortKey: Ord, StatisticsType>(
     groups
 }
 
+/// The label of a transformed value axis, naming the transform unless it is hidden by the render options.
+fn value_axis_label(
+    value_name: impl ToString,
+    value_transform: AxisTransform,
+    render_options: &RenderOptions,
+) -> String {
+    if render_options.hide_transform_in_label {
+        value_name.to_string()
+    } else {
+        format!("{} [{value_transform}]", value_name.to_string())
+    }
+}
+
+/// If the value axis is not transformed, but the positive values span more than two orders of magnitude,
+/// suggest a logarithmic axis, or return it if `auto_transform` is set.
+/// Otherwise, the given transform is returned.
+/// Zero values do not prevent the suggestion, since the log zero policy places them.
+fn suggest_value_transform(
+    name: &impl ToString,
+    value_transform: AxisTransform,
+    min_positive_value: f64,
+    max_value: f64,
+    auto_transform: bool,
+) -> AxisTransform {
+    const MAX_LINEAR_RATIO: f64 = 100.0;
+    // Without positive values, the smallest one is infinite and the ratio is zero.
+    if !value_transform.is_linear() || max_value / min_positive_value <= MAX_LINEAR_RATIO {
+        return value_transform;
+    }
+
+    if auto_transform {
+        info!(
+            "Positive values of plot {} span {min_positive_value}..{max_value}, using a logarithmic value axis",
+            name.to_string()
+        );
+        AxisTransform::Log { base: 10.0 }
+    } else {
+        info!(
+            "Positive values of plot {} span {min_positive_value}..{max_value}, consider using `--value-axis log`",
+            name.to_string()
+        );
+        value_transform
+    }
+}
+
+/// Ticks at round values for a transformed value axis spanning the given range of transformed values.
+///
+/// Evenly spaced ticks in the transformed space would have odd values, so instead the values of evenly spaced ticks
+/// are rounded to as few significant digits as possible while keeping most ticks distinct.
+/// Returns `None` if the axis is not transformed, since then the ticks of the mesh are round already.
+fn value_ticks(
+    value_transform: AxisTransform,
+    min_chart_value: f64,
+    max_chart_value: f64,
+) -> Option<Vec<(f64, String)>> {
+    const TICK_AMOUNT: usize = 10;
+    if value_transform.is_linear() {
+        return None;
+    }
+
+    let values: Vec<_> = (0..=TICK_AMOUNT)
+        .map(|index| {
+            min_chart_value
+                + (max_chart_value - min_chart_value) * index as f64 / TICK_AMOUNT as f64
+        })
+        .map(|chart_value| value_transform.apply_inverse(chart_value))
+        .filter(|value| value.is_finite() && *value >= 0.0)
+        .collect();
+    let rounded_values = (1..=3)
+        .map(|significant_digits| {
+            values
+                .iter()
+                .map(|value| R64::new(round_to_significant_digits(*value, significant_digits)))
+                .collect::<BTreeSet<_>>()
+        })
+        .find(|rounded_values| rounded_values.len() * 3 >= values.len() * 2)
+        .unwrap_or_else(|| values.iter().copied().map(R64::new).collect());
+
+    Some(
+        rounded_values
+            .into_iter()
+            .map(|value| {
+                let value = value.raw();
+                (value_transform.apply(value), format_value(&value))
+            })
+            .filter(|(chart_value, _)| {
+                chart_value.is_finite() && (min_chart_value..=max_chart_value).contains(chart_value)
+            })
+            .collect(),
+    )
+}
+
+fn round_to_significant_digits(value: f64, significant_digits: i32) -> f64 {
+    if value == 0.0 {
+        return 0.0;
+    }
+
+    let exponent = significant_digits - 1 - value.abs().log10().floor() as i32;
+    // Dividing by an exact power of ten is more precise than multiplying with an inexact one.
+    if exponent >= 0 {
+        let factor = 10f64.powi(exponent);
+        (value * factor).round() / factor
+    } else {
+        let factor = 10f64.powi(-exponent);
+        (value / factor).round() * factor
+    }
+}
+
+/// Format a tick of a transformed axis with the untransformed value.
+/// Ticks in the margin of the chart may lie outside of the domain of the transform, and are left empty.
+fn format_transformed_value(value_transform: AxisTransform, value: f64) -> String {
+    let value = value_transform.apply_inverse(value);
+    if value.is_finite() {
+        format_value(&value)
+    } else {
+        String::new()
+    }
+}
+
+/// The header of an exported column of plotted values, with their unit if they were scaled into one.
+fn with_unit(header: &str, render_options: &RenderOptions) -> String {
+    match &render_options.value_unit {
+        Some(unit) => format!("{header} [{unit}]"),
+        None => header.to_string(),
+    }
+}
+
+/// Format a duration given in seconds with the largest fitting unit of hours, minutes and seconds.
+fn format_duration(seconds: f64) -> String {
+    if seconds >= 3600.0 {
+        format!("{:.1}h", seconds / 3600.0)
+    } else if seconds >= 60.0 {
+        format!("{:.1}m", seconds / 60.0)
+    } else {
+        format!("{seconds:.2}s")
+    }
+}
+
 fn format_value(value: &f64) -> String {
+    /// The metric prefixes of the tiers above the unprefixed one, each a factor of a thousand apart.
+    const PREFIXES: [&str; 5] = ["k", "M", "G", "T", "P"];
+
     let value = *value;
     assert!(
-        value.is_sign_positive() && value.is_finite() && !value.is_nan() && !value.is_subnormal(),
+        value.is_finite() && !value.is_subnormal(),
         "Unsupported value: {value}"
     );
 
+    // Also catches negative zero, which should not be labelled with a sign.
     if value == 0.0 {
-        "0".to_string()
-    } else if value < 1e3 {
-        format!("{:.0}", value)
-    } else if value < 1e4 {
-        format!("{:.2}k", value / 1e3)
-    } else if value < 1e5 {
-        format!("{:.1}k", value / 1e3)
-    } else if value < 1e6 {
-        format!("{:.0}k", value / 1e3)
-    } else if value < 1e7 {
-        format!("{:.2}M", value / 1e6)
-    } else if value < 1e8 {
-        format!("{:.1}M", value / 1e6)
-    } else if value < 1e9 {
-        format!("{:.0}M", value / 1e6)
-    } else if value < 1e10 {
-        format!("{:.2}G", value / 1e9)
-    } else if value < 1e11 {
-        format!("{:.1}G", value / 1e9)
-    } else if value < 1e12 {
-        format!("{:.0}G", value / 1e9)
-    } else {
-        todo!("Support larger values");
+        return "0".to_string();
+    } else if value < 0.0 {
+        return format!("-{}", format_value(&-value));
+    } else if value < 1e-3 {
+        return format!("{value:.2e}");
+    }
+
+    // The tier is chosen from the rounded value, such that e.g. 999.6 is labelled 1.00k rather than 1000.
+    let rounded = round_to_significant_digits(value, 3);
+    if rounded < 1.0 {
+        return rounded.to_string();
+    } else if value.round() < 1e3 {
+        return format!("{value:.0}");
+    }
+
+    for (index, prefix) in PREFIXES.into_iter().enumerate() {
+        let scaled = value / 10f64.powi(3 * (index as i32 + 1));
+        // Three significant digits, with one decimal less if rounding reaches the next power of ten.
+        for decimals in [2, 1, 0] {
+            let label = format!("{scaled:.decimals$}");
+            if label.parse::<f64>().unwrap() < 10f64.powi(3 - decimals as i32) {
+                return format!("{label}{prefix}");
+            }
+        }
+    }
+
+    // There are no common prefixes beyond peta.
+    format!("{value:.2e}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_file(seed: u64, host: &str) -> StatisticsFile {
+        let mut file =
+            StatisticsFile::from_toml_str(include_str!("../test_files/statistics-0.toml")).unwrap();
+        file.parameters.seed = seed;
+        file.parameters
+            .extra
+            .insert("host".to_string(), host.to_string());
+        file
+    }
+
+    #[test]
+    fn seeds_with_different_sidecar_hosts_merge_into_one_box() {
+        let groups = BTreeMap::from([(
+            "group".to_string(),
+            vec![test_file(0, "alpha"), test_file(1, "beta")],
+        )]);
+        let (merged_groups, _, _) = merge_and_sort_files_in_groups(
+            groups,
+            None,
+            KeyBucketScale::Linear,
+            &[],
+            |parameters| parameters.length as f64,
+            |file| run_set(file, &[]),
+        );
+        let merged_files = &merged_groups["group"];
+        assert_eq!(merged_files.len(), 1);
+        assert_eq!(merged_files[0].contained_files.len(), 2);
+    }
+
+    #[test]
+    fn grouped_sidecar_fields_stay_apart() {
+        let group_by_meta = ["host".to_string()];
+        assert_ne!(
+            run_set(&test_file(0, "alpha"), &group_by_meta),
+            run_set(&test_file(1, "beta"), &group_by_meta)
+        );
+        assert_eq!(
+            run_set(&test_file(0, "alpha"), &group_by_meta),
+            run_set(&test_file(1, "alpha"), &group_by_meta)
+        );
+    }
+
+    #[test]
+    fn bucket_file_counts_of_uneven_groups() {
+        let file_of_length = |length| {
+            let mut file = test_file(0, "alpha");
+            file.parameters.length = length;
+            file
+        };
+        let groups = BTreeMap::from([
+            (
+                "a".to_string(),
+                vec![
+                    file_of_length(100),
+                    file_of_length(110),
+                    file_of_length(110),
+                    file_of_length(190),
+                ],
+            ),
+            ("b".to_string(), vec![file_of_length(200)]),
+        ]);
+        let (merged_groups, min_key, max_key) = merge_and_sort_files_in_groups(
+            groups,
+            Some(2),
+            KeyBucketScale::Linear,
+            &[],
+            |parameters| parameters.length as f64,
+            |file| run_set(file, &[]),
+        );
+        assert_eq!((min_key, max_key), (100.0, 200.0));
+
+        let counts = |group: &str| {
+            merged_groups[group]
+                .iter()
+                .map(|file| (file.contained_files.len(), file.bucket_file_count))
+                .collect::<Vec<_>>()
+        };
+        // The lengths 100 and 110 share the first bucket, but are merged separately.
+        assert_eq!(counts("a"), vec![(1, 3), (2, 3), (1, 1)]);
+        assert_eq!(counts("b"), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn negative_key_ranges_are_not_stretched_to_zero() {
+        let groups = BTreeMap::from([(
+            "group".to_string(),
+            [300, 500]
+                .into_iter()
+                .map(|length| {
+                    let mut file = test_file(0, "alpha");
+                    file.parameters.length = length;
+                    file
+                })
+                .collect(),
+        )]);
+        let (merged_groups, min_key, max_key) = merge_and_sort_files_in_groups(
+            groups,
+            Some(2),
+            KeyBucketScale::Linear,
+            &[],
+            |parameters| -(parameters.length as f64) / 100.0,
+            |file| run_set(file, &[]),
+        );
+        assert_eq!((min_key, max_key), (-5.0, -3.0));
+        let keys: Vec<_> = merged_groups["group"]
+            .iter()
+            .map(|file| file.key.raw())
+            .collect();
+        assert_eq!(keys, [-4.5, -3.5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Logarithmic key buckets require positive keys")]
+    fn log_key_buckets_reject_non_positive_keys() {
+        let groups = BTreeMap::from([(
+            "group".to_string(),
+            vec![test_file(0, "alpha"), test_file(1, "alpha")],
+        )]);
+        merge_and_sort_files_in_groups(
+            groups,
+            Some(2),
+            KeyBucketScale::Log,
+            &[],
+            |parameters| parameters.seed as f64,
+            |file| run_set(file, &[]),
+        );
+    }
+
+    #[test]
+    fn balance_check_accepts_no_groups() {
+        check_equal_group_sizes(&BTreeMap::<String, Vec<MergedStatisticsFile>>::new(), true);
+    }
+
+    #[test]
+    fn only_the_top_groups_need_to_be_balanced() {
+        let mut statistics_files = Vec::new();
+        for (group, amount) in [("a", 3), ("b", 3), ("c", 1), ("d", 1), ("e", 1)] {
+            for seed in 0..amount {
+                let mut file = test_file(seed, "alpha");
+                file.parameters.test_sequence_name = group.to_string();
+                statistics_files.push(file);
+            }
+        }
+
+        let data = prepare_grouped_plot(
+            &statistics_files,
+            None,
+            KeyBucketScale::Linear,
+            &[],
+            Some(2),
+            true,
+            |parameters| parameters.length as f64,
+            |file| file.parameters.test_sequence_name.clone(),
+            |file| run_set(file, &[]),
+            |file| file.statistics.statistics.opened_nodes.raw(),
+        );
+        let group_sizes: Vec<_> = data
+            .groups
+            .iter()
+            .map(|(group_name, group)| (group_name.as_str(), group_file_amount(group)))
+            .collect();
+        assert_eq!(group_sizes, vec![("a", 3), ("b", 3), ("other", 3)]);
+    }
+
+    #[test]
+    fn rounding_to_significant_digits() {
+        assert_eq!(round_to_significant_digits(0.0, 1), 0.0);
+        assert_eq!(round_to_significant_digits(1234.5, 1), 1000.0);
+        assert_eq!(round_to_significant_digits(1234.5, 2), 1200.0);
+        assert_eq!(round_to_significant_digits(0.012345, 2), 0.012);
+        assert_eq!(round_to_significant_digits(-987.0, 1), -1000.0);
+    }
+
+    #[test]
+    fn ticks_of_transformed_axes_are_round() {
+        assert_eq!(value_ticks(AxisTransform::Linear, 0.0, 100.0), None);
+        assert_eq!(
+            value_ticks(AxisTransform::PolynomialRoot { degree: 1.0 }, 0.0, 100.0),
+            None
+        );
+
+        let value_transform = AxisTransform::PolynomialRoot { degree: 2.0 };
+        let ticks = value_ticks(value_transform, 0.0, 100.0).unwrap();
+        assert!(ticks.len() >= 7);
+        assert!(ticks.windows(2).all(|window| window[0].0 < window[1].0));
+        for (chart_value, label) in ticks {
+            assert!((0.0..=100.0).contains(&chart_value));
+            let value = value_transform.apply_inverse(chart_value);
+            assert!((value - round_to_significant_digits(value, 2)).abs() < 1e-6);
+            assert_eq!(label, format_value(&value));
+        }
+    }
+
+    #[test]
+    fn background_is_only_filled_if_set() {
+        let directory = std::env::temp_dir().join(format!(
+            "template-switch-statistics-background-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        for (name, background) in [
+            ("transparent.svg", None),
+            ("filled.svg", Some(theme::ThemeColor(1, 2, 3))),
+        ] {
+            let path = directory.join(name);
+            let theme = Theme {
+                background,
+                ..Default::default()
+            };
+            create_drawing_area(&path, (10, 10), &theme)
+                .unwrap()
+                .present()
+                .unwrap();
+            let svg = std::fs::read_to_string(&path).unwrap();
+            assert_eq!(svg.contains("<rect"), background.is_some(), "{svg}");
+        }
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn wildcards_match_names() {
+        for (pattern, name, expected) in [
+            ("*", "", true),
+            ("*", "statistics-0.toml", true),
+            ("statistics-*.toml", "statistics-12.toml", true),
+            ("statistics-*.toml", "nested-statistics-0.toml", false),
+            ("statistics-?.toml", "statistics-1.toml", true),
+            ("statistics-?.toml", "statistics-12.toml", false),
+            ("*-*-*.toml", "a-b-c.toml", true),
+            ("*a*b", "xaxbxb", true),
+            ("*a*b", "xaxbx", false),
+            ("run", "run", true),
+            ("run", "runs", false),
+        ] {
+            assert_eq!(
+                matches_wildcards(pattern, name),
+                expected,
+                "{pattern} {name}"
+            );
+        }
+    }
+
+    /// A fresh temporary directory named after the test.
+    fn test_directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!(
+            "template-switch-statistics-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    #[test]
+    fn wildcards_in_directory_names_match_directories() {
+        let directory = test_directory("wildcard-directories");
+        for run in ["run-a", "run-b", "other"] {
+            std::fs::create_dir_all(directory.join(run)).unwrap();
+            std::fs::write(directory.join(run).join("stats.toml"), "").unwrap();
+        }
+        std::fs::write(directory.join("run-c"), "").unwrap();
+
+        assert_eq!(
+            expand_wildcards(&directory.join("run-*").join("stats.toml")),
+            vec![
+                directory.join("run-a").join("stats.toml"),
+                directory.join("run-b").join("stats.toml"),
+            ]
+        );
+        assert_eq!(
+            expand_wildcards(&directory.join("*").join("*.toml")).len(),
+            3
+        );
+        assert!(expand_wildcards(&directory.join("run-*").join("missing.toml")).is_empty());
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn recursion_does_not_follow_symlink_cycles() {
+        let directory = test_directory("symlink-cycle");
+        std::fs::create_dir_all(directory.join("runs")).unwrap();
+        std::fs::write(directory.join("runs").join("stats.toml"), "").unwrap();
+        std::os::unix::fs::symlink(&directory, directory.join("runs").join("loop")).unwrap();
+
+        assert_eq!(
+            statistics_files_in_directory(&directory, true),
+            vec![directory.join("runs").join("stats.toml")]
+        );
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn plots_share_the_union_of_their_value_ranges() {
+        let plot = |min_value, max_value, min_positive_value| GroupedPlotData::<String> {
+            groups: Default::default(),
+            key_bucket_amount: None,
+            min_key: 0.0,
+            max_key: 1.0,
+            min_value,
+            max_value,
+            min_positive_value,
+        };
+        let mut plots = [
+            plot(-5.0, -2.0, f64::INFINITY),
+            plot(-4.0, -1.0, f64::INFINITY),
+        ];
+        share_value_range(&mut plots);
+        for plot in &plots {
+            assert_eq!((plot.min_value, plot.max_value), (-5.0, -1.0));
+        }
+
+        let mut plots = [plot(2.0, 3.0, 2.0), plot(0.0, 10.0, 0.5)];
+        share_value_range(&mut plots);
+        for plot in &plots {
+            assert_eq!(
+                (plot.min_value, plot.max_value, plot.min_positive_value),
+                (0.0, 10.0, 0.5)
+            );
+        }
+    }
+
+    #[test]
+    fn log_axis_is_suggested_for_positive_values_spanning_orders_of_magnitude() {
+        let log = AxisTransform::Log { base: 10.0 };
+        let suggest = |min_positive_value, max_value| {
+            suggest_value_transform(
+                &"plot",
+                AxisTransform::Linear,
+                min_positive_value,
+                max_value,
+                true,
+            )
+        };
+        assert_eq!(suggest(1.0, 1000.0), log);
+        assert_eq!(suggest(1.0, 50.0), AxisTransform::Linear);
+        // Only positive values are compared, so zeros do not prevent the suggestion.
+        assert_eq!(suggest(0.5, 1000.0), log);
+        assert_eq!(suggest(f64::INFINITY, 0.0), AxisTransform::Linear);
+        assert_eq!(
+            suggest_value_transform(&"plot", AxisTransform::Reciprocal, 1.0, 1000.0, true),
+            AxisTransform::Reciprocal
+        );
+    }
+
+    #[test]
+    fn facet_names_are_safe_file_name_components() {
+        assert_eq!(file_name_component("host=a,seed=1"), "host=a,seed=1");
+        assert_eq!(file_name_component("0.5"), "0.5");
+        assert_eq!(file_name_component("../etc/passwd"), "___etc_passwd");
+        assert_eq!(file_name_component("a\\b c:d"), "a_b_c_d");
+        assert_eq!(file_name_component("..."), "__.");
+    }
+
+    #[test]
+    fn non_finite_coordinates_are_skipped_and_counted() {
+        let skipped = SkippedCoordinates::new(&"plot");
+        assert_eq!(skipped.finite_or_skip("point", 1.0, 2.0), Some((1.0, 2.0)));
+        assert_eq!(skipped.finite_or_skip("point", f64::NAN, 2.0), None);
+        assert_eq!(skipped.finite_or_skip("point", 1.0, f64::INFINITY), None);
+        // Values beyond the range of f32 are not finite in chart coordinates.
+        assert_eq!(skipped.finite_or_skip("box value", 1.0, f64::MAX), None);
+        assert_eq!(
+            *skipped.amounts.borrow(),
+            BTreeMap::from([("box value", 1), ("point", 2)])
+        );
+    }
+
+    #[test]
+    fn load_errors_name_the_failing_path() {
+        let directory = test_directory("load-errors");
+        let missing = directory.join("missing.toml");
+        let malformed = directory.join("malformed.toml");
+        let unreadable_sidecar = directory.join("statistics-0.toml");
+        std::fs::write(&malformed, "not toml").unwrap();
+        std::fs::write(
+            &unreadable_sidecar,
+            include_str!("../test_files/statistics-0.toml"),
+        )
+        .unwrap();
+        std::fs::write(unreadable_sidecar.with_extension("meta.json"), "not json").unwrap();
+
+        let paths = [
+            missing.clone(),
+            malformed.clone(),
+            unreadable_sidecar.clone(),
+        ];
+        let panic =
+            std::panic::catch_unwind(|| load_statistics_files(&paths, true, false, false, None))
+                .unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        for path in &paths {
+            assert!(message.contains(&format!("{path:?}")), "{message}");
+        }
+        assert_eq!(
+            load_statistics_files(&paths, true, false, true, None).len(),
+            0
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn quartiles_are_mapped_from_data_space() {
+        let values = [1.0, 10.0, 100.0, 1000.0];
+        let data_quartiles = Quartiles::new(&values).values();
+        assert_eq!(
+            transformed_quartiles(&values, |value| value).values(),
+            data_quartiles
+        );
+
+        for transform in [
+            AxisTransform::Log { base: 10.0 },
+            AxisTransform::PolynomialRoot { degree: 3.0 },
+            AxisTransform::Reciprocal,
+        ] {
+            let [_, lower, median, upper, _] =
+                transformed_quartiles(&values, |value| transform.apply(value)).values();
+            let mut round_trip =
+                [lower, median, upper].map(|value| transform.apply_inverse(value as f64));
+            // The reciprocal reverses the order of the quartiles.
+            round_trip.sort_by(f64::total_cmp);
+            for (round_trip, data_quartile) in round_trip.into_iter().zip(&data_quartiles[1..4]) {
+                assert!(
+                    (round_trip / *data_quartile as f64 - 1.0).abs() < 1e-5,
+                    "{transform} maps {data_quartile} to {round_trip}"
+                );
+            }
+            assert!(lower <= median && median <= upper);
+        }
+    }
+
+    #[test]
+    fn bin_width_splits_the_key_range_into_uniform_bins() {
+        let keys = [0.0, 0.2, 0.7, 1.2, 1.4, 1.9, 2.0];
+        let bins = HistogramBins::new(None, Some(0.5), 1, keys);
+        let mut counts = BTreeMap::<_, usize>::new();
+        for key in keys {
+            *counts.entry(bins.bin(key).unwrap()).or_default() += 1;
+        }
+        // The maximum key lies on the upper edge of the last bin, but is still counted in it.
+        assert_eq!(counts, BTreeMap::from([(0, 2), (1, 1), (2, 2), (3, 2)]));
+        assert_eq!(bins.bounds(3), (1.5, 2.0));
+        assert_eq!(bins.center(0), 0.25);
+        assert_eq!(bins.label(0.25), "0.25");
+    }
+
+    #[test]
+    fn histogram_keys_are_rounded_to_multiples() {
+        let bins = HistogramBins::new(None, None, 5, []);
+        assert_eq!(bins.bin(3.0), Some(1));
+        assert_eq!(bins.bin(7.0), Some(1));
+        assert_eq!(bins.center(1), 5.0);
+        assert_eq!(bins.bounds(1), (2.5, 7.5));
+        assert_eq!(bins.label(5.0), "5");
+        assert_eq!(bins.label(7.0), "");
+    }
+
+    #[test]
+    fn histogram_bars_of_negative_keys_are_placed_at_their_keys() {
+        let bins = HistogramBins::new(None, None, 1, []);
+        for key in -3..=3 {
+            let bin = bins.bin(key as f64).unwrap();
+            assert_eq!(bins.center(bin), key as f64);
+            assert_eq!(bins.bounds(bin), (key as f64 - 0.5, key as f64 + 0.5));
+            assert_eq!(bins.label(key as f64), key.to_string());
+        }
+        assert_eq!(bins.label(-0.0), "0");
+        assert_eq!(format_value(&-3.0), "-3");
+    }
+
+    #[test]
+    fn run_set_missing_a_seed_is_reported() {
+        let statistics_files: Vec<_> = (0..10)
+            .filter(|&seed| seed != 7)
+            .map(|seed| test_file(seed, "alpha"))
+            .collect();
+        let incomplete_run_sets =
+            check_expected_seeds(&statistics_files, &"0..10".parse().unwrap(), &[]);
+        assert_eq!(incomplete_run_sets.len(), 1);
+        assert_eq!(incomplete_run_sets[0].1.missing, [7]);
+
+        // Only the first seven files have exactly the seeds 0..7.
+        let complete = check_expected_seeds(&statistics_files[..7], &"0..7".parse().unwrap(), &[]);
+        assert!(complete.is_empty());
+    }
+
+    #[test]
+    fn long_labels_are_truncated_with_an_ellipsis() {
+        let label = group_label("tsalign anti-diagonal", Some(8));
+        assert_eq!(label, "tsalign…");
+        assert_eq!(label.chars().count(), 8);
+        assert_eq!(group_label("tsalign", Some(7)), "tsalign");
+        assert_eq!(
+            group_label("tsalign anti-diagonal", None),
+            "tsalign anti-diagonal"
+        );
+        // Characters are counted rather than bytes.
+        assert_eq!(group_label("äöüäöü", Some(4)), "äöü…");
+    }
+
+    #[test]
+    fn baseline_boxes_are_solid_and_current_boxes_hatched() {
+        let directory = test_directory("baseline-boxes");
+        let cli = Cli::parse_from([
+            "template-switch-statistics".as_ref(),
+            "-o".as_ref(),
+            directory.as_os_str(),
+        ]);
+        let render_options = RenderOptions::new(&cli, Theme::default());
+        let files: Vec<_> = ["a", "b"]
+            .into_iter()
+            .flat_map(|group| {
+                (0..3).map(move |seed| {
+                    let mut file = test_file(seed, group);
+                    file.statistics.statistics.opened_nodes = R64::new(100.0 * (seed + 1) as f64);
+                    file
+                })
+            })
+            .collect();
+        let prepare = || {
+            prepare_grouped_plot(
+                &files,
+                None,
+                KeyBucketScale::Linear,
+                &[],
+                None,
+                false,
+                |parameters| parameters.length as f64,
+                |file| file.parameters.extra["host"].clone(),
+                |file| run_set(file, &[]),
+                |file| file.statistics.statistics.opened_nodes.raw(),
+            )
+        };
+        let (data, baseline) = (prepare(), prepare());
+        grouped_linear_bar_plot(
+            &directory,
+            "baseline",
+            "Length",
+            "Opened Nodes",
+            (400, 300),
+            AxisTransform::Linear,
+            &render_options,
+            &data,
+            Some(&baseline),
+            |file| file.statistics.statistics.opened_nodes.raw(),
+        )
+        .unwrap();
+
+        let svg = std::fs::read_to_string(directory.join("baseline.svg")).unwrap();
+        let colors = Theme::default().group_colors(2);
+        for (group, color) in ["a", "b"].into_iter().zip(colors) {
+            let color = format!("#{:02X}{:02X}{:02X}", color.0, color.1, color.2);
+            let elements: Vec<_> = svg
+                .lines()
+                .filter(|line| line.contains(&format!("\"{color}\"")))
+                .collect();
+            assert!(
+                elements
+                    .iter()
+                    .any(|element| element.starts_with("<rect")
+                        && element.contains("opacity=\"0.3\"")),
+                "no solid baseline box for group {group}"
+            );
+            let is_diagonal = |element: &&&str| {
+                let points = element.split("points=\"").nth(1).unwrap_or("");
+                let points: Vec<Vec<&str>> = points
+                    .split_whitespace()
+                    .take_while(|point| point.contains(','))
+                    .map(|point| point.split(',').collect())
+                    .collect();
+                points.len() == 2 && points[0][0] != points[1][0] && points[0][1] != points[1][1]
+            };
+            assert_eq!(
+                elements
+                    .iter()
+                    .filter(|element| element.starts_with("<polyline"))
+                    .filter(is_diagonal)
+                    .count(),
+                // The hatch lines of the single box of the group.
+                7,
+                "no hatched box for group {group}"
+            );
+        }
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn parsed_files_keep_the_order_of_their_paths() {
+        let directory = test_directory("parse-order");
+        let paths: Vec<_> = (0..50)
+            .map(|seed| {
+                let path = directory.join(format!("statistics-{seed}.toml"));
+                std::fs::write(
+                    &path,
+                    include_str!("../test_files/statistics-0.toml")
+                        .replace("seed = 0\n", &format!("seed = {seed}\n")),
+                )
+                .unwrap();
+                path
+            })
+            .collect();
+
+        let seeds: Vec<_> = parse_statistics_files(&paths, None)
+            .into_iter()
+            .map(|result| result.ok().unwrap().parameters.seed)
+            .collect();
+        assert_eq!(seeds, (0..50).collect::<Vec<_>>());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn plot_errors_name_the_plot_and_its_file() {
+        let directory = test_directory("missing-output-directory");
+        let missing_directory = directory.join("missing");
+        let cli = Cli::parse_from([
+            "template-switch-statistics".as_ref(),
+            "-o".as_ref(),
+            missing_directory.as_os_str(),
+        ]);
+        let render_options = RenderOptions::new(&cli, Theme::default());
+        let files: Vec<_> = (0..3).map(|seed| test_file(seed, "a")).collect();
+        let data = prepare_grouped_plot(
+            &files,
+            None,
+            KeyBucketScale::Linear,
+            &[],
+            None,
+            false,
+            |parameters| parameters.length as f64,
+            |file| file.parameters.extra["host"].clone(),
+            |file| run_set(file, &[]),
+            |file| file.statistics.statistics.opened_nodes.raw(),
+        );
+
+        let error = grouped_linear_bar_plot(
+            &missing_directory,
+            "missing",
+            "Length",
+            "Opened Nodes",
+            (400, 300),
+            AxisTransform::Linear,
+            &render_options,
+            &data,
+            None,
+            |file| file.statistics.statistics.opened_nodes.raw(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "plot missing to {:?}",
+                missing_directory.join("missing.svg")
+            )
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn format_value_at_prefix_boundaries() {
+        for (value, expected) in [
+            (0.0, "0"),
+            (-0.0, "0"),
+            (0.5, "0.5"),
+            (0.1234, "0.123"),
+            (0.00012, "1.20e-4"),
+            (999.0, "999"),
+            (999.6, "1.00k"),
+            (1e3, "1.00k"),
+            (9999.7, "10.0k"),
+            (99_999.0, "100k"),
+            (999_999.0, "1.00M"),
+            (-1e3, "-1.00k"),
+            (1e6, "1.00M"),
+            (1e9, "1.00G"),
+            (1e12, "1.00T"),
+            (1e15, "1.00P"),
+            (1e18, "1.00e18"),
+            (2.5e21, "2.50e21"),
+        ] {
+            assert_eq!(format_value(&value), expected, "{value}");
+        }
     }
 }
@@ -0,0 +1,332 @@
+use std::{fmt::Display, str::FromStr};
+
+use clap::ValueEnum;
+use lib_tsalign::a_star_aligner::template_switch_distance::AlignmentType;
+use log::Level;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    diagnostics::{self, DiagnosticCategory},
+    statistics_file::StatisticsFile,
+};
+
+/// A plottable value of a statistics file.
+///
+/// Metrics are named in snake case, but the kebab case of their names is accepted as well,
+/// as are the short names `runtime` and `memory`.
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum Metric {
+    Cost,
+    #[serde(alias = "cost-per-base")]
+    #[value(alias = "cost-per-base")]
+    CostPerBase,
+    #[serde(alias = "duration-seconds", alias = "runtime")]
+    #[value(aliases = ["duration-seconds", "runtime"])]
+    DurationSeconds,
+    #[serde(alias = "opened-nodes")]
+    #[value(alias = "opened-nodes")]
+    OpenedNodes,
+    #[serde(alias = "closed-nodes")]
+    #[value(alias = "closed-nodes")]
+    ClosedNodes,
+    #[serde(alias = "suboptimal-opened-nodes")]
+    #[value(alias = "suboptimal-opened-nodes")]
+    SuboptimalOpenedNodes,
+    #[serde(alias = "suboptimal-opened-nodes-ratio")]
+    #[value(alias = "suboptimal-opened-nodes-ratio")]
+    SuboptimalOpenedNodesRatio,
+    #[serde(alias = "template-switch-amount")]
+    #[value(alias = "template-switch-amount")]
+    TemplateSwitchAmount,
+    /// The amount of template switches per base of sequence length.
+    #[serde(alias = "ts-density")]
+    #[value(alias = "ts-density")]
+    TsDensity,
+    /// The peak memory usage in bytes.
+    #[serde(alias = "memory-bytes", alias = "memory")]
+    #[value(aliases = ["memory-bytes", "memory"])]
+    MemoryBytes,
+    /// The peak memory usage in bytes per opened node.
+    #[serde(alias = "memory-per-node")]
+    #[value(alias = "memory-per-node")]
+    MemoryPerNode,
+    /// The amount of opened nodes per byte of peak memory usage.
+    #[serde(alias = "nodes-per-memory")]
+    #[value(alias = "nodes-per-memory")]
+    NodesPerMemory,
+    /// The amount of matches in the alignment, including those inside template switches.
+    #[serde(rename = "op:match")]
+    #[value(name = "op:match")]
+    OpMatch,
+    /// The amount of substitutions in the alignment, including those inside template switches.
+    #[serde(rename = "op:substitution")]
+    #[value(name = "op:substitution")]
+    OpSubstitution,
+    /// The amount of inserted characters in the alignment, including those inside template switches.
+    #[serde(rename = "op:insertion")]
+    #[value(name = "op:insertion")]
+    OpInsertion,
+    /// The amount of deleted characters in the alignment, including those inside template switches.
+    #[serde(rename = "op:deletion")]
+    #[value(name = "op:deletion")]
+    OpDeletion,
+}
+
+impl Metric {
+    pub fn value(&self, file: &StatisticsFile) -> f64 {
+        let statistics = &file.statistics.statistics;
+        match self {
+            Metric::Cost => statistics.cost.raw(),
+            Metric::CostPerBase => statistics.cost_per_base.raw(),
+            Metric::DurationSeconds => statistics.duration_seconds.raw(),
+            Metric::OpenedNodes => statistics.opened_nodes.raw(),
+            Metric::ClosedNodes => statistics.closed_nodes.raw(),
+            Metric::SuboptimalOpenedNodes => statistics.suboptimal_opened_nodes.raw(),
+            Metric::SuboptimalOpenedNodesRatio => statistics.suboptimal_opened_nodes_ratio.raw(),
+            Metric::TemplateSwitchAmount => file.template_switch_amount() as f64,
+            Metric::TsDensity => {
+                if file.parameters.length == 0 {
+                    // Non-finite values are skipped when plotting.
+                    f64::NAN
+                } else {
+                    file.template_switch_amount() as f64 / file.parameters.length as f64
+                }
+            }
+            Metric::MemoryBytes => file
+                .memory_bytes
+                // Non-finite values are skipped when plotting.
+                .map_or(f64::NAN, |memory_bytes| memory_bytes as f64),
+            Metric::MemoryPerNode => ratio(
+                file.memory_bytes.map(|memory_bytes| memory_bytes as f64),
+                statistics.opened_nodes.raw(),
+            ),
+            Metric::NodesPerMemory => ratio(
+                Some(statistics.opened_nodes.raw()),
+                file.memory_bytes
+                    .map(|memory_bytes| memory_bytes as f64)
+                    .unwrap_or(0.0),
+            ),
+            Metric::OpMatch => file.operation_count(|alignment_type| {
+                matches!(
+                    alignment_type,
+                    AlignmentType::Match | AlignmentType::SecondaryMatch
+                )
+            }) as f64,
+            Metric::OpSubstitution => file.operation_count(|alignment_type| {
+                matches!(
+                    alignment_type,
+                    AlignmentType::Substitution | AlignmentType::SecondarySubstitution
+                )
+            }) as f64,
+            Metric::OpInsertion => file.operation_count(|alignment_type| {
+                matches!(
+                    alignment_type,
+                    AlignmentType::Insertion | AlignmentType::SecondaryInsertion
+                )
+            }) as f64,
+            Metric::OpDeletion => file.operation_count(|alignment_type| {
+                matches!(
+                    alignment_type,
+                    AlignmentType::Deletion | AlignmentType::SecondaryDeletion
+                )
+            }) as f64,
+        }
+    }
+}
+
+/// Divide `numerator` by `denominator`, or return NaN if the numerator is missing or the denominator is zero.
+/// Non-finite values are skipped when plotting.
+fn ratio(numerator: Option<f64>, denominator: f64) -> f64 {
+    match numerator {
+        Some(numerator) if denominator != 0.0 => numerator / denominator,
+        _ => f64::NAN,
+    }
+}
+
+impl Metric {
+    /// Warn if the metric is zero or missing for all given files,
+    /// since then it most likely does not apply to the aligners that produced them.
+    pub fn check_applicability(&self, files: &[StatisticsFile]) {
+        let applicable = files.iter().any(|file| {
+            let value = self.value(file);
+            value.is_finite() && value != 0.0
+        });
+
+        if !applicable && !files.is_empty() {
+            diagnostics::report(
+                Level::Warn,
+                DiagnosticCategory::InapplicableMetric,
+                format!(
+                    "Metric {} is zero or missing in all files, it may not apply to the selected aligners",
+                    self.identifier()
+                ),
+                [
+                    ("metric", self.identifier()),
+                    ("files", files.len().to_string()),
+                ],
+            );
+        }
+    }
+
+    /// Parse a metric from its name or one of its aliases.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        Self::from_str(name, false).map_err(|_| {
+            format!(
+                "Unknown metric {name:?}, the supported metrics are {}",
+                Self::value_variants()
+                    .iter()
+                    .map(Self::identifier)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+    }
+
+    /// The name of the metric, as used in recipes and file names.
+    pub fn identifier(&self) -> String {
+        self.to_possible_value().unwrap().get_name().to_string()
+    }
+
+    /// The human-readable name of the metric, without its unit.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Metric::Cost => "Alignment Cost",
+            Metric::CostPerBase => "Alignment Cost per Base",
+            Metric::DurationSeconds => "Runtime",
+            Metric::OpenedNodes => "Opened Nodes",
+            Metric::ClosedNodes => "Closed Nodes",
+            Metric::SuboptimalOpenedNodes => "Suboptimal Opened Nodes",
+            Metric::SuboptimalOpenedNodesRatio => "Suboptimal Opened Nodes Ratio",
+            Metric::TemplateSwitchAmount => "Template Switches",
+            Metric::TsDensity => "Template Switches per Base",
+            Metric::MemoryBytes => "Peak Memory",
+            Metric::MemoryPerNode => "Memory per Opened Node",
+            Metric::NodesPerMemory => "Opened Nodes per Byte",
+            Metric::OpMatch => "Matches",
+            Metric::OpSubstitution => "Substitutions",
+            Metric::OpInsertion => "Insertions",
+            Metric::OpDeletion => "Deletions",
+        }
+    }
+
+    /// The unit of the metric, if it has one.
+    pub fn unit(&self) -> Option<&'static str> {
+        match self {
+            Metric::DurationSeconds => Some("s"),
+            Metric::MemoryBytes | Metric::MemoryPerNode => Some("B"),
+            _ => None,
+        }
+    }
+
+    /// True if the metric is an amount of bytes, which can be scaled to a larger unit with [`ByteUnit`](crate::byte_unit::ByteUnit).
+    pub fn is_bytes(&self) -> bool {
+        self.unit() == Some("B")
+    }
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.unit() {
+            Some(unit) => write!(f, "{} [{unit}]", self.name()),
+            None => write!(f, "{}", self.name()),
+        }
+    }
+}
+
+/// Two metrics to be compared with each other, given as `x=<metric>,y=<metric>`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct MetricPair {
+    pub x: Metric,
+    pub y: Metric,
+}
+
+impl FromStr for MetricPair {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let (mut x, mut y) = (None, None);
+        for assignment in source.split(',') {
+            let (axis, metric) = assignment.split_once('=').ok_or_else(|| {
+                format!("Metric pairs must be given as x=<metric>,y=<metric>, but found {source:?}")
+            })?;
+            let metric = Some(Metric::parse(metric.trim())?);
+            match axis.trim() {
+                "x" => x = metric,
+                "y" => y = metric,
+                axis => return Err(format!("Unknown axis {axis:?} in metric pair {source:?}")),
+            }
+        }
+
+        match (x, y) {
+            (Some(x), Some(y)) => Ok(Self { x, y }),
+            _ => Err(format!(
+                "Metric pairs must contain both x and y, but found {source:?}"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_file() -> StatisticsFile {
+        StatisticsFile::from_toml_str(include_str!("../test_files/statistics-0.toml")).unwrap()
+    }
+
+    #[test]
+    fn metrics_are_parsed_from_names_and_aliases() {
+        assert_eq!(Metric::parse("opened_nodes"), Ok(Metric::OpenedNodes));
+        assert_eq!(Metric::parse("opened-nodes"), Ok(Metric::OpenedNodes));
+        assert_eq!(Metric::parse("runtime"), Ok(Metric::DurationSeconds));
+        assert_eq!(Metric::parse("memory"), Ok(Metric::MemoryBytes));
+        assert_eq!(Metric::parse("op:match"), Ok(Metric::OpMatch));
+        assert_eq!(Metric::OpMatch.identifier(), "op:match");
+        assert_eq!(Metric::TsDensity.identifier(), "ts_density");
+
+        let error = Metric::parse("speed").unwrap_err();
+        assert!(error.contains("\"speed\""), "{error}");
+        assert!(error.contains("opened_nodes"), "{error}");
+    }
+
+    #[test]
+    fn ts_density_divides_by_the_sequence_length() {
+        let mut file = test_file();
+        file.parameters.length = 100;
+        assert_eq!(
+            Metric::TsDensity.value(&file),
+            Metric::TemplateSwitchAmount.value(&file) / 100.0
+        );
+
+        file.parameters.length = 0;
+        assert!(Metric::TsDensity.value(&file).is_nan());
+    }
+
+    #[test]
+    fn missing_memory_is_not_a_number() {
+        let mut file = test_file();
+        file.memory_bytes = None;
+        assert!(Metric::MemoryBytes.value(&file).is_nan());
+        assert!(Metric::MemoryPerNode.value(&file).is_nan());
+
+        file.memory_bytes = Some(1000);
+        assert_eq!(Metric::MemoryBytes.value(&file), 1000.0);
+    }
+
+    #[test]
+    fn metric_pairs_require_both_axes() {
+        assert_eq!(
+            "x=cost, y=runtime".parse::<MetricPair>(),
+            Ok(MetricPair {
+                x: Metric::Cost,
+                y: Metric::DurationSeconds,
+            })
+        );
+        assert!("x=cost".parse::<MetricPair>().is_err());
+        assert!("z=cost,y=cost".parse::<MetricPair>().is_err());
+    }
+}
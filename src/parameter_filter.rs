@@ -0,0 +1,143 @@
+use std::{collections::BTreeSet, str::FromStr};
+
+use crate::statistics_file::{AlignmentParameterName, AlignmentParameters};
+
+/// Keep only the files whose parameter, strategy or metadata field `key` has the given value, given as `key=value`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterFilter {
+    key: String,
+    value: String,
+}
+
+impl ParameterFilter {
+    /// The name of the filtered field.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// True if the filtered field of the given parameters exists and has the value of the filter.
+    pub fn matches(&self, parameters: &AlignmentParameters) -> bool {
+        let value = match AlignmentParameterName::ALL
+            .into_iter()
+            .find(|name| name.identifier() == self.key)
+        {
+            Some(name) => Some(name.value(parameters)),
+            None => parameters
+                .strategies
+                .get(&self.key)
+                .or_else(|| parameters.extra.get(&self.key))
+                .cloned(),
+        };
+        value.is_some_and(|value| value == self.value)
+    }
+
+    /// The names of the fields that can be filtered by in the given parameters.
+    pub fn known_keys<'a>(
+        parameters: impl IntoIterator<Item = &'a AlignmentParameters>,
+    ) -> BTreeSet<String> {
+        let mut keys: BTreeSet<_> = AlignmentParameterName::ALL
+            .into_iter()
+            .map(|name| name.identifier().to_string())
+            .collect();
+        for parameters in parameters {
+            keys.extend(parameters.strategies.keys().cloned());
+            keys.extend(parameters.extra.keys().cloned());
+        }
+        keys
+    }
+
+    /// Check that every filter filters by one of the given known fields.
+    /// The error lists the known fields.
+    pub fn check_keys(filters: &[Self], known_keys: &BTreeSet<String>) -> Result<(), String> {
+        match filters
+            .iter()
+            .find(|filter| !known_keys.contains(filter.key()))
+        {
+            Some(filter) => Err(format!(
+                "Cannot filter by unknown field {:?}, the known fields are {}.",
+                filter.key(),
+                known_keys.iter().cloned().collect::<Vec<_>>().join(", ")
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for ParameterFilter {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let (key, value) = source
+            .split_once('=')
+            .ok_or_else(|| format!("Filter {source:?} is not of the form `key=value`"))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(format!("Filter {source:?} has no key"));
+        }
+        Ok(Self {
+            key: key.to_string(),
+            value: value.trim().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameters() -> AlignmentParameters {
+        let mut parameters = AlignmentParameters {
+            test_sequence_name: "single_ts_0".to_string(),
+            length: 1000,
+            ..Default::default()
+        };
+        parameters
+            .strategies
+            .insert("aligner".to_string(), "fpa".to_string());
+        parameters
+            .extra
+            .insert("host".to_string(), "alpha".to_string());
+        parameters
+    }
+
+    #[test]
+    fn filters_match_parameters_strategies_and_metadata() {
+        let matches = |filter: &str| {
+            filter
+                .parse::<ParameterFilter>()
+                .unwrap()
+                .matches(&parameters())
+        };
+        assert!(matches("length=1000"));
+        assert!(matches("aligner = fpa"));
+        assert!(matches("host=alpha"));
+        assert!(!matches("length=100"));
+        assert!(!matches("aligner=tsalign"));
+        assert!(!matches("missing=value"));
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected_with_the_known_keys() {
+        let known_keys = ParameterFilter::known_keys([&parameters()]);
+        let filters: Vec<ParameterFilter> = ["length=1000", "colour=red"]
+            .into_iter()
+            .map(|filter| filter.parse().unwrap())
+            .collect();
+        assert_eq!(
+            ParameterFilter::check_keys(&filters[..1], &known_keys),
+            Ok(())
+        );
+
+        let error = ParameterFilter::check_keys(&filters, &known_keys).unwrap_err();
+        assert!(error.contains("\"colour\""), "{error}");
+        for known_key in ["aligner", "host", "length", "seed"] {
+            assert!(error.contains(known_key), "{error}");
+        }
+    }
+
+    #[test]
+    fn malformed_filters_are_rejected() {
+        assert!("length".parse::<ParameterFilter>().is_err());
+        assert!("=1000".parse::<ParameterFilter>().is_err());
+    }
+}
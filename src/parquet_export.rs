@@ -0,0 +1,228 @@
+use std::{fs::File, path::Path, sync::Arc};
+
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, Float64Array, RecordBatch, StringArray, UInt64Array,
+};
+use arrow_schema::{Field, Schema};
+use log::info;
+use parquet::arrow::ArrowWriter;
+
+use crate::statistics_file::StatisticsFile;
+
+/// Write the given statistics files as a parquet file with one row per statistics file
+/// and one column per parameter and statistic.
+///
+/// If `drop_constant_columns` is set, columns that have the same value in all rows are omitted.
+pub fn write_statistics_parquet(
+    path: impl AsRef<Path>,
+    statistics_files: &[StatisticsFile],
+    drop_constant_columns: bool,
+) {
+    let path = path.as_ref();
+    info!(
+        "Writing {} statistics files to {path:?}",
+        statistics_files.len()
+    );
+
+    let strings = |field: fn(&StatisticsFile) -> Option<&str>| -> ArrayRef {
+        Arc::new(StringArray::from_iter(statistics_files.iter().map(field)))
+    };
+    let integers = |field: fn(&StatisticsFile) -> Option<u64>| -> ArrayRef {
+        Arc::new(UInt64Array::from_iter(statistics_files.iter().map(field)))
+    };
+    let floats = |field: fn(&StatisticsFile) -> f64| -> ArrayRef {
+        Arc::new(Float64Array::from_iter_values(
+            statistics_files.iter().map(field),
+        ))
+    };
+
+    let mut columns = vec![
+        (
+            "test_sequence_name",
+            strings(|file| Some(&file.parameters.test_sequence_name)),
+        ),
+        (
+            "length",
+            integers(|file| Some(file.parameters.length as u64)),
+        ),
+        ("seed", integers(|file| Some(file.parameters.seed))),
+        (
+            "alignment_config",
+            strings(|file| Some(&file.parameters.alignment_config)),
+        ),
+        (
+            "ts_node_ord_strategy",
+            strings(|file| Some(&file.parameters.ts_node_ord_strategy)),
+        ),
+        (
+            "timestamp",
+            strings(|file| file.parameters.timestamp.as_deref()),
+        ),
+        ("cost", floats(|file| file.statistics.statistics.cost.raw())),
+        (
+            "cost_per_base",
+            floats(|file| file.statistics.statistics.cost_per_base.raw()),
+        ),
+        (
+            "duration_seconds",
+            floats(|file| file.statistics.statistics.duration_seconds.raw()),
+        ),
+        (
+            "opened_nodes",
+            floats(|file| file.statistics.statistics.opened_nodes.raw()),
+        ),
+        (
+            "closed_nodes",
+            floats(|file| file.statistics.statistics.closed_nodes.raw()),
+        ),
+        (
+            "suboptimal_opened_nodes",
+            floats(|file| file.statistics.statistics.suboptimal_opened_nodes.raw()),
+        ),
+        (
+            "suboptimal_opened_nodes_ratio",
+            floats(|file| {
+                file.statistics
+                    .statistics
+                    .suboptimal_opened_nodes_ratio
+                    .raw()
+            }),
+        ),
+        (
+            "template_switch_amount",
+            integers(|file| Some(file.template_switch_amount() as u64)),
+        ),
+        (
+            "optimal",
+            Arc::new(BooleanArray::from_iter(
+                statistics_files.iter().map(|file| Some(file.optimal)),
+            )) as ArrayRef,
+        ),
+        ("memory_bytes", integers(|file| file.memory_bytes)),
+    ];
+    if drop_constant_columns && statistics_files.len() > 1 {
+        let (constant_columns, varying_columns) =
+            columns.into_iter().partition::<Vec<_>, _>(|(_, column)| {
+                let first = column.slice(0, 1).to_data();
+                (1..column.len()).all(|row| column.slice(row, 1).to_data() == first)
+            });
+        if !constant_columns.is_empty() {
+            info!(
+                "Dropping constant columns {:?}",
+                constant_columns
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+            );
+        }
+        columns = varying_columns;
+    }
+
+    let schema = Arc::new(Schema::new(
+        columns
+            .iter()
+            .map(|(name, column)| {
+                // Only the optional fields may be missing.
+                let nullable = matches!(*name, "timestamp" | "memory_bytes");
+                Field::new(*name, column.data_type().clone(), nullable)
+            })
+            .collect::<Vec<_>>(),
+    ));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        columns.into_iter().map(|(_, column)| column).collect(),
+    )
+    .unwrap_or_else(|error| panic!("Could not assemble parquet columns: {error}"));
+
+    let file = File::create(path)
+        .unwrap_or_else(|error| panic!("Could not create parquet file {path:?}: {error}"));
+    let mut writer = ArrowWriter::try_new(file, schema, None)
+        .unwrap_or_else(|error| panic!("Could not create parquet writer for {path:?}: {error}"));
+    writer
+        .write(&batch)
+        .unwrap_or_else(|error| panic!("Could not write parquet file {path:?}: {error}"));
+    writer
+        .close()
+        .unwrap_or_else(|error| panic!("Could not write parquet file {path:?}: {error}"));
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::UInt64Type;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+
+    #[test]
+    fn written_parquet_reads_back() {
+        let directory = std::env::temp_dir().join(format!(
+            "template-switch-statistics-parquet-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("statistics.parquet");
+
+        let file =
+            StatisticsFile::from_toml_str(include_str!("../test_files/statistics-0.toml")).unwrap();
+        let mut second = file.clone();
+        second.parameters.seed = 1;
+        second.memory_bytes = Some(1024);
+        write_statistics_parquet(&path, &[file, second], false);
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<_> = reader.map(Result::unwrap).collect();
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 2);
+
+        let seeds = batch
+            .column_by_name("seed")
+            .unwrap()
+            .as_primitive::<UInt64Type>();
+        assert_eq!(seeds.values().to_vec(), vec![0, 1]);
+        let memory_bytes = batch
+            .column_by_name("memory_bytes")
+            .unwrap()
+            .as_primitive::<UInt64Type>();
+        assert!(memory_bytes.is_null(0));
+        assert_eq!(memory_bytes.value(1), 1024);
+        let names = batch
+            .column_by_name("test_sequence_name")
+            .unwrap()
+            .as_string::<i32>();
+        assert_eq!(names.value(0), "single_ts_0");
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn constant_parquet_columns_are_dropped() {
+        let directory = std::env::temp_dir().join(format!(
+            "template-switch-statistics-parquet-constant-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("statistics.parquet");
+
+        let file =
+            StatisticsFile::from_toml_str(include_str!("../test_files/statistics-0.toml")).unwrap();
+        let mut second = file.clone();
+        second.parameters.seed = 1;
+        write_statistics_parquet(&path, &[file, second], true);
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap()).unwrap();
+        let fields: Vec<_> = builder
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+        assert_eq!(fields, vec!["seed".to_string()]);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
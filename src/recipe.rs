@@ -0,0 +1,253 @@
+use std::{fs::File, io::Read, path::Path};
+
+use log::info;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    axis_transform::{AxisTransform, AxisTransformKind},
+    group_size_report, grouped_linear_bar_plot,
+    key_bucket_scale::KeyBucketScale,
+    metric::Metric,
+    prepare_grouped_plot, split_by_seed_parity,
+    statistics_file::{AlignmentParameterName, AlignmentParameters, StatisticsFile},
+    GroupedPlotData, RenderOptions,
+};
+
+/// A list of plots to generate from the same statistics files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recipe {
+    #[serde(rename = "plot", default)]
+    pub plots: Vec<PlotSpec>,
+}
+
+/// The description of a single grouped plot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlotSpec {
+    /// The name of the plot, also used as output file name.
+    pub name: String,
+    /// The statistic that is plotted on the `y`-axis.
+    pub metric: Metric,
+    /// The parameter that is plotted on the `x`-axis. Must be numeric.
+    pub key: AlignmentParameterName,
+    /// The parameters whose values make up the group name.
+    pub group_by: Vec<AlignmentParameterName>,
+    /// Make the `y`-axis an n-th-root axis with `n = value_polynomial_degree`.
+    #[serde(default = "default_value_polynomial_degree")]
+    pub value_polynomial_degree: f64,
+    /// The transformation applied to the values before placing them on the `y`-axis.
+    #[serde(default)]
+    pub value_axis: AxisTransformKind,
+    /// The base of a logarithmic `y`-axis.
+    #[serde(default = "default_value_log_base")]
+    pub value_log_base: f64,
+    /// Split each group into the files with even and odd seeds.
+    #[serde(default)]
+    pub split_by_seed_parity: bool,
+    /// Bucket the experiments by their key.
+    #[serde(default)]
+    pub key_bucket_amount: Option<usize>,
+    /// How the boundaries of the key buckets are spaced.
+    #[serde(default)]
+    pub key_bucket_scale: KeyBucketScale,
+    /// Keep only this many groups, combining the remaining groups into a group named `other`.
+    #[serde(default)]
+    pub top_groups: Option<usize>,
+    #[serde(default = "default_size")]
+    pub size: (u32, u32),
+}
+
+fn default_value_polynomial_degree() -> f64 {
+    1.0
+}
+
+fn default_value_log_base() -> f64 {
+    10.0
+}
+
+fn default_size() -> (u32, u32) {
+    (400, 400)
+}
+
+impl Recipe {
+    pub fn from_file(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        info!("Loading recipe {path:?}");
+
+        let mut buffer = String::new();
+        File::open(path)
+            .unwrap_or_else(|error| panic!("Could not open recipe {path:?}: {error}"))
+            .read_to_string(&mut buffer)
+            .unwrap_or_else(|error| panic!("Could not read recipe {path:?}: {error}"));
+        let recipe: Self = toml::from_str(&buffer)
+            .unwrap_or_else(|error| panic!("Could not parse recipe {path:?}: {error}"));
+
+        for plot in &recipe.plots {
+            plot.validate();
+        }
+        recipe
+    }
+}
+
+impl PlotSpec {
+    fn validate(&self) {
+        if self.key.numeric_value(&Default::default()).is_none() {
+            panic!(
+                "Plot {}: the key {:?} is not a numeric parameter.",
+                self.name, self.key
+            );
+        }
+        if self.key_bucket_amount == Some(0) {
+            panic!(
+                "Plot {}: if set, key buckets must be at least one.",
+                self.name
+            );
+        }
+        if self.top_groups == Some(0) {
+            panic!(
+                "Plot {}: if set, the amount of top groups must be at least one.",
+                self.name
+            );
+        }
+        if !(self.value_polynomial_degree >= 1.0 && self.value_polynomial_degree.is_finite()) {
+            panic!(
+                "Plot {}: the value polynomial degree must be at least one.",
+                self.name
+            );
+        }
+        if !(self.value_log_base > 1.0 && self.value_log_base.is_finite()) {
+            panic!(
+                "Plot {}: the value log base must be larger than one.",
+                self.name
+            );
+        }
+    }
+
+    pub fn prepare(
+        &self,
+        statistics_files: &[StatisticsFile],
+        require_balanced_groups: bool,
+    ) -> GroupedPlotData<String> {
+        info!("Preparing plot {}", self.name);
+
+        prepare_grouped_plot(
+            statistics_files,
+            self.key_bucket_amount,
+            self.key_bucket_scale,
+            &[],
+            self.top_groups,
+            require_balanced_groups,
+            |parameters| self.key(parameters),
+            |file| self.group_name(file),
+            |file| self.merge_key(file),
+            |file| self.metric.value(file),
+        )
+    }
+
+    pub fn group_size_report(&self, statistics_files: &[StatisticsFile]) -> String {
+        group_size_report(
+            statistics_files,
+            self.key_bucket_amount,
+            self.key_bucket_scale,
+            |parameters| self.key(parameters),
+            |file| self.group_name(file),
+            |file| self.merge_key(file),
+        )
+    }
+
+    fn key(&self, parameters: &AlignmentParameters) -> f64 {
+        self.key.numeric_value(parameters).unwrap()
+    }
+
+    fn group_name(&self, file: &StatisticsFile) -> String {
+        let group_name = self
+            .group_by
+            .iter()
+            .map(|parameter| parameter.format_value(&file.parameters))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if self.split_by_seed_parity {
+            split_by_seed_parity(group_name, file)
+        } else {
+            group_name
+        }
+    }
+
+    fn merge_key(&self, file: &StatisticsFile) -> AlignmentParameters {
+        let mut parameters = file.parameters.clone();
+        parameters.seed = 0;
+        // The cost is a result of the alignment, so seeds that found alignments of different costs
+        // are repetitions of the same experiment, unless the cost is the key.
+        if self.key != AlignmentParameterName::Cost {
+            parameters.cost = 0;
+        }
+        parameters.timestamp = None;
+        // Recipes cannot group by sidecar metadata, so it only tells repetitions apart.
+        parameters.extra.clear();
+        parameters
+    }
+
+    pub fn render(
+        &self,
+        output_directory: impl AsRef<Path>,
+        render_options: &RenderOptions,
+        data: &GroupedPlotData<String>,
+        baseline: Option<&GroupedPlotData<String>>,
+    ) -> anyhow::Result<()> {
+        info!("Rendering plot {}", self.name);
+
+        grouped_linear_bar_plot(
+            output_directory,
+            &self.name,
+            self.key,
+            self.metric,
+            self.size,
+            AxisTransform::new(
+                self.value_axis,
+                self.value_polynomial_degree,
+                self.value_log_base,
+            ),
+            render_options,
+            data,
+            baseline,
+            |file| self.metric.value(file),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_file(seed: u64, cost: u64) -> StatisticsFile {
+        let mut file =
+            StatisticsFile::from_toml_str(include_str!("../test_files/statistics-0.toml")).unwrap();
+        file.parameters.seed = seed;
+        file.parameters.cost = cost;
+        file.parameters.timestamp = Some(format!("2024-05-01T12:00:0{seed}Z"));
+        file.parameters
+            .extra
+            .insert("host".to_string(), format!("host-{seed}"));
+        file
+    }
+
+    #[test]
+    fn repetitions_share_a_merge_key() {
+        let recipe: Recipe = toml::from_str(include_str!("../test_files/recipe.toml")).unwrap();
+        let plot = &recipe.plots[0];
+        assert_eq!(
+            plot.merge_key(&test_file(0, 3)),
+            plot.merge_key(&test_file(1, 5))
+        );
+    }
+
+    #[test]
+    fn cost_keys_are_kept_in_the_merge_key() {
+        let mut recipe: Recipe = toml::from_str(include_str!("../test_files/recipe.toml")).unwrap();
+        let plot = &mut recipe.plots[0];
+        plot.key = AlignmentParameterName::Cost;
+        assert_ne!(
+            plot.merge_key(&test_file(0, 3)),
+            plot.merge_key(&test_file(1, 5))
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use std::{fmt::Display, str::FromStr};
+
+/// A function of the key, drawn for reference next to the measured values, e.g. `n^2` or `n*log(n)`.
+///
+/// The grammar is tiny:
+/// ```text
+/// product := power ('*' power)*
+/// power   := atom ('^' number)?
+/// atom    := 'n' | number | 'log' '(' product ')' | '(' product ')'
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceCurve {
+    source: String,
+    expression: Expression,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expression {
+    Key,
+    Constant(f64),
+    Log(Box<Expression>),
+    Power(Box<Expression>, f64),
+    Product(Vec<Expression>),
+}
+
+impl ReferenceCurve {
+    pub fn evaluate(&self, key: f64) -> f64 {
+        self.expression.evaluate(key)
+    }
+
+    /// The factor `c` that minimises the squared error of `c * f(key)` to the given `(key, value)` points.
+    /// Points at which the curve or the value is not finite are ignored.
+    pub fn fit_scale(&self, points: impl IntoIterator<Item = (f64, f64)>) -> f64 {
+        let (numerator, denominator) = points
+            .into_iter()
+            .map(|(key, value)| (self.evaluate(key), value))
+            .filter(|(reference, value)| reference.is_finite() && value.is_finite())
+            .fold(
+                (0.0, 0.0),
+                |(numerator, denominator), (reference, value)| {
+                    (
+                        numerator + reference * value,
+                        denominator + reference * reference,
+                    )
+                },
+            );
+
+        if denominator > 0.0 {
+            numerator / denominator
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Expression {
+    fn evaluate(&self, key: f64) -> f64 {
+        match self {
+            Self::Key => key,
+            Self::Constant(constant) => *constant,
+            Self::Log(argument) => argument.evaluate(key).ln(),
+            Self::Power(base, exponent) => base.evaluate(key).powf(*exponent),
+            Self::Product(factors) => factors.iter().map(|factor| factor.evaluate(key)).product(),
+        }
+    }
+}
+
+impl FromStr for ReferenceCurve {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            source,
+            remaining: source,
+        };
+        let expression = parser.product()?;
+        parser.skip_whitespace();
+        if !parser.remaining.is_empty() {
+            return Err(parser.error("expected '*', '^' or end of input"));
+        }
+
+        Ok(Self {
+            source: source.trim().to_string(),
+            expression,
+        })
+    }
+}
+
+impl Display for ReferenceCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    remaining: &'a str,
+}
+
+impl Parser<'_> {
+    fn product(&mut self) -> Result<Expression, String> {
+        let mut factors = vec![self.power()?];
+        while self.consume("*") {
+            factors.push(self.power()?);
+        }
+
+        Ok(if factors.len() == 1 {
+            factors.pop().unwrap()
+        } else {
+            Expression::Product(factors)
+        })
+    }
+
+    fn power(&mut self) -> Result<Expression, String> {
+        let base = self.atom()?;
+        if self.consume("^") {
+            let exponent = self.number()?;
+            Ok(Expression::Power(Box::new(base), exponent))
+        } else {
+            Ok(base)
+        }
+    }
+
+    fn atom(&mut self) -> Result<Expression, String> {
+        if self.consume("log") {
+            if !self.consume("(") {
+                return Err(self.error("expected '(' after 'log'"));
+            }
+            let argument = self.product()?;
+            if !self.consume(")") {
+                return Err(self.error("expected ')'"));
+            }
+            Ok(Expression::Log(Box::new(argument)))
+        } else if self.consume("n") {
+            Ok(Expression::Key)
+        } else if self.consume("(") {
+            let expression = self.product()?;
+            if !self.consume(")") {
+                return Err(self.error("expected ')'"));
+            }
+            Ok(expression)
+        } else {
+            self.number().map(Expression::Constant)
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        self.skip_whitespace();
+        let length = self
+            .remaining
+            .find(|character: char| !(character.is_ascii_digit() || character == '.'))
+            .unwrap_or(self.remaining.len());
+        let number = self.remaining[..length]
+            .parse()
+            .map_err(|_| self.error("expected 'n', 'log', '(' or a number"))?;
+        self.remaining = &self.remaining[length..];
+        Ok(number)
+    }
+
+    fn consume(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if let Some(remaining) = self.remaining.strip_prefix(token) {
+            self.remaining = remaining;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.remaining = self.remaining.trim_start();
+    }
+
+    fn error(&self, message: &str) -> String {
+        let position = self.source.len() - self.remaining.len();
+        format!(
+            "Could not parse reference curve {:?} at position {position}: {message}",
+            self.source
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quadratic_curve_is_fitted_to_quadratic_data() {
+        let curve: ReferenceCurve = "n^2".parse().unwrap();
+        assert_eq!(curve.evaluate(3.0), 9.0);
+
+        let points: Vec<_> = [1.0, 2.0, 4.0, 8.0]
+            .into_iter()
+            .map(|key| (key, 2.5 * key * key))
+            .collect();
+        let scale = curve.fit_scale(points.iter().copied());
+        assert!((scale - 2.5).abs() < 1e-12, "{scale}");
+        for (key, value) in points {
+            assert!((scale * curve.evaluate(key) - value).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn products_logarithms_and_parentheses_are_parsed() {
+        let curve: ReferenceCurve = " 2 * n * log(n) ".parse().unwrap();
+        assert_eq!(curve.to_string(), "2 * n * log(n)");
+        assert!((curve.evaluate(std::f64::consts::E) - 2.0 * std::f64::consts::E).abs() < 1e-12);
+
+        let curve: ReferenceCurve = "(n*n)^0.5".parse().unwrap();
+        assert!((curve.evaluate(7.0) - 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn invalid_expressions_are_rejected() {
+        for source in ["", "n^", "n +", "log(n", "m"] {
+            assert!(source.parse::<ReferenceCurve>().is_err(), "{source:?}");
+        }
+    }
+
+    #[test]
+    fn non_finite_points_are_ignored_when_fitting() {
+        let curve: ReferenceCurve = "log(n)".parse().unwrap();
+        // The logarithm of zero is not finite.
+        let scale = curve.fit_scale([(0.0, 1.0), (std::f64::consts::E, 3.0), (1.0, f64::NAN)]);
+        assert!((scale - 3.0).abs() < 1e-12);
+        assert_eq!(curve.fit_scale([(1.0, 1.0)]), 0.0);
+    }
+}
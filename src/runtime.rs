@@ -0,0 +1,86 @@
+/// Parse a runtime of the form `mm:ss`, `hh:mm:ss` or `d-hh:mm:ss` into seconds,
+/// as written e.g. by `/usr/bin/time` or job schedulers.
+/// The seconds may have a fractional part, as in `01:02:03.5`.
+pub fn parse_runtime(runtime: &str) -> Result<f64, String> {
+    let (days, clock) = match runtime.split_once('-') {
+        Some((days, clock)) => (Some(days), clock),
+        None => (None, runtime),
+    };
+    let components: Vec<_> = clock.split(':').collect();
+    let (hours, minutes, seconds) = match components.as_slice() {
+        [minutes, seconds] => (None, minutes, seconds),
+        [hours, minutes, seconds] => (Some(hours), minutes, seconds),
+        _ => {
+            return Err(format!(
+                "Runtime {runtime:?} is not of the form `mm:ss`, `hh:mm:ss` or `d-hh:mm:ss`"
+            ))
+        }
+    };
+    if days.is_some() && hours.is_none() {
+        return Err(format!(
+            "Runtime {runtime:?} has days but no hours, expected `d-hh:mm:ss`"
+        ));
+    }
+
+    let integer = |component: &str, name: &str| {
+        component
+            .parse::<u64>()
+            .map(|value| value as f64)
+            .map_err(|_| {
+                format!("The {name} {component:?} of runtime {runtime:?} are not a non-negative integer")
+            })
+    };
+    let seconds = seconds
+        .parse::<f64>()
+        .ok()
+        .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+        .ok_or_else(|| {
+            format!("The seconds {seconds:?} of runtime {runtime:?} are not a non-negative number")
+        })?;
+    let days = days.map_or(Ok(0.0), |days| integer(days, "days"))?;
+    let hours = hours.map_or(Ok(0.0), |hours| integer(hours, "hours"))?;
+    let minutes = integer(minutes, "minutes")?;
+    Ok(((days * 24.0 + hours) * 60.0 + minutes) * 60.0 + seconds)
+}
+
+/// Parse an amount of seconds, given either as plain number or as runtime accepted by [`parse_runtime`].
+pub fn parse_seconds(source: &str) -> Result<f64, String> {
+    if source.contains(':') {
+        parse_runtime(source)
+    } else {
+        source
+            .parse::<f64>()
+            .map_err(|error| format!("{source:?} is neither a number nor a runtime: {error}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_without_minutes_is_rejected() {
+        assert!(parse_runtime("90").is_err());
+        assert!(parse_runtime("1-02:03").is_err());
+        assert!(parse_runtime("1:-2").is_err());
+    }
+
+    #[test]
+    fn runtimes_are_parsed() {
+        for (runtime, seconds) in [
+            ("1:30", 90.0),
+            ("1:02:03", 3723.0),
+            ("2-03:04:05", 183845.0),
+            ("0:00.25", 0.25),
+        ] {
+            assert_eq!(parse_runtime(runtime), Ok(seconds), "{runtime}");
+        }
+    }
+
+    #[test]
+    fn seconds_are_plain_numbers_or_runtimes() {
+        assert_eq!(parse_seconds("90"), Ok(90.0));
+        assert_eq!(parse_seconds("1:30"), Ok(90.0));
+        assert!(parse_seconds("ninety").is_err());
+    }
+}
@@ -0,0 +1,69 @@
+/// Select at most `max_amount` items uniformly at random, preserving their order.
+///
+/// The selection is deterministic for a given `seed`.
+pub fn subsample<T>(items: &[T], max_amount: usize, seed: u64) -> Vec<&T> {
+    if items.len() <= max_amount {
+        return items.iter().collect();
+    }
+
+    // Partial Fisher-Yates shuffle of the indices.
+    let mut random = SplitMix64(seed);
+    let mut indices: Vec<_> = (0..items.len()).collect();
+    for i in 0..max_amount {
+        let j = i + (random.next() % (items.len() - i) as u64) as usize;
+        indices.swap(i, j);
+    }
+
+    let mut indices = indices[..max_amount].to_vec();
+    indices.sort_unstable();
+    indices.into_iter().map(|index| &items[index]).collect()
+}
+
+/// A pseudo-random offset in `[-0.5, 0.5)`, e.g. to spread overlapping points.
+///
+/// The offset is deterministic for a given `seed`.
+pub fn jitter(seed: u64) -> f64 {
+    (SplitMix64(seed).next() >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+}
+
+/// A small pseudo-random number generator, sufficient for reproducible subsampling.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subsample_is_capped_ordered_and_deterministic() {
+        let items: Vec<_> = (0..1000).collect();
+        let sample = subsample(&items, 10, 42);
+        assert_eq!(sample.len(), 10);
+        assert!(sample.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(sample, subsample(&items, 10, 42));
+        assert_ne!(sample, subsample(&items, 10, 43));
+    }
+
+    #[test]
+    fn small_inputs_are_kept_entirely() {
+        let items = [3, 1, 2];
+        assert_eq!(subsample(&items, 3, 0), [&3, &1, &2]);
+        assert_eq!(subsample(&items, 5, 0).len(), 3);
+    }
+
+    #[test]
+    fn jitter_stays_within_half_a_unit() {
+        for seed in 0..1000 {
+            assert!((-0.5..0.5).contains(&jitter(seed)));
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::stats;
+
+/// Samples with fewer values are not tested, since the test would be meaningless for them.
+pub const MIN_SAMPLE_SIZE: usize = 3;
+
+/// The significance of the difference between two samples, as annotated in plots.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Significance {
+    /// `p >= 0.05`, or too few values to tell.
+    NotSignificant,
+    /// `p < 0.05`.
+    Significant,
+    /// `p < 0.01`.
+    HighlySignificant,
+}
+
+impl Significance {
+    pub fn from_p_value(p_value: f64) -> Self {
+        if p_value < 0.01 {
+            Self::HighlySignificant
+        } else if p_value < 0.05 {
+            Self::Significant
+        } else {
+            Self::NotSignificant
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::NotSignificant => "ns",
+            Self::Significant => "*",
+            Self::HighlySignificant => "**",
+        }
+    }
+}
+
+/// The two-sided p-value of the Mann-Whitney U test of whether the two samples come from the same distribution.
+/// The p-value is computed from the normal approximation of `U`, with corrections for ties and continuity.
+///
+/// Returns `None` if a sample has fewer than [`MIN_SAMPLE_SIZE`] values.
+pub fn mann_whitney_u_test(a: &[f64], b: &[f64]) -> Option<f64> {
+    if a.len() < MIN_SAMPLE_SIZE || b.len() < MIN_SAMPLE_SIZE {
+        return None;
+    }
+
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    let n = n1 + n2;
+    let mut values: Vec<_> = a.iter().chain(b).copied().collect();
+    let ranks = stats::ranks(values.iter().copied());
+    let rank_sum_a: f64 = ranks[..a.len()].iter().sum();
+    let u = rank_sum_a - n1 * (n1 + 1.0) * 0.5;
+
+    values.sort_by(f64::total_cmp);
+    let tie_sum: f64 = values
+        .chunk_by(|x, y| x == y)
+        .map(|ties| {
+            let t = ties.len() as f64;
+            t * t * t - t
+        })
+        .sum();
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - tie_sum / (n * (n - 1.0)));
+    if variance <= 0.0 {
+        // All values are equal.
+        return Some(1.0);
+    }
+
+    let z = ((u - n1 * n2 * 0.5).abs() - 0.5).max(0.0) / variance.sqrt();
+    Some(erfc(z / std::f64::consts::SQRT_2).min(1.0))
+}
+
+/// The complementary error function, with a relative error below `1.2e-7` (Numerical Recipes, section 6.2).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let polynomial = [
+        -1.26551223,
+        1.00002368,
+        0.37409196,
+        0.09678418,
+        -0.18628806,
+        0.27886807,
+        -1.13520398,
+        1.48851587,
+        -0.82215223,
+        0.17087277,
+    ]
+    .iter()
+    .rev()
+    .fold(0.0, |accumulator, coefficient| {
+        coefficient + t * accumulator
+    });
+    let result = t * (-z * z + polynomial).exp();
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The reference p-values are those of R's `wilcox.test(a, b, exact = FALSE)`,
+    /// which equal those of SciPy's `mannwhitneyu(a, b, method = "asymptotic")`.
+    fn assert_p_value(a: &[f64], b: &[f64], expected: f64) {
+        let p_value = mann_whitney_u_test(a, b).unwrap();
+        assert!(
+            (p_value - expected).abs() < 1e-6,
+            "p-value {p_value} differs from {expected}"
+        );
+        // The test is symmetric.
+        let swapped = mann_whitney_u_test(b, a).unwrap();
+        assert!((p_value - swapped).abs() < 1e-12);
+    }
+
+    #[test]
+    fn p_value_without_ties() {
+        assert_p_value(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0], 0.0808556);
+        assert_p_value(
+            &[0.80, 0.83, 1.89, 1.04, 1.45, 1.38, 1.91, 1.64, 0.73, 1.46],
+            &[1.15, 0.88, 0.90, 0.74, 1.21],
+            0.2446236,
+        );
+    }
+
+    #[test]
+    fn p_value_with_ties() {
+        assert_p_value(
+            &[1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0],
+            &[3.0, 4.0, 4.0, 5.0, 5.0, 6.0],
+            0.0129999,
+        );
+    }
+
+    #[test]
+    fn too_small_samples_are_not_tested() {
+        assert_eq!(mann_whitney_u_test(&[1.0, 2.0], &[3.0, 4.0, 5.0]), None);
+        assert_eq!(mann_whitney_u_test(&[1.0, 2.0, 3.0], &[]), None);
+    }
+
+    #[test]
+    fn equal_values_are_not_significant() {
+        assert_eq!(mann_whitney_u_test(&[2.0; 4], &[2.0; 5]), Some(1.0));
+    }
+
+    #[test]
+    fn erfc_matches_reference_values() {
+        for (x, expected) in [
+            (0.0, 1.0),
+            (0.5, 0.4795001222),
+            (1.0, 0.1572992071),
+            (-1.0, 1.8427007929),
+            (2.0, 0.0046777350),
+        ] {
+            assert!(
+                ((erfc(x) - expected) / expected).abs() < 1.2e-7,
+                "erfc({x}) = {} differs from {expected}",
+                erfc(x)
+            );
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::{collections::BTreeMap, path::Path};
+
+use log::info;
+use rusqlite::{types::ValueRef, Connection};
+use serde_json::{Map, Value};
+
+use crate::statistics_file::StatisticsFile;
+
+/// Read statistics files from the rows returned by an SQL query.
+///
+/// Each column is mapped to the field of the statistics file with the same name,
+/// i.e. the columns are expected to be named like the keys of a statistics toml file
+/// (`cost`, `cost_per_base`, `duration_seconds`, ..., `test_sequence_name`, `length`, `seed`, ...).
+/// The `column_map` maps field names to differently named columns.
+///
+/// The column `alignment` is expected to contain the alignment as JSON text.
+/// If there is no such column, the alignment is empty.
+pub fn read_statistics_files(
+    path: impl AsRef<Path>,
+    query: &str,
+    column_map: &[(String, String)],
+) -> Vec<StatisticsFile> {
+    let path = path.as_ref();
+    info!("Reading statistics from database {path:?}");
+
+    let connection = Connection::open(path)
+        .unwrap_or_else(|error| panic!("Could not open database {path:?}: {error}"));
+    let mut statement = connection
+        .prepare(query)
+        .unwrap_or_else(|error| panic!("Could not prepare query {query:?}: {error}"));
+
+    let field_names: Vec<_> = statement
+        .column_names()
+        .into_iter()
+        .map(|column_name| {
+            column_map
+                .iter()
+                .find(|(_, column)| column == column_name)
+                .map(|(field, _)| field.clone())
+                .unwrap_or_else(|| column_name.to_string())
+        })
+        .collect();
+
+    let mut rows = statement
+        .query([])
+        .unwrap_or_else(|error| panic!("Could not execute query {query:?}: {error}"));
+    let mut statistics_files = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .unwrap_or_else(|error| panic!("Could not read row from database {path:?}: {error}"))
+    {
+        let mut fields: BTreeMap<_, _> = field_names
+            .iter()
+            .enumerate()
+            .map(|(index, field_name)| {
+                let value = row.get_ref(index).unwrap_or_else(|error| {
+                    panic!("Could not read column {field_name} from database {path:?}: {error}")
+                });
+                (field_name.clone(), json_value(field_name, value))
+            })
+            .collect();
+        fields
+            .entry("alignment".to_string())
+            .or_insert_with(|| Value::Array(Vec::new()));
+
+        let row_index = statistics_files.len();
+        let statistics_file: StatisticsFile =
+            serde_json::from_value(Value::Object(fields.into_iter().collect::<Map<_, _>>()))
+                .unwrap_or_else(|error| {
+                    panic!("Could not parse row {row_index} of database {path:?}: {error}")
+                });
+        statistics_files.push(statistics_file.deserialisation_post_processing());
+    }
+
+    info!("Read {} rows", statistics_files.len());
+    statistics_files
+}
+
+/// Parse a column mapping of the form `field=column`.
+pub fn parse_column_mapping(mapping: &str) -> Result<(String, String), String> {
+    let (field, column) = mapping.split_once('=').ok_or_else(|| {
+        format!("Column mappings must be given as field=column, but found {mapping:?}")
+    })?;
+    Ok((field.trim().to_string(), column.trim().to_string()))
+}
+
+fn json_value(field_name: &str, value: ValueRef) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(integer) => integer.into(),
+        ValueRef::Real(real) => real.into(),
+        ValueRef::Text(text) => {
+            let text = String::from_utf8_lossy(text);
+            if field_name == "alignment" {
+                serde_json::from_str(&text)
+                    .unwrap_or_else(|error| panic!("Could not parse alignment {text:?}: {error}"))
+            } else {
+                Value::String(text.into_owned())
+            }
+        }
+        ValueRef::Blob(_) => panic!("Column {field_name} contains a blob, which is not supported."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_are_read_as_statistics_files() {
+        let directory = std::env::temp_dir().join(format!(
+            "template-switch-statistics-sqlite-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("statistics.sqlite");
+
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE runs (
+                    test_sequence_name TEXT, length INTEGER, seed INTEGER,
+                    cost INTEGER, cost_per_base REAL, runtime REAL,
+                    opened_nodes INTEGER, closed_nodes INTEGER,
+                    suboptimal_opened_nodes INTEGER, suboptimal_opened_nodes_ratio REAL,
+                    alignment TEXT
+                );
+                INSERT INTO runs VALUES
+                    ('single_ts_0', 100, 0, 0, 0.0, 0.5, 2137, 711, 82, 0.04, '[[100, \"Match\"]]'),
+                    ('single_ts_0', 100, 1, 2, 0.02, 1.5, 3000, 900, 10, 0.01, '[]');",
+            )
+            .unwrap();
+
+        let statistics_files = read_statistics_files(
+            &path,
+            "SELECT * FROM runs ORDER BY seed",
+            &[parse_column_mapping("duration_seconds=runtime").unwrap()],
+        );
+        assert_eq!(statistics_files.len(), 2);
+        let [first, second] = &statistics_files[..] else {
+            unreachable!()
+        };
+        assert_eq!(first.parameters.seed, 0);
+        assert_eq!(first.statistics.statistics.duration_seconds.raw(), 0.5);
+        assert_eq!(first.statistics.alignment.len(), 1);
+        assert_eq!(second.parameters.seed, 1);
+        assert_eq!(second.parameters.cost, 2);
+        assert_eq!(second.statistics.statistics.opened_nodes.raw(), 3000.0);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn column_mappings_need_an_equals_sign() {
+        assert_eq!(
+            parse_column_mapping(" seed = run_seed "),
+            Ok(("seed".to_string(), "run_seed".to_string()))
+        );
+        assert!(parse_column_mapping("seed").is_err());
+    }
+}
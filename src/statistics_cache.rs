@@ -0,0 +1,175 @@
+use std::{
+    fs::File,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use log::{debug, info, Level};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    diagnostics::{self, DiagnosticCategory},
+    statistics_file::StatisticsFile,
+};
+
+/// A directory of parsed statistics files, such that repeated runs do not need to parse the same toml files again.
+///
+/// Each entry records the path, length and modification time of its statistics file,
+/// and is only used while the statistics file is unchanged.
+pub struct StatisticsCache {
+    directory: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    path: PathBuf,
+    length: u64,
+    modified: SystemTime,
+    statistics_file: StatisticsFile,
+}
+
+impl StatisticsCache {
+    pub fn new(directory: impl AsRef<Path>) -> Self {
+        let directory = directory.as_ref();
+        info!("Caching parsed statistics files in {directory:?}");
+        std::fs::create_dir_all(directory).unwrap_or_else(|error| {
+            panic!("Could not create cache directory {directory:?}: {error}")
+        });
+        Self {
+            directory: directory.to_path_buf(),
+        }
+    }
+
+    /// The cached statistics file at `path`, or `None` if there is no entry or it is stale or corrupt.
+    pub fn load(&self, path: &Path) -> Option<StatisticsFile> {
+        let path = path.canonicalize().ok()?;
+        let metadata = std::fs::metadata(&path).ok()?;
+        let modified = metadata.modified().ok()?;
+        let entry_path = self.entry_path(&path);
+        let file = File::open(&entry_path).ok()?;
+        let entry: CacheEntry = match serde_json::from_reader(BufReader::new(file)) {
+            Ok(entry) => entry,
+            Err(error) => {
+                debug!("Ignoring corrupt cache entry {entry_path:?}: {error}");
+                return None;
+            }
+        };
+
+        if entry.path != path || entry.length != metadata.len() || entry.modified != modified {
+            debug!("Ignoring stale cache entry {entry_path:?} of {path:?}");
+            return None;
+        }
+        // Fields that are not serialised, such as the parameter `cost`, are restored as after parsing.
+        Some(entry.statistics_file.deserialisation_post_processing())
+    }
+
+    /// Store the parsed statistics file at `path`.
+    /// Failing to store it is reported, but does not abort, since the cache only saves time.
+    pub fn store(&self, path: &Path, statistics_file: &StatisticsFile) {
+        let stored = (|| {
+            let path = path.canonicalize()?;
+            let metadata = std::fs::metadata(&path)?;
+            let entry_path = self.entry_path(&path);
+            let entry = CacheEntry {
+                path,
+                length: metadata.len(),
+                modified: metadata.modified()?,
+                statistics_file: statistics_file.clone(),
+            };
+            serde_json::to_writer(BufWriter::new(File::create(entry_path)?), &entry)?;
+            Ok::<_, Box<dyn std::error::Error>>(())
+        })();
+
+        if let Err(error) = stored {
+            diagnostics::report(
+                Level::Warn,
+                DiagnosticCategory::Cache,
+                "Could not cache statistics file",
+                [("file", format!("{path:?}")), ("error", error.to_string())],
+            );
+        }
+    }
+
+    /// Entries are named by a hash of the path of their statistics file.
+    /// Since the path is recorded in the entry, a collision only causes a fresh parse.
+    fn entry_path(&self, path: &Path) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        path.hash(&mut hasher);
+        self.directory
+            .join(format!("{:016x}.json", hasher.finish()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    const SOURCE: &str = include_str!("../test_files/statistics-0.toml");
+
+    /// A fresh directory containing a statistics file and a cache.
+    fn test_directory(name: &str) -> (PathBuf, StatisticsCache) {
+        let directory = std::env::temp_dir().join(format!(
+            "template-switch-statistics-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("statistics-0.toml"), SOURCE).unwrap();
+        let cache = StatisticsCache::new(directory.join("cache"));
+        (directory, cache)
+    }
+
+    fn set_modified(path: &Path, modified: SystemTime) {
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    #[test]
+    fn unchanged_file_is_loaded_from_cache() {
+        let (directory, cache) = test_directory("unchanged");
+        let path = directory.join("statistics-0.toml");
+        let statistics_file = StatisticsFile::from_toml_str(SOURCE).unwrap();
+
+        assert!(cache.load(&path).is_none());
+        cache.store(&path, &statistics_file);
+        let cached = cache.load(&path).unwrap();
+        assert_eq!(cached.parameters, statistics_file.parameters);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn touched_file_invalidates_its_entry() {
+        let (directory, cache) = test_directory("touched");
+        let path = directory.join("statistics-0.toml");
+        cache.store(&path, &StatisticsFile::from_toml_str(SOURCE).unwrap());
+
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+        set_modified(&path, modified + Duration::from_secs(10));
+        assert!(cache.load(&path).is_none());
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn resized_file_with_same_modification_time_invalidates_its_entry() {
+        let (directory, cache) = test_directory("resized");
+        let path = directory.join("statistics-0.toml");
+        cache.store(&path, &StatisticsFile::from_toml_str(SOURCE).unwrap());
+
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+        std::fs::write(&path, SOURCE.replace("seed = 0", "seed = 10")).unwrap();
+        set_modified(&path, modified);
+        assert!(cache.load(&path).is_none());
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
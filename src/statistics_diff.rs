@@ -0,0 +1,180 @@
+use std::fmt::Display;
+
+use lib_tsalign::a_star_aligner::alignment_result::AlignmentStatistics;
+
+/// The field-by-field difference between two [`AlignmentStatistics`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignmentStatisticsDiff {
+    pub fields: Vec<StatisticDiff>,
+}
+
+/// The difference of a single field of two [`AlignmentStatistics`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatisticDiff {
+    pub name: &'static str,
+    pub a: f64,
+    pub b: f64,
+    /// `b - a`.
+    pub absolute: f64,
+    /// `(b - a) / |a|`, or NaN if `a` is zero.
+    pub relative: f64,
+}
+
+/// Compare every statistic of `a` with the one of `b`.
+pub fn diff_statistics(
+    a: &AlignmentStatistics,
+    b: &AlignmentStatistics,
+) -> AlignmentStatisticsDiff {
+    let fields = [
+        ("cost", a.cost, b.cost),
+        ("cost_per_base", a.cost_per_base, b.cost_per_base),
+        ("duration_seconds", a.duration_seconds, b.duration_seconds),
+        ("opened_nodes", a.opened_nodes, b.opened_nodes),
+        ("closed_nodes", a.closed_nodes, b.closed_nodes),
+        (
+            "suboptimal_opened_nodes",
+            a.suboptimal_opened_nodes,
+            b.suboptimal_opened_nodes,
+        ),
+        (
+            "suboptimal_opened_nodes_ratio",
+            a.suboptimal_opened_nodes_ratio,
+            b.suboptimal_opened_nodes_ratio,
+        ),
+    ]
+    .into_iter()
+    .map(|(name, a, b)| {
+        let (a, b) = (a.raw(), b.raw());
+        let absolute = b - a;
+        StatisticDiff {
+            name,
+            a,
+            b,
+            absolute,
+            relative: if a == 0.0 {
+                f64::NAN
+            } else {
+                absolute / a.abs()
+            },
+        }
+    })
+    .collect();
+
+    AlignmentStatisticsDiff { fields }
+}
+
+impl AlignmentStatisticsDiff {
+    /// The field with the largest relative increase, since all statistics are better when lower.
+    pub fn largest_regression(&self) -> Option<&StatisticDiff> {
+        self.fields
+            .iter()
+            .filter(|field| field.relative > 0.0)
+            .max_by(|a, b| a.relative.total_cmp(&b.relative))
+    }
+}
+
+impl Display for AlignmentStatisticsDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name_width = self
+            .fields
+            .iter()
+            .map(|field| field.name.len())
+            .chain(["statistic".len()])
+            .max()
+            .unwrap();
+        let largest_regression = self.largest_regression().map(|field| field.name);
+
+        writeln!(
+            f,
+            "{:<name_width$}  {:>14}  {:>14}  {:>14}  {:>9}",
+            "statistic", "a", "b", "b - a", "relative"
+        )?;
+        for field in &self.fields {
+            write!(
+                f,
+                "{:<name_width$}  {:>14.6}  {:>14.6}  {:>+14.6}  {:>+8.1}%",
+                field.name,
+                field.a,
+                field.b,
+                field.absolute,
+                field.relative * 100.0
+            )?;
+            if Some(field.name) == largest_regression {
+                write!(f, "  <- largest regression")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noisy_float::types::R64;
+
+    use super::*;
+    use crate::statistics_file::StatisticsFile;
+
+    fn statistics(cost: f64, opened_nodes: f64, closed_nodes: f64) -> AlignmentStatistics {
+        let mut statistics =
+            StatisticsFile::from_toml_str(include_str!("../test_files/statistics-0.toml"))
+                .unwrap()
+                .statistics
+                .statistics;
+        statistics.cost = R64::new(cost);
+        statistics.opened_nodes = R64::new(opened_nodes);
+        statistics.closed_nodes = R64::new(closed_nodes);
+        statistics
+    }
+
+    fn field<'diff>(diff: &'diff AlignmentStatisticsDiff, name: &str) -> &'diff StatisticDiff {
+        diff.fields.iter().find(|field| field.name == name).unwrap()
+    }
+
+    #[test]
+    fn deltas_are_reported_per_field() {
+        let diff = diff_statistics(
+            &statistics(0.0, 200.0, 100.0),
+            &statistics(4.0, 300.0, 50.0),
+        );
+        assert_eq!(diff.fields.len(), 7);
+
+        let opened_nodes = field(&diff, "opened_nodes");
+        assert_eq!((opened_nodes.a, opened_nodes.b), (200.0, 300.0));
+        assert_eq!(opened_nodes.absolute, 100.0);
+        assert_eq!(opened_nodes.relative, 0.5);
+
+        let closed_nodes = field(&diff, "closed_nodes");
+        assert_eq!(closed_nodes.absolute, -50.0);
+        assert_eq!(closed_nodes.relative, -0.5);
+
+        // Relative to zero, the change is undefined.
+        let cost = field(&diff, "cost");
+        assert_eq!(cost.absolute, 4.0);
+        assert!(cost.relative.is_nan());
+
+        let unchanged = field(&diff, "duration_seconds");
+        assert_eq!(unchanged.absolute, 0.0);
+        assert_eq!(unchanged.relative, 0.0);
+    }
+
+    #[test]
+    fn largest_regression_is_the_largest_relative_increase() {
+        let diff = diff_statistics(
+            &statistics(0.0, 200.0, 100.0),
+            &statistics(4.0, 300.0, 50.0),
+        );
+        assert_eq!(diff.largest_regression().unwrap().name, "opened_nodes");
+
+        let improvement = diff_statistics(
+            &statistics(1.0, 200.0, 100.0),
+            &statistics(1.0, 100.0, 50.0),
+        );
+        assert_eq!(improvement.largest_regression(), None);
+        assert!(!improvement.to_string().contains("largest regression"));
+        assert!(diff.to_string().lines().any(
+            |line| line.starts_with("opened_nodes") && line.ends_with("<- largest regression")
+        ));
+    }
+}
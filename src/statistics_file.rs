@@ -1,10 +1,40 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use chrono::{DateTime, FixedOffset};
+use clap::ValueEnum;
 use lib_tsalign::a_star_aligner::{
     alignment_result::{AlignmentResult, AlignmentStatistics},
     template_switch_distance::AlignmentType,
 };
+use log::debug;
 use noisy_float::types::R64;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::stats;
+
+/// The statistics of a single alignment, as written by the experiment pipeline.
+///
+/// Both the alignment result and the parameters are flattened into the top level of the file.
+/// When deserialising, a flattened struct takes the keys it knows from the shared map,
+/// so a key used by both structs would silently be captured by the first one.
+/// Hence the top-level keys are reserved as follows:
+///
+///  * alignment result: `alignment`, `cost`, `cost_per_base`, `duration_seconds`, `opened_nodes`,
+///    `closed_nodes`, `suboptimal_opened_nodes`, `suboptimal_opened_nodes_ratio`;
+///  * parameters: `test_sequence_name`, `length`, `seed`, `alignment_config`, `ts_node_ord_strategy`,
+///    `timestamp` and the legacy alias `node_ordering_strategy`,
+///    as well as any further key ending in `_strategy`;
+///  * this struct: `optimal`, `memory_bytes`.
+///
+/// The parameter `cost` is not read from the file but copied from the alignment result
+/// in [`StatisticsFile::deserialisation_post_processing`], since it would collide with the `cost` of the result.
+///
+/// Newer tooling nests the statistics of the alignment result in a `[statistics]` table
+/// and the strategies in a `[strategies]` table.
+/// [`StatisticsFile::from_toml_str`] accepts both layouts by moving the nested keys to the top level.
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct StatisticsFile {
     #[serde(flatten)]
@@ -12,63 +42,339 @@ pub struct StatisticsFile {
 
     #[serde(flatten)]
     pub parameters: AlignmentParameters,
+
+    /// False if the aligner terminated without proving that the alignment is optimal.
+    /// Files that do not record this are assumed to be optimal.
+    #[serde(default = "default_optimal")]
+    pub optimal: bool,
+
+    /// The peak memory usage of the aligner, if recorded.
+    #[serde(default)]
+    pub memory_bytes: Option<u64>,
+
+    /// True if the runtime was clamped at a timeout, i.e. the actual runtime is unknown but at least the recorded one.
+    #[serde(skip)]
+    pub censored: bool,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub struct AlignmentParameters {
     pub test_sequence_name: String,
     pub length: usize,
+    /// Copied from the alignment result, since the key `cost` is reserved for it.
     #[serde(skip)]
     pub cost: u64,
     pub seed: u64,
+    /// Aligners without template switches, such as `fpa`, do not record the strategy fields.
+    #[serde(default = "not_applicable")]
     pub alignment_config: String,
+    /// Older tooling wrote this as `node_ordering_strategy`.
+    #[serde(default = "not_applicable", alias = "node_ordering_strategy")]
     pub ts_node_ord_strategy: String,
+    /// Further strategies of the aligner, i.e. the string values of keys ending in `_strategy`,
+    /// such that new strategies of the aligner are distinguished without changes to this struct.
+    #[serde(flatten, deserialize_with = "deserialize_strategies")]
+    pub strategies: BTreeMap<String, String>,
+    /// The time at which the run was started, as RFC3339 timestamp, if recorded.
+    #[serde(default)]
+    pub timestamp: Option<String>,
+    /// Further metadata of the run, such as the git commit or the host, read from a sidecar file.
+    #[serde(skip)]
+    pub extra: BTreeMap<String, String>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+/// Serialised without the contained files, since those are the input files and would dominate the size.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize)]
 pub struct MergedStatisticsFile {
     pub min_statistics: AlignmentStatistics,
     pub max_statistics: AlignmentStatistics,
     pub mean_statistics: AlignmentStatistics,
     pub median_statistics: AlignmentStatistics,
-    pub contained_statistics: Vec<AlignmentStatistics>,
+    /// The sample standard deviation of the statistics, which is zero for a single file.
+    pub std_statistics: AlignmentStatistics,
+    /// The statistics at further requested percentiles between zero and one.
+    #[serde(serialize_with = "serialize_percentile_statistics")]
+    pub percentile_statistics: BTreeMap<R64, AlignmentStatistics>,
+    /// The files that were merged into this file.
+    /// Whole files are kept rather than only their statistics, since metrics such as the template switch density
+    /// also depend on the parameters and alignment of a file.
+    #[serde(skip)]
+    pub contained_files: Vec<StatisticsFile>,
+    /// The number of files merged into the key bucket of this file.
+    /// Without key buckets, this is the number of files merged into this file.
+    pub bucket_file_count: usize,
 
     pub key: R64,
 }
 
+fn default_optimal() -> bool {
+    true
+}
+
+fn not_applicable() -> String {
+    "n/a".to_string()
+}
+
+/// Serialise the percentiles as strings, since not all formats support numeric keys.
+fn serialize_percentile_statistics<S: Serializer>(
+    percentile_statistics: &BTreeMap<R64, AlignmentStatistics>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(
+        percentile_statistics
+            .iter()
+            .map(|(percentile, statistics)| (percentile.to_string(), statistics)),
+    )
+}
+
+/// Takes all remaining top-level keys of a statistics file, and keeps the strategies among them.
+fn deserialize_strategies<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, String>, D::Error> {
+    Ok(BTreeMap::<String, toml::Value>::deserialize(deserializer)?
+        .into_iter()
+        .filter_map(|(key, value)| match value {
+            toml::Value::String(value) if key.ends_with("_strategy") => Some((key, value)),
+            _ => None,
+        })
+        .collect())
+}
+
+/// The tables in which newer tooling nests keys that older tooling wrote to the top level.
+const NESTED_TABLES: [&str; 2] = ["statistics", "strategies"];
+
+/// Move the keys of the nested tables to the top level, where keys already at the top level take precedence.
+///
+/// Returns true if the file used the nested layout.
+fn flatten_nested_layout(table: &mut toml::Table) -> bool {
+    let mut nested = false;
+    for nested_table in NESTED_TABLES {
+        if let Some(toml::Value::Table(nested_table)) = table.remove(nested_table) {
+            for (key, value) in nested_table {
+                table.entry(key).or_insert(value);
+            }
+            nested = true;
+        }
+    }
+    nested
+}
+
 impl StatisticsFile {
+    /// Parse a statistics file in either the flat or the nested layout.
+    pub fn from_toml_str(source: &str) -> Result<Self, toml::de::Error> {
+        let mut table = toml::from_str::<toml::Table>(source)?;
+        let layout = if flatten_nested_layout(&mut table) {
+            "nested"
+        } else {
+            "flat"
+        };
+        debug!("Detected the {layout} layout of a statistics file");
+
+        Ok(toml::Value::Table(table)
+            .try_into::<Self>()?
+            .deserialisation_post_processing())
+    }
+
     pub fn deserialisation_post_processing(mut self) -> Self {
         self.parameters.cost = self.statistics.statistics.cost.raw() as u64;
         self
     }
+
+    /// Recover a statistics file whose end was cut off, e.g. because its run was killed while writing it.
+    ///
+    /// The incomplete lines at the end are dropped until the rest parses.
+    /// If the parameters `test_sequence_name`, `length` and `seed` and all statistics are left,
+    /// the file is returned together with the fields that were cut off and defaulted, i.e. at most the alignment.
+    /// Otherwise, the file cannot be recovered and `None` is returned,
+    /// since made up statistics would distort the plotted values.
+    pub fn recover_truncated(source: &str) -> Option<(Self, Vec<&'static str>)> {
+        let mut lines: Vec<_> = source.lines().collect();
+        if !source.ends_with('\n') {
+            lines.pop();
+        }
+        let mut table = loop {
+            if let Ok(table) = toml::from_str::<toml::Table>(&lines.join("\n")) {
+                break table;
+            }
+            lines.pop()?;
+        };
+        flatten_nested_layout(&mut table);
+
+        let mut defaulted_fields = Vec::new();
+        if !table.contains_key("alignment") {
+            table.insert("alignment".to_string(), toml::Value::Array(Vec::new()));
+            defaulted_fields.push("alignment");
+        }
+        for field in [
+            "cost",
+            "cost_per_base",
+            "duration_seconds",
+            "opened_nodes",
+            "closed_nodes",
+            "suboptimal_opened_nodes",
+            "suboptimal_opened_nodes_ratio",
+        ] {
+            if !table.contains_key(field) {
+                return None;
+            }
+        }
+
+        let statistics_file = toml::Value::Table(table).try_into::<Self>().ok()?;
+        Some((
+            statistics_file.deserialisation_post_processing(),
+            defaulted_fields,
+        ))
+    }
+
+    /// Read the metadata from the sidecar of the statistics file at `path`, i.e. the file with extension `.meta.json`,
+    /// into the extra parameters.
+    /// Only the top-level fields with a string, number or boolean value are read.
+    ///
+    /// Returns false if there is no sidecar, and an error naming the sidecar if it cannot be read or parsed.
+    pub fn load_sidecar_metadata(&mut self, path: &Path) -> Result<bool, String> {
+        let sidecar_path = path.with_extension("meta.json");
+        let sidecar = match std::fs::read_to_string(&sidecar_path) {
+            Ok(sidecar) => sidecar,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(error) => {
+                return Err(format!(
+                    "Could not read metadata file {sidecar_path:?}: {error}"
+                ))
+            }
+        };
+        let metadata: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&sidecar)
+            .map_err(|error| {
+            format!("Could not parse metadata file {sidecar_path:?}: {error}")
+        })?;
+
+        for (key, value) in metadata {
+            let value = match value {
+                serde_json::Value::String(value) => value,
+                serde_json::Value::Number(value) => value.to_string(),
+                serde_json::Value::Bool(value) => value.to_string(),
+                serde_json::Value::Null
+                | serde_json::Value::Array(_)
+                | serde_json::Value::Object(_) => continue,
+            };
+            self.parameters.extra.insert(key, value);
+        }
+        Ok(true)
+    }
+
+    /// Clamp the runtime at `timeout` seconds, marking the file as censored if it reached the timeout.
+    pub fn apply_timeout(&mut self, timeout: f64) {
+        let duration_seconds = &mut self.statistics.statistics.duration_seconds;
+        if duration_seconds.raw() >= timeout {
+            *duration_seconds = R64::new(timeout);
+            self.censored = true;
+        }
+    }
+
+    /// The amount of operations in the alignment that match the given predicate.
+    /// Repeated operations are counted individually.
+    pub fn operation_count(&self, predicate: impl Fn(&AlignmentType) -> bool) -> usize {
+        self.statistics
+            .alignment
+            .iter()
+            .filter(|(_, alignment_type)| predicate(alignment_type))
+            .map(|(multiplicity, _)| multiplicity)
+            .sum()
+    }
+
+    /// The amount of template switches in the alignment.
+    pub fn template_switch_amount(&self) -> usize {
+        self.statistics
+            .alignment
+            .iter()
+            .filter(|(_, alignment_type)| {
+                matches!(alignment_type, AlignmentType::TemplateSwitchEntrance { .. })
+            })
+            .count()
+    }
+}
+
+impl AlignmentParameters {
+    /// The parsed [`timestamp`](Self::timestamp), if recorded as valid RFC3339 timestamp.
+    pub fn parsed_timestamp(&self) -> Option<DateTime<FixedOffset>> {
+        self.timestamp
+            .as_ref()
+            .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+    }
+
+    /// The names of the further [`strategies`](Self::strategies) whose value differs between the given parameter sets.
+    /// A strategy that is missing from some parameter sets counts as differing.
+    pub fn varying_strategies<'a>(
+        parameters: impl IntoIterator<Item = &'a AlignmentParameters>,
+    ) -> Vec<String> {
+        let mut parameters = parameters.into_iter();
+        let Some(first) = parameters.next() else {
+            return Vec::new();
+        };
+
+        let mut names: BTreeSet<_> = first.strategies.keys().cloned().collect();
+        let mut varying = BTreeSet::new();
+        for parameters in parameters {
+            names.extend(parameters.strategies.keys().cloned());
+            varying.extend(
+                names
+                    .iter()
+                    .filter(|name| first.strategies.get(*name) != parameters.strategies.get(*name))
+                    .cloned(),
+            );
+        }
+        varying.into_iter().collect()
+    }
 }
 
 impl MergedStatisticsFile {
-    pub fn from_statistics_files(key: R64, statistics_files: Vec<StatisticsFile>) -> Self {
+    /// Merge the given files, computing the statistics at the given percentiles besides the median.
+    pub fn from_statistics_files(
+        key: R64,
+        statistics_files: Vec<StatisticsFile>,
+        percentiles: &[R64],
+    ) -> Self {
         assert!(!statistics_files.is_empty());
+        if let Some(percentile) = percentiles
+            .iter()
+            .find(|percentile| !(0.0..=1.0).contains(&percentile.raw()))
+        {
+            panic!("Percentiles must be between zero and one, but found {percentile}.");
+        }
 
+        let statistics: Vec<_> = statistics_files
+            .iter()
+            .map(|file| file.statistics.statistics.clone())
+            .collect();
         let mut result = Self {
             min_statistics: AlignmentStatistics::max_value(),
             max_statistics: AlignmentStatistics::min_value(),
             mean_statistics: AlignmentStatistics::zero(),
             median_statistics: AlignmentStatistics::piecewise_percentile(
-                &statistics_files
-                    .iter()
-                    .map(|file| file.statistics.statistics.clone())
-                    .collect::<Vec<_>>(),
+                &statistics,
                 R64::new(0.5),
             ),
-            contained_statistics: Default::default(),
+            std_statistics: piecewise_standard_deviation(&statistics),
+            percentile_statistics: percentiles
+                .iter()
+                .map(|&percentile| {
+                    (
+                        percentile,
+                        AlignmentStatistics::piecewise_percentile(&statistics, percentile),
+                    )
+                })
+                .collect(),
+            contained_files: Default::default(),
+            bucket_file_count: statistics_files.len(),
 
             key,
         };
 
-        for statistics in &statistics_files {
-            let statistics = &statistics.statistics.statistics;
+        for file in &statistics_files {
+            let statistics = &file.statistics.statistics;
             result.min_statistics = result.min_statistics.piecewise_min(statistics);
             result.max_statistics = result.max_statistics.piecewise_max(statistics);
             result.mean_statistics = result.mean_statistics.piecewise_add(statistics);
-            result.contained_statistics.push(statistics.clone());
+            result.contained_files.push(file.clone());
         }
 
         result.mean_statistics = result
@@ -78,3 +384,382 @@ impl MergedStatisticsFile {
         result
     }
 }
+
+/// The piecewise sample standard deviation of the given statistics, which is zero for a single statistic.
+fn piecewise_standard_deviation(statistics: &[AlignmentStatistics]) -> AlignmentStatistics {
+    let standard_deviation = |field: fn(&AlignmentStatistics) -> R64| {
+        let values: Vec<_> = statistics
+            .iter()
+            .map(|statistics| field(statistics).raw())
+            .collect();
+        R64::new(stats::mean_and_standard_deviation(&values).1)
+    };
+
+    let mut result = AlignmentStatistics::zero();
+    result.cost = standard_deviation(|statistics| statistics.cost);
+    result.cost_per_base = standard_deviation(|statistics| statistics.cost_per_base);
+    result.duration_seconds = standard_deviation(|statistics| statistics.duration_seconds);
+    result.opened_nodes = standard_deviation(|statistics| statistics.opened_nodes);
+    result.closed_nodes = standard_deviation(|statistics| statistics.closed_nodes);
+    result.suboptimal_opened_nodes =
+        standard_deviation(|statistics| statistics.suboptimal_opened_nodes);
+    result.suboptimal_opened_nodes_ratio =
+        standard_deviation(|statistics| statistics.suboptimal_opened_nodes_ratio);
+    result
+}
+
+/// The name of a field of [`AlignmentParameters`].
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum AlignmentParameterName {
+    TestSequenceName,
+    Length,
+    Cost,
+    Seed,
+    AlignmentConfig,
+    TsNodeOrdStrategy,
+}
+
+impl AlignmentParameterName {
+    pub const ALL: [Self; 6] = [
+        Self::TestSequenceName,
+        Self::Length,
+        Self::Cost,
+        Self::Seed,
+        Self::AlignmentConfig,
+        Self::TsNodeOrdStrategy,
+    ];
+
+    /// The name of the parameter, as used in statistics files.
+    pub fn identifier(&self) -> &'static str {
+        match self {
+            Self::TestSequenceName => "test_sequence_name",
+            Self::Length => "length",
+            Self::Cost => "cost",
+            Self::Seed => "seed",
+            Self::AlignmentConfig => "alignment_config",
+            Self::TsNodeOrdStrategy => "ts_node_ord_strategy",
+        }
+    }
+
+    /// Returns the value of this parameter as string.
+    pub fn value(&self, parameters: &AlignmentParameters) -> String {
+        match self {
+            Self::TestSequenceName => parameters.test_sequence_name.clone(),
+            Self::Length => parameters.length.to_string(),
+            Self::Cost => parameters.cost.to_string(),
+            Self::Seed => parameters.seed.to_string(),
+            Self::AlignmentConfig => parameters.alignment_config.clone(),
+            Self::TsNodeOrdStrategy => parameters.ts_node_ord_strategy.clone(),
+        }
+    }
+
+    /// Returns the parameters that have the same value in all given parameter sets, together with that value.
+    pub fn constant_parameters<'a>(
+        parameters: impl IntoIterator<Item = &'a AlignmentParameters>,
+    ) -> Vec<(Self, String)> {
+        let mut parameters = parameters.into_iter();
+        let Some(first) = parameters.next() else {
+            return Vec::new();
+        };
+
+        let mut constant_parameters: Vec<_> = Self::ALL
+            .into_iter()
+            .map(|name| (name, name.value(first)))
+            .collect();
+        for parameters in parameters {
+            constant_parameters.retain(|(name, value)| &name.value(parameters) == value);
+        }
+        constant_parameters
+    }
+
+    /// Returns the value of this parameter as a number, if it is numeric.
+    pub fn numeric_value(&self, parameters: &AlignmentParameters) -> Option<f64> {
+        match self {
+            Self::Length => Some(parameters.length as f64),
+            Self::Cost => Some(parameters.cost as f64),
+            Self::Seed => Some(parameters.seed as f64),
+            Self::TestSequenceName | Self::AlignmentConfig | Self::TsNodeOrdStrategy => None,
+        }
+    }
+
+    /// Returns the value of this parameter formatted for use in a group name.
+    pub fn format_value(&self, parameters: &AlignmentParameters) -> String {
+        match self {
+            Self::TestSequenceName => parameters.test_sequence_name.clone(),
+            Self::Length => format!("len {}", parameters.length),
+            Self::Cost => format!("cost {}", parameters.cost),
+            Self::Seed => format!("seed {}", parameters.seed),
+            Self::AlignmentConfig => parameters.alignment_config.clone(),
+            Self::TsNodeOrdStrategy => parameters.ts_node_ord_strategy.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for AlignmentParameterName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::TestSequenceName => "Test Sequence",
+                Self::Length => "Length",
+                Self::Cost => "Alignment Cost",
+                Self::Seed => "Seed",
+                Self::AlignmentConfig => "Alignment Config",
+                Self::TsNodeOrdStrategy => "Node Ordering Strategy",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPLETE: &str = include_str!("../test_files/statistics-0.toml");
+    const NESTED: &str = include_str!("../test_files/nested-statistics-0.toml");
+
+    #[test]
+    fn reserved_keys_are_read_into_their_structs() {
+        let statistics_file = StatisticsFile::from_toml_str(COMPLETE).unwrap();
+        let statistics = &statistics_file.statistics.statistics;
+        assert_eq!(statistics_file.statistics.alignment.len(), 5);
+        assert_eq!(statistics.cost.raw(), 0.0);
+        assert_eq!(statistics.duration_seconds.raw(), 0.000546915);
+        assert_eq!(statistics.opened_nodes.raw(), 2137.0);
+        assert_eq!(statistics.closed_nodes.raw(), 711.0);
+        assert_eq!(statistics.suboptimal_opened_nodes.raw(), 82.0);
+
+        let parameters = &statistics_file.parameters;
+        assert_eq!(parameters.test_sequence_name, "single_ts_0");
+        assert_eq!(parameters.length, 100);
+        assert_eq!(parameters.seed, 0);
+        assert_eq!(parameters.alignment_config, "default");
+        assert_eq!(parameters.ts_node_ord_strategy, "anti-diagonal");
+
+        assert!(statistics_file.optimal);
+        assert_eq!(statistics_file.memory_bytes, None);
+
+        // The parameter `cost` is copied from the alignment result.
+        let statistics_file =
+            StatisticsFile::from_toml_str(&COMPLETE.replace("cost = 0\n", "cost = 7\n")).unwrap();
+        assert_eq!(statistics_file.statistics.statistics.cost.raw(), 7.0);
+        assert_eq!(statistics_file.parameters.cost, 7);
+    }
+
+    #[test]
+    fn nested_layout_equals_flat_layout() {
+        let flat = StatisticsFile::from_toml_str(COMPLETE).unwrap();
+        let nested = StatisticsFile::from_toml_str(NESTED).unwrap();
+        assert_eq!(nested, flat);
+        assert_eq!(nested.parameters.ts_node_ord_strategy, "anti-diagonal");
+    }
+
+    #[test]
+    fn legacy_node_ordering_strategy_equals_current_name() {
+        let current = StatisticsFile::from_toml_str(COMPLETE).unwrap();
+        let legacy = StatisticsFile::from_toml_str(
+            &COMPLETE.replace("ts_node_ord_strategy =", "node_ordering_strategy ="),
+        )
+        .unwrap();
+        assert_eq!(legacy, current);
+        assert_eq!(legacy.parameters.ts_node_ord_strategy, "anti-diagonal");
+    }
+
+    #[test]
+    fn serialised_file_parses_to_the_same_file() {
+        for source in [COMPLETE, NESTED] {
+            let statistics_file = StatisticsFile::from_toml_str(source).unwrap();
+            let serialised = toml::to_string(&statistics_file).unwrap();
+            assert_eq!(
+                StatisticsFile::from_toml_str(&serialised).unwrap(),
+                statistics_file
+            );
+        }
+    }
+
+    #[test]
+    fn top_level_keys_take_precedence_over_nested_keys() {
+        let source = NESTED.replace("seed = 0\n", "seed = 0\ncost_per_base = 0.5\n");
+        let statistics_file = StatisticsFile::from_toml_str(&source).unwrap();
+        assert_eq!(
+            statistics_file.statistics.statistics.cost_per_base.raw(),
+            0.5
+        );
+    }
+
+    #[test]
+    fn recover_truncated_defaults_cut_off_parameters() {
+        let truncated = &COMPLETE[..COMPLETE.find("anti-diagonal").unwrap()];
+        let (statistics_file, defaulted_fields) =
+            StatisticsFile::recover_truncated(truncated).unwrap();
+        assert!(defaulted_fields.is_empty());
+        assert_eq!(statistics_file.parameters.test_sequence_name, "single_ts_0");
+        assert_eq!(statistics_file.parameters.ts_node_ord_strategy, "n/a");
+        assert_eq!(
+            statistics_file.statistics.statistics.opened_nodes.raw(),
+            2137.0
+        );
+    }
+
+    #[test]
+    fn recover_truncated_rejects_cut_off_statistics() {
+        let source = "\
+test_sequence_name = \"single_ts_0\"
+length = 100
+seed = 0
+alignment = []
+cost = 12
+cost_per_base = 0.12
+duration_seconds = 0.0005
+opened_nodes = 21";
+        assert!(StatisticsFile::recover_truncated(source).is_none());
+    }
+
+    #[test]
+    fn sidecar_errors_name_the_sidecar() {
+        let directory = std::env::temp_dir().join(format!(
+            "template-switch-statistics-sidecar-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+        let mut statistics_file = StatisticsFile::from_toml_str(COMPLETE).unwrap();
+
+        let path = directory.join("statistics-0.toml");
+        assert_eq!(statistics_file.load_sidecar_metadata(&path), Ok(false));
+
+        std::fs::write(
+            directory.join("statistics-0.meta.json"),
+            r#"{"host": "alpha", "cores": 8, "tags": ["x"]}"#,
+        )
+        .unwrap();
+        assert_eq!(statistics_file.load_sidecar_metadata(&path), Ok(true));
+        assert_eq!(
+            statistics_file.parameters.extra,
+            BTreeMap::from([
+                ("cores".to_string(), "8".to_string()),
+                ("host".to_string(), "alpha".to_string()),
+            ])
+        );
+
+        std::fs::write(directory.join("statistics-0.meta.json"), "{\"host\": ").unwrap();
+        let error = statistics_file.load_sidecar_metadata(&path).unwrap_err();
+        assert!(error.contains("statistics-0.meta.json"), "{error}");
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn invalid_timestamps_are_not_parsed() {
+        let mut parameters = AlignmentParameters::default();
+        assert_eq!(parameters.parsed_timestamp(), None);
+        parameters.timestamp = Some("yesterday".to_string());
+        assert_eq!(parameters.parsed_timestamp(), None);
+        parameters.timestamp = Some("2024-05-01T12:30:00+02:00".to_string());
+        assert_eq!(
+            parameters.parsed_timestamp().unwrap().to_rfc3339(),
+            "2024-05-01T12:30:00+02:00"
+        );
+    }
+
+    #[test]
+    fn merged_files_keep_the_metrics_of_their_files() {
+        let files: Vec<_> = [0, 1]
+            .into_iter()
+            .map(|seed| {
+                let mut file = StatisticsFile::from_toml_str(COMPLETE).unwrap();
+                file.parameters.seed = seed;
+                file.parameters.length *= (seed + 1) as usize;
+                file
+            })
+            .collect();
+        let merged = MergedStatisticsFile::from_statistics_files(R64::new(0.0), files.clone(), &[]);
+        assert_eq!(merged.contained_files, files);
+        for metric in [
+            crate::metric::Metric::OpenedNodes,
+            crate::metric::Metric::TsDensity,
+        ] {
+            let values: Vec<_> = merged
+                .contained_files
+                .iter()
+                .map(|file| metric.value(file))
+                .collect();
+            assert_eq!(
+                values,
+                files
+                    .iter()
+                    .map(|file| metric.value(file))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    fn files_with_opened_nodes(opened_nodes: &[f64]) -> Vec<StatisticsFile> {
+        opened_nodes
+            .iter()
+            .enumerate()
+            .map(|(seed, &opened_nodes)| {
+                let mut file = StatisticsFile::from_toml_str(COMPLETE).unwrap();
+                file.parameters.seed = seed as u64;
+                file.statistics.statistics.opened_nodes = R64::new(opened_nodes);
+                file
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merged_files_keep_the_requested_percentiles() {
+        let merged = MergedStatisticsFile::from_statistics_files(
+            R64::new(0.0),
+            files_with_opened_nodes(&[300.0, 100.0, 200.0]),
+            &[R64::new(1.0), R64::new(0.0)],
+        );
+        let opened_nodes: Vec<_> = merged
+            .percentile_statistics
+            .iter()
+            .map(|(percentile, statistics)| (percentile.raw(), statistics.opened_nodes.raw()))
+            .collect();
+        assert_eq!(opened_nodes, vec![(0.0, 100.0), (1.0, 300.0)]);
+
+        let serialised = serde_json::to_value(&merged).unwrap();
+        assert_eq!(
+            serialised["percentile_statistics"]["1"]["opened_nodes"],
+            serde_json::json!(300.0)
+        );
+    }
+
+    #[test]
+    fn merged_files_keep_the_standard_deviation() {
+        let merged = MergedStatisticsFile::from_statistics_files(
+            R64::new(0.0),
+            files_with_opened_nodes(&[100.0, 200.0, 300.0]),
+            &[],
+        );
+        assert_eq!(merged.std_statistics.opened_nodes.raw(), 100.0);
+        assert_eq!(merged.std_statistics.closed_nodes.raw(), 0.0);
+        assert!(
+            serde_json::to_value(&merged).unwrap()["std_statistics"]["opened_nodes"].is_number()
+        );
+
+        let single = MergedStatisticsFile::from_statistics_files(
+            R64::new(0.0),
+            files_with_opened_nodes(&[100.0]),
+            &[],
+        );
+        assert_eq!(single.std_statistics.opened_nodes.raw(), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Percentiles must be between zero and one")]
+    fn percentiles_above_one_are_rejected() {
+        MergedStatisticsFile::from_statistics_files(
+            R64::new(0.0),
+            files_with_opened_nodes(&[100.0]),
+            &[R64::new(90.0)],
+        );
+    }
+}
@@ -0,0 +1,268 @@
+/// The Pearson correlation coefficient of the given pairs.
+///
+/// Returns NaN if there are fewer than two pairs or one of the coordinates is constant,
+/// since then the correlation is undefined.
+pub fn pearson_correlation(pairs: &[(f64, f64)]) -> f64 {
+    if pairs.len() < 2 {
+        return f64::NAN;
+    }
+
+    let amount = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / amount;
+    let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / amount;
+    let (covariance, variance_x, variance_y) = pairs.iter().fold(
+        (0.0, 0.0, 0.0),
+        |(covariance, variance_x, variance_y), (x, y)| {
+            let (dx, dy) = (x - mean_x, y - mean_y);
+            (
+                covariance + dx * dy,
+                variance_x + dx * dx,
+                variance_y + dy * dy,
+            )
+        },
+    );
+
+    if variance_x == 0.0 || variance_y == 0.0 {
+        f64::NAN
+    } else {
+        covariance / (variance_x * variance_y).sqrt()
+    }
+}
+
+/// The Spearman rank correlation coefficient of the given pairs, i.e. the Pearson correlation of their ranks.
+/// Tied values receive the mean of their ranks.
+pub fn spearman_correlation(pairs: &[(f64, f64)]) -> f64 {
+    let x_ranks = ranks(pairs.iter().map(|(x, _)| *x));
+    let y_ranks = ranks(pairs.iter().map(|(_, y)| *y));
+    pearson_correlation(&x_ranks.into_iter().zip(y_ranks).collect::<Vec<_>>())
+}
+
+/// The given percentile (between 0 and 100) of the given sorted values,
+/// linearly interpolated between the closest ranks.
+///
+/// Returns NaN if there are no values.
+pub fn percentile(sorted_values: &[f64], percentile: f64) -> f64 {
+    if sorted_values.is_empty() {
+        return f64::NAN;
+    }
+
+    let rank = percentile / 100.0 * (sorted_values.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    let fraction = rank - lower as f64;
+    sorted_values[lower] * (1.0 - fraction) + sorted_values[upper] * fraction
+}
+
+/// The mean and the sample standard deviation of the given values.
+///
+/// A single value has a standard deviation of zero, and without values both are NaN.
+pub fn mean_and_standard_deviation(values: &[f64]) -> (f64, f64) {
+    let amount = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / amount;
+    if values.len() < 2 {
+        return (mean, if values.is_empty() { f64::NAN } else { 0.0 });
+    }
+
+    let variance = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / (amount - 1.0);
+    (mean, variance.sqrt())
+}
+
+/// The relative standard error of the mean of the given values, i.e. `std / (mean * sqrt(n))`
+/// with the sample standard deviation `std`.
+///
+/// Returns NaN if there are fewer than two values or the mean is zero, since then it is undefined.
+pub fn relative_standard_error(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return f64::NAN;
+    }
+
+    let (mean, standard_deviation) = mean_and_standard_deviation(values);
+    if mean == 0.0 {
+        return f64::NAN;
+    }
+    standard_deviation / (mean.abs() * (values.len() as f64).sqrt())
+}
+
+/// The points that are not dominated by any other point when minimising both coordinates,
+/// sorted by ascending first and hence descending second coordinate.
+/// Of several equal points, only one is part of the front.
+pub fn pareto_front(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+
+    let mut front: Vec<(f64, f64)> = Vec::new();
+    for point in points {
+        // All points of the front have a lower or equal first coordinate, so only a lower second coordinate is not dominated.
+        if front.last().map_or(true, |last| point.1 < last.1) {
+            front.push(point);
+        }
+    }
+    front
+}
+
+/// Half the width of the approximate 95% confidence interval of the median,
+/// as used for the notches of boxplots (McGill et al., 1978).
+pub fn median_confidence_half_width(
+    lower_quartile: f64,
+    upper_quartile: f64,
+    sample_size: usize,
+) -> f64 {
+    1.57 * (upper_quartile - lower_quartile) / (sample_size as f64).sqrt()
+}
+
+/// The area under the piecewise linear curve through the given points, which must be sorted by their first coordinate.
+pub fn trapezoidal_area(points: &[(f64, f64)]) -> f64 {
+    points
+        .windows(2)
+        .map(|window| {
+            let [(x1, y1), (x2, y2)] = [window[0], window[1]];
+            (x2 - x1) * (y1 + y2) * 0.5
+        })
+        .sum()
+}
+
+/// The slope and intercept of the least squares line through the given points.
+///
+/// Returns `None` if there are fewer than two distinct first coordinates, since then the line is not unique.
+pub fn linear_regression(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    let amount = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / amount;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / amount;
+    let (covariance, variance_x) =
+        points
+            .iter()
+            .fold((0.0, 0.0), |(covariance, variance_x), (x, y)| {
+                let dx = x - mean_x;
+                (covariance + dx * (y - mean_y), variance_x + dx * dx)
+            });
+
+    if points.len() < 2 || variance_x == 0.0 {
+        None
+    } else {
+        let slope = covariance / variance_x;
+        Some((slope, mean_y - slope * mean_x))
+    }
+}
+
+/// The one-based ranks of the given values in their order, where tied values receive the mean of their ranks.
+pub fn ranks(values: impl IntoIterator<Item = f64>) -> Vec<f64> {
+    let values: Vec<_> = values.into_iter().collect();
+    let mut order: Vec<_> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let end = start
+            + order[start..]
+                .iter()
+                .take_while(|&&index| values[index] == values[order[start]])
+                .count();
+        // Ranks are one-based, and ties share the mean of their ranks.
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &index in &order[start..end] {
+            ranks[index] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pareto_front_drops_dominated_points() {
+        let points = [(1.0, 5.0), (2.0, 3.0), (3.0, 4.0), (4.0, 1.0), (5.0, 1.0)];
+        assert_eq!(
+            pareto_front(&points),
+            vec![(1.0, 5.0), (2.0, 3.0), (4.0, 1.0)]
+        );
+    }
+
+    #[test]
+    fn pareto_front_keeps_one_of_equal_points() {
+        let points = [(2.0, 2.0), (1.0, 3.0), (2.0, 2.0), (1.0, 3.0)];
+        assert_eq!(pareto_front(&points), vec![(1.0, 3.0), (2.0, 2.0)]);
+    }
+
+    #[test]
+    fn pareto_front_drops_points_dominated_in_one_coordinate_only() {
+        // Equal in one coordinate and worse in the other is dominated.
+        let points = [(1.0, 2.0), (1.0, 3.0), (2.0, 2.0)];
+        assert_eq!(pareto_front(&points), vec![(1.0, 2.0)]);
+    }
+
+    #[test]
+    fn pareto_front_of_nothing_is_empty() {
+        assert!(pareto_front(&[]).is_empty());
+    }
+
+    #[test]
+    fn percentile_interpolates_between_ranks() {
+        let values = [1.0, 2.0, 4.0, 8.0];
+        assert_eq!(percentile(&values, 0.0), 1.0);
+        assert_eq!(percentile(&values, 100.0), 8.0);
+        assert_eq!(percentile(&values, 50.0), 3.0);
+        assert_eq!(percentile(&values, 25.0), 1.75);
+        assert_eq!(percentile(&[5.0], 75.0), 5.0);
+        assert!(percentile(&[], 50.0).is_nan());
+    }
+
+    #[test]
+    fn mean_and_sample_standard_deviation() {
+        let (mean, standard_deviation) =
+            mean_and_standard_deviation(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert_eq!(mean, 5.0);
+        assert!((standard_deviation - (32.0f64 / 7.0).sqrt()).abs() < 1e-12);
+        assert_eq!(mean_and_standard_deviation(&[3.0]), (3.0, 0.0));
+        let (mean, standard_deviation) = mean_and_standard_deviation(&[]);
+        assert!(mean.is_nan() && standard_deviation.is_nan());
+    }
+
+    #[test]
+    fn ties_share_the_mean_rank() {
+        assert_eq!(
+            ranks([3.0, 1.0, 4.0, 1.0, 5.0]),
+            vec![3.0, 1.5, 4.0, 1.5, 5.0]
+        );
+        assert_eq!(ranks([2.0, 2.0, 2.0]), vec![2.0, 2.0, 2.0]);
+        assert!(ranks([]).is_empty());
+    }
+
+    #[test]
+    fn linear_regression_of_a_line() {
+        let points = [(0.0, 1.0), (1.0, 3.0), (2.0, 5.0), (3.0, 7.0)];
+        assert_eq!(linear_regression(&points), Some((2.0, 1.0)));
+        // The least squares line through symmetric noise is the line without it.
+        let noisy = [(0.0, 0.0), (0.0, 2.0), (2.0, 2.0), (2.0, 4.0)];
+        assert_eq!(linear_regression(&noisy), Some((1.0, 1.0)));
+    }
+
+    #[test]
+    fn linear_regression_needs_two_distinct_keys() {
+        assert_eq!(linear_regression(&[]), None);
+        assert_eq!(linear_regression(&[(1.0, 2.0)]), None);
+        assert_eq!(linear_regression(&[(1.0, 2.0), (1.0, 3.0)]), None);
+    }
+
+    #[test]
+    fn median_confidence_half_width_of_known_quartiles() {
+        // 1.57 * IQR / sqrt(n) with an IQR of 4 and 16 samples.
+        assert!((median_confidence_half_width(2.0, 6.0, 16) - 1.57).abs() < 1e-12);
+        // The notch narrows with more samples.
+        assert!(median_confidence_half_width(2.0, 6.0, 64) < 1.57);
+    }
+
+    #[test]
+    fn trapezoidal_area_under_a_piecewise_linear_curve() {
+        let points = [(0.0, 0.0), (1.0, 2.0), (3.0, 2.0), (4.0, 0.0)];
+        assert_eq!(trapezoidal_area(&points), 6.0);
+        assert_eq!(trapezoidal_area(&[(1.0, 5.0)]), 0.0);
+        assert_eq!(trapezoidal_area(&[]), 0.0);
+    }
+}
@@ -0,0 +1,279 @@
+use std::{fmt::Display, fs::File, io::Read, path::Path};
+
+use clap::ValueEnum;
+use log::{info, Level};
+use plotters::{
+    chart::SeriesLabelPosition,
+    style::{Color, HSLColor, RGBColor, BLUE, CYAN, GREEN, MAGENTA, RED},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    colormap::Colormap,
+    diagnostics::{self, DiagnosticCategory},
+};
+
+/// The amount of evenly spaced hues up to which the colors of groups can still be told apart.
+const MAX_DISTINCT_HUES: usize = 20;
+
+/// Styling defaults shared by all plots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// The colors assigned to the groups, in order.
+    pub palette: Vec<ThemeColor>,
+    pub font_family: String,
+    pub caption_font_size: u32,
+    pub annotation_font_size: u32,
+    /// The background of the plot. If unset, the background is transparent.
+    pub background: Option<ThemeColor>,
+    /// The axes at whose labels grid lines are drawn.
+    pub grid: GridLines,
+    pub grid_style: GridStyle,
+    pub legend_position: LegendPosition,
+    /// The fraction of a key slot covered by the boxes of all groups at that key.
+    pub box_width_factor: f64,
+    /// The whisker styles assigned to the groups, in order, paralleling the palette.
+    pub whisker_styles: Vec<WhiskerStyle>,
+    /// If set, the groups are colored by evenly spaced samples of this colormap instead of the palette.
+    #[serde(skip)]
+    pub colormap: Option<Colormap>,
+}
+
+/// An RGB color, serialised as hex string `#rrggbb`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ThemeColor(pub u8, pub u8, pub u8);
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum GridLines {
+    None,
+    X,
+    Y,
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum GridStyle {
+    Solid,
+    Dashed,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum LegendPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// The style of the whiskers of a box, to distinguish overlapping boxes of different groups.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum WhiskerStyle {
+    /// Caps as wide as the box.
+    Full,
+    /// Caps half as wide as the box.
+    Half,
+    /// No caps.
+    Bare,
+    /// Caps as wide as the box, drawn with thicker lines.
+    Thick,
+}
+
+impl Theme {
+    pub fn from_file(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        info!("Loading theme {path:?}");
+
+        let mut buffer = String::new();
+        File::open(path)
+            .unwrap_or_else(|error| panic!("Could not open theme {path:?}: {error}"))
+            .read_to_string(&mut buffer)
+            .unwrap_or_else(|error| panic!("Could not read theme {path:?}: {error}"));
+        let theme: Self = toml::from_str(&buffer)
+            .unwrap_or_else(|error| panic!("Could not parse theme {path:?}: {error}"));
+
+        if theme.palette.is_empty() {
+            panic!("The palette of theme {path:?} is empty.");
+        }
+        if !(theme.box_width_factor > 0.0 && theme.box_width_factor <= 1.0) {
+            panic!("The box width factor of theme {path:?} must be in (0, 1].");
+        }
+        theme
+    }
+
+    /// The colors of the given amount of groups, in order.
+    /// If a colormap is set, it is sampled once per group.
+    /// Otherwise, if the palette has fewer colors than there are groups, then all groups are colored with evenly spaced hues instead,
+    /// such that every group is drawn.
+    pub fn group_colors(&self, group_amount: usize) -> Vec<RGBColor> {
+        if let Some(colormap) = self.colormap {
+            return colormap
+                .palette(group_amount)
+                .iter()
+                .map(ThemeColor::rgb)
+                .collect();
+        }
+        if group_amount <= self.palette.len() {
+            return self.palette.iter().map(ThemeColor::rgb).collect();
+        }
+
+        if group_amount > MAX_DISTINCT_HUES {
+            diagnostics::report(
+                Level::Warn,
+                DiagnosticCategory::IndistinctColors,
+                "There are too many groups for their colors to be told apart",
+                [("groups", group_amount), ("max", MAX_DISTINCT_HUES)],
+            );
+        } else {
+            info!(
+                "The palette has only {} colors for {group_amount} groups, using evenly spaced hues instead",
+                self.palette.len()
+            );
+        }
+        (0..group_amount)
+            .map(|index| {
+                let (r, g, b) = HSLColor(index as f64 / group_amount as f64, 0.8, 0.45)
+                    .to_backend_color()
+                    .rgb;
+                RGBColor(r, g, b)
+            })
+            .collect()
+    }
+
+    /// The whisker style of the group with the given index.
+    /// The styles are repeated if there are more groups than styles.
+    pub fn whisker_style(&self, group_index: usize) -> WhiskerStyle {
+        if self.whisker_styles.is_empty() {
+            WhiskerStyle::Full
+        } else {
+            self.whisker_styles[group_index % self.whisker_styles.len()]
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            palette: [RED, GREEN, BLUE, MAGENTA, CYAN, RGBColor(10, 100, 10)]
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            font_family: "sans-serif".to_string(),
+            caption_font_size: 24,
+            annotation_font_size: 10,
+            background: None,
+            grid: GridLines::Y,
+            grid_style: GridStyle::Solid,
+            legend_position: LegendPosition::BottomRight,
+            box_width_factor: 0.7,
+            whisker_styles: Vec::new(),
+            colormap: None,
+        }
+    }
+}
+
+impl GridLines {
+    pub fn x(&self) -> bool {
+        matches!(self, Self::X | Self::Both)
+    }
+
+    pub fn y(&self) -> bool {
+        matches!(self, Self::Y | Self::Both)
+    }
+}
+
+impl WhiskerStyle {
+    /// The width of the whisker caps relative to the width of the box.
+    pub fn whisker_width(&self) -> f64 {
+        match self {
+            Self::Full | Self::Thick => 1.0,
+            Self::Half => 0.5,
+            Self::Bare => 0.0,
+        }
+    }
+
+    pub fn stroke_width(&self) -> u32 {
+        match self {
+            Self::Full | Self::Half | Self::Bare => 1,
+            Self::Thick => 2,
+        }
+    }
+}
+
+impl ThemeColor {
+    pub fn rgb(&self) -> RGBColor {
+        RGBColor(self.0, self.1, self.2)
+    }
+}
+
+impl From<RGBColor> for ThemeColor {
+    fn from(color: RGBColor) -> Self {
+        Self(color.0, color.1, color.2)
+    }
+}
+
+impl TryFrom<String> for ThemeColor {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let hex = value
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6 && hex.is_ascii())
+            .ok_or_else(|| format!("Colors must be given as #rrggbb, but found {value:?}"))?;
+        let channel = |index: usize| {
+            u8::from_str_radix(&hex[index..index + 2], 16)
+                .map_err(|error| format!("Invalid color {value:?}: {error}"))
+        };
+
+        Ok(Self(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+impl From<ThemeColor> for String {
+    fn from(color: ThemeColor) -> Self {
+        color.to_string()
+    }
+}
+
+impl Display for ThemeColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl From<LegendPosition> for SeriesLabelPosition {
+    fn from(position: LegendPosition) -> Self {
+        match position {
+            LegendPosition::TopLeft => SeriesLabelPosition::UpperLeft,
+            LegendPosition::TopRight => SeriesLabelPosition::UpperRight,
+            LegendPosition::BottomLeft => SeriesLabelPosition::LowerLeft,
+            LegendPosition::BottomRight => SeriesLabelPosition::LowerRight,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colormaps_are_sampled_once_per_group() {
+        let theme = Theme {
+            colormap: Some(Colormap::Viridis),
+            ..Default::default()
+        };
+        for group_amount in [1, 3, theme.palette.len() + 4] {
+            let colors = theme.group_colors(group_amount);
+            assert_eq!(colors.len(), group_amount);
+            assert_eq!(colors[0], Colormap::Viridis.color(0.0));
+        }
+        let colors = theme.group_colors(3);
+        assert_eq!(colors[1], Colormap::Viridis.color(0.5));
+        assert_eq!(colors[2], Colormap::Viridis.color(1.0));
+    }
+}